@@ -5,7 +5,7 @@ use air_pass::Pass;
 
 use clap::{Args, ValueEnum};
 use miden_diagnostics::{
-    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler, Severity,
 };
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -40,6 +40,108 @@ pub struct Transpile {
         help = "Defines the target language, defaults to Winterfell"
     )]
     target: Option<Target>,
+
+    #[arg(
+        short = 'I',
+        long = "include",
+        value_name = "DIR",
+        help = "Adds a directory to search for imported modules, may be given multiple times. Directories in the AIRSCRIPT_PATH environment variable are searched after these"
+    )]
+    include: Vec<PathBuf>,
+
+    #[arg(
+        long = "check-selectors",
+        help = "Warns when the selectors of an `enf match` statement cannot be proven to be binary and mutually exclusive"
+    )]
+    check_selectors: bool,
+
+    #[command(flatten)]
+    winterfell: WinterfellArgs,
+}
+
+/// Options of the Winterfell backend
+#[derive(Args)]
+#[command(next_help_heading = "Winterfell options")]
+struct WinterfellArgs {
+    #[arg(
+        long = "air-crate",
+        value_name = "PATH",
+        help = "Path of the crate to import the Air trait and its associated types from, defaults to winter_air"
+    )]
+    air_crate: Option<String>,
+
+    #[arg(
+        long = "math-crate",
+        value_name = "PATH",
+        help = "Path of the crate to import the field types from, defaults to winter_math"
+    )]
+    math_crate: Option<String>,
+
+    #[arg(
+        long = "utils-crate",
+        value_name = "PATH",
+        help = "Path of the crate to import the serialization traits from, defaults to winter_utils"
+    )]
+    utils_crate: Option<String>,
+
+    #[arg(
+        long = "air-name",
+        value_name = "NAME",
+        help = "Name of the generated Air struct, defaults to the name of the AirScript program"
+    )]
+    air_name: Option<String>,
+
+    #[arg(
+        long = "public-inputs-name",
+        value_name = "NAME",
+        help = "Name of the generated public inputs struct, defaults to PublicInputs"
+    )]
+    public_inputs_name: Option<String>,
+
+    #[arg(
+        long = "visibility",
+        value_name = "VIS",
+        help = "Visibility of the generated structs, one of `pub`, `pub(crate)`, `pub(super)`, or an empty string for private, defaults to `pub`"
+    )]
+    visibility: Option<String>,
+
+    #[arg(
+        long = "no-std",
+        help = "Imports `Vec` and `vec!` from `alloc`, for crates declared with `#![no_std]`"
+    )]
+    no_std: bool,
+}
+impl WinterfellArgs {
+    /// Returns the name of the first option which was given on the command line, if any.
+    fn first_given(&self) -> Option<&'static str> {
+        [
+            ("--air-crate", self.air_crate.is_some()),
+            ("--math-crate", self.math_crate.is_some()),
+            ("--utils-crate", self.utils_crate.is_some()),
+            ("--air-name", self.air_name.is_some()),
+            ("--public-inputs-name", self.public_inputs_name.is_some()),
+            ("--visibility", self.visibility.is_some()),
+            ("--no-std", self.no_std),
+        ]
+        .into_iter()
+        .find_map(|(name, given)| given.then_some(name))
+    }
+
+    fn config(&self) -> air_codegen_winter::CodegenConfig {
+        let default = air_codegen_winter::CodegenConfig::default();
+        air_codegen_winter::CodegenConfig {
+            air_crate: self.air_crate.clone().unwrap_or(default.air_crate),
+            math_crate: self.math_crate.clone().unwrap_or(default.math_crate),
+            utils_crate: self.utils_crate.clone().unwrap_or(default.utils_crate),
+            air_name: self.air_name.clone(),
+            public_inputs_name: self
+                .public_inputs_name
+                .clone()
+                .unwrap_or(default.public_inputs_name),
+            visibility: self.visibility.clone().unwrap_or(default.visibility),
+            no_std: self.no_std,
+        }
+    }
 }
 
 impl Transpile {
@@ -49,14 +151,36 @@ impl Transpile {
 
         let input_path = &self.input;
 
+        // The Winterfell options would otherwise be silently ignored by the other backends
+        let target = self.target.unwrap_or(Target::Winterfell);
+        if target != Target::Winterfell {
+            if let Some(option) = self.winterfell.first_given() {
+                return Err(format!(
+                    "{option} is only supported by the Winterfell target"
+                ));
+            }
+        }
+
         let codemap = Arc::new(CodeMap::new());
         let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
         let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
 
+        // Imported modules are searched for in the directories given on the command line first,
+        // followed by those in the environment
+        let config = air_parser::ParserConfig {
+            search_paths: self.include.clone(),
+        }
+        .with_env_search_paths();
+
         // Parse from file to internal representation
-        let air = air_parser::parse_file(&diagnostics, codemap, input_path)
+        let air = air_parser::parse_file_with_config(&diagnostics, codemap, input_path, config)
             .map_err(CompileError::Parse)
             .and_then(|ast| {
+                let ast = if self.check_selectors {
+                    air_parser::transforms::SelectorAnalysis::new(&diagnostics).run(ast)?
+                } else {
+                    ast
+                };
                 let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
                     .chain(air_parser::transforms::Inlining::new(&diagnostics))
                     .chain(air_ir::passes::AstToAir::new(&diagnostics));
@@ -66,9 +190,10 @@ impl Transpile {
         match air {
             Ok(air) => {
                 // generate Rust code targeting Winterfell
-                let target = self.target.unwrap_or(Target::Winterfell);
                 let backend: Box<dyn CodeGenerator<Output = String>> = match target {
-                    Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator),
+                    Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator::new(
+                        self.winterfell.config(),
+                    )),
                     Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
                 };
 
@@ -81,7 +206,16 @@ impl Transpile {
                         path
                     }
                 };
-                let code = backend.generate(&air).expect("code generation failed");
+                let code = match backend.generate(&air) {
+                    Ok(code) => code,
+                    Err(err) => {
+                        diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message(err.to_string())
+                            .emit();
+                        return Err("code generation failed".into());
+                    }
+                };
                 if let Err(err) = fs::write(&output_path, code) {
                     return Err(format!("{err:?}"));
                 }
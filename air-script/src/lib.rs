@@ -1,7 +1,9 @@
 pub use air_codegen_masm::{
     CodeGenerator as MasmCodeGenerator, CodegenConfig as MasmCodegenConfig,
 };
-pub use air_codegen_winter::CodeGenerator as WinterfellCodeGenerator;
+pub use air_codegen_winter::{
+    CodeGenerator as WinterfellCodeGenerator, CodegenConfig as WinterfellCodegenConfig,
+};
 pub use air_ir::{passes, Air, CompileError};
-pub use air_parser::{parse, parse_file, transforms};
+pub use air_parser::{parse, parse_file, parse_file_with_config, transforms, ParserConfig};
 pub use air_pass::Pass;
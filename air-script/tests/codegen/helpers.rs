@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use air_ir::{CodeGenerator, CompileError};
+use air_ir::{Air, CodeGenerator, CompileError};
 use air_pass::Pass;
 use miden_diagnostics::{
     term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
@@ -8,9 +8,22 @@ use miden_diagnostics::{
 
 pub enum Target {
     Winterfell,
+    WinterfellWithConfig(air_codegen_winter::CodegenConfig),
     Masm,
 }
 
+impl Target {
+    fn backend(self) -> Box<dyn CodeGenerator<Output = String>> {
+        match self {
+            Target::Winterfell => Box::<air_codegen_winter::CodeGenerator>::default(),
+            Target::WinterfellWithConfig(config) => {
+                Box::new(air_codegen_winter::CodeGenerator::new(config))
+            }
+            Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
+        }
+    }
+}
+
 pub struct Test {
     input_path: String,
 }
@@ -19,27 +32,38 @@ impl Test {
         Test { input_path }
     }
 
-    pub fn transpile(&self, target: Target) -> Result<String, CompileError> {
+    pub fn compile(&self) -> Result<Air, CompileError> {
         let codemap = Arc::new(CodeMap::new());
         let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
         let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
 
         // Parse from file to internal representation
-        let air = air_parser::parse_file(&diagnostics, codemap, &self.input_path)
+        air_parser::parse_file(&diagnostics, codemap, &self.input_path)
             .map_err(CompileError::Parse)
             .and_then(|ast| {
                 let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
                     .chain(air_parser::transforms::Inlining::new(&diagnostics))
                     .chain(air_ir::passes::AstToAir::new(&diagnostics));
                 pipeline.run(ast)
-            })?;
+            })
+    }
 
-        let backend: Box<dyn CodeGenerator<Output = String>> = match target {
-            Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator),
-            Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
-        };
+    pub fn transpile(&self, target: Target) -> Result<String, CompileError> {
+        let air = self.compile()?;
 
         // generate Rust code targeting Winterfell
-        Ok(backend.generate(&air).expect("code generation failed"))
+        Ok(target
+            .backend()
+            .generate(&air)
+            .expect("code generation failed"))
+    }
+
+    /// Returns the error reported by the backend for `target`, which must reject the input
+    pub fn codegen_error(&self, target: Target) -> String {
+        let air = self.compile().expect("compilation failed");
+        match target.backend().generate(&air) {
+            Ok(_) => panic!("expected code generation to fail"),
+            Err(err) => err.to_string(),
+        }
     }
 }
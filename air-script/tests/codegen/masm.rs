@@ -24,6 +24,91 @@ fn binary() {
     expected.assert_eq(&generated_masm);
 }
 
+#[test]
+fn buses() {
+    let generated_masm = Test::new("tests/buses/buses.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../buses/buses.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
+#[test]
+fn lookup() {
+    let generated_masm = Test::new("tests/lookup/lookup.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../lookup/lookup.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
+#[test]
+fn typed_columns() {
+    let generated_masm = Test::new("tests/typed_columns/typed_columns.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../typed_columns/typed_columns.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
+#[test]
+fn pub_inputs_integrity() {
+    let generated_masm =
+        Test::new("tests/pub_inputs_integrity/pub_inputs_integrity.air".to_string())
+            .transpile(Target::Masm)
+            .unwrap();
+
+    let expected = expect_file!["../pub_inputs_integrity/pub_inputs_integrity.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
+#[test]
+fn periodic_columns_computed() {
+    let generated_air =
+        Test::new("tests/periodic_columns_computed/periodic_columns_computed.air".to_string())
+            .transpile(Target::Masm)
+            .unwrap();
+
+    let expected = expect_file!["../periodic_columns_computed/periodic_columns_computed.masm"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn periodic_columns_aux() {
+    let generated_air =
+        Test::new("tests/periodic_columns_aux/periodic_columns_aux.air".to_string())
+            .transpile(Target::Masm)
+            .unwrap();
+
+    let expected = expect_file!["../periodic_columns_aux/periodic_columns_aux.masm"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn builtins() {
+    let generated_masm = Test::new("tests/builtins/builtins.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../builtins/builtins.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
+// See the section on multiple auxiliary segments in docs/src/description/declarations.md
+#[test]
+fn multi_aux_trace() {
+    let err = Test::new("tests/multi_aux_trace/multi_aux_trace.air".to_string())
+        .codegen_error(Target::Masm);
+
+    assert_eq!(
+        err,
+        "2 auxiliary trace segments are declared, but the Miden assembly backend supports only one"
+    );
+}
+
 #[test]
 fn periodic_columns() {
     let generated_masm = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
@@ -131,6 +216,18 @@ fn random_values() {
     expected.assert_eq(&generated_masm);
 }
 
+// The named groups of random values are laid out one after another, starting at the first
+// random value of the single auxiliary segment
+#[test]
+fn random_values_groups() {
+    let generated_masm = Test::new("tests/random_values/random_values_groups.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../random_values/random_values_groups.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
 #[test]
 fn list_comprehension() {
     let generated_masm = Test::new("tests/list_comprehension/list_comprehension.air".to_string())
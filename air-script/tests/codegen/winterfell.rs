@@ -24,6 +24,150 @@ fn binary() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn buses() {
+    let generated_air = Test::new("tests/buses/buses.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../buses/buses.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn lookup() {
+    let generated_air = Test::new("tests/lookup/lookup.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../lookup/lookup.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn typed_columns() {
+    let generated_air = Test::new("tests/typed_columns/typed_columns.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../typed_columns/typed_columns.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs_integrity() {
+    let generated_air =
+        Test::new("tests/pub_inputs_integrity/pub_inputs_integrity.air".to_string())
+            .transpile(Target::Winterfell)
+            .unwrap();
+
+    let expected = expect_file!["../pub_inputs_integrity/pub_inputs_integrity.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs_variable() {
+    let generated_air = Test::new("tests/pub_inputs_variable/pub_inputs_variable.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs_variable/pub_inputs_variable.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs_variable_with_config() {
+    let config = air_codegen_winter::CodegenConfig {
+        air_crate: "prover::air".to_string(),
+        math_crate: "prover::math".to_string(),
+        utils_crate: "prover::utils".to_string(),
+        air_name: Some("ProverAir".to_string()),
+        public_inputs_name: "ProverInputs".to_string(),
+        visibility: "pub(crate)".to_string(),
+        no_std: true,
+    };
+    let generated_air = Test::new("tests/pub_inputs_variable/pub_inputs_variable.air".to_string())
+        .transpile(Target::WinterfellWithConfig(config))
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs_variable/pub_inputs_variable_with_config.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn err_config_invalid_air_name() {
+    let config = air_codegen_winter::CodegenConfig {
+        air_name: Some("Prover-Air".to_string()),
+        ..Default::default()
+    };
+    let err = Test::new("tests/pub_inputs_variable/pub_inputs_variable.air".to_string())
+        .codegen_error(Target::WinterfellWithConfig(config));
+
+    assert_eq!(
+        err,
+        "invalid Air struct name `Prover-Air`, expected a Rust identifier"
+    );
+}
+
+#[test]
+fn err_config_invalid_visibility() {
+    let config = air_codegen_winter::CodegenConfig {
+        visibility: "pub(in crate)".to_string(),
+        ..Default::default()
+    };
+    let err = Test::new("tests/pub_inputs_variable/pub_inputs_variable.air".to_string())
+        .codegen_error(Target::WinterfellWithConfig(config));
+
+    assert_eq!(
+        err,
+        "invalid visibility `pub(in crate)`, expected one of `pub`, `pub(crate)`, `pub(super)`, or an empty string"
+    );
+}
+
+#[test]
+fn periodic_columns_computed() {
+    let generated_air =
+        Test::new("tests/periodic_columns_computed/periodic_columns_computed.air".to_string())
+            .transpile(Target::Winterfell)
+            .unwrap();
+
+    let expected = expect_file!["../periodic_columns_computed/periodic_columns_computed.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn periodic_columns_aux() {
+    let generated_air =
+        Test::new("tests/periodic_columns_aux/periodic_columns_aux.air".to_string())
+            .transpile(Target::Winterfell)
+            .unwrap();
+
+    let expected = expect_file!["../periodic_columns_aux/periodic_columns_aux.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn builtins() {
+    let generated_air = Test::new("tests/builtins/builtins.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../builtins/builtins.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+// See the section on multiple auxiliary segments in docs/src/description/declarations.md
+#[test]
+fn multi_aux_trace() {
+    let err = Test::new("tests/multi_aux_trace/multi_aux_trace.air".to_string())
+        .codegen_error(Target::Winterfell);
+
+    assert_eq!(
+        err,
+        "2 auxiliary trace segments are declared, but the Winterfell backend supports only one"
+    );
+}
+
 #[test]
 fn periodic_columns() {
     let generated_air = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
@@ -132,6 +276,18 @@ fn random_values() {
     expected.assert_eq(&generated_air);
 }
 
+// The named groups of random values are laid out one after another, starting at the first
+// random value of the single auxiliary segment
+#[test]
+fn random_values_groups() {
+    let generated_air = Test::new("tests/random_values/random_values_groups.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../random_values/random_values_groups.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn list_comprehension() {
     let generated_air = Test::new("tests/list_comprehension/list_comprehension.air".to_string())
@@ -169,6 +325,16 @@ fn selectors() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn selectors_with_default() {
+    let generated_air = Test::new("tests/selectors/selectors_with_default.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../selectors/selectors_with_default.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn constraint_comprehension() {
     let generated_air =
@@ -187,3 +353,14 @@ fn constraint_comprehension() {
     let expected = expect_file!["../constraint_comprehension/constraint_comprehension.rs"];
     expected.assert_eq(&generated_air);
 }
+
+#[test]
+fn shared_subexpressions() {
+    let generated_air =
+        Test::new("tests/shared_subexpressions/shared_subexpressions.air".to_string())
+            .transpile(Target::Winterfell)
+            .unwrap();
+
+    let expected = expect_file!["../shared_subexpressions/shared_subexpressions.rs"];
+    expected.assert_eq(&generated_air);
+}
@@ -0,0 +1,104 @@
+use prover::air::{Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
+use prover::math::fields::f64::BaseElement as Felt;
+use prover::math::{ExtensionOf, FieldElement};
+use alloc::vec;
+use alloc::vec::Vec;
+use prover::utils::{ByteWriter, Serializable};
+
+pub(crate) struct ProverInputs {
+    advice: Vec<Felt>,
+    outputs: Vec<Felt>,
+    stack_inputs: [Felt; 4],
+}
+
+impl ProverInputs {
+    pub(crate) fn new(advice: Vec<Felt>, outputs: Vec<Felt>, stack_inputs: [Felt; 4]) -> Self {
+        Self { advice, outputs, stack_inputs }
+    }
+}
+
+impl Serializable for ProverInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.advice.len() as u32);
+        target.write(self.advice.as_slice());
+        target.write_u32(self.outputs.len() as u32);
+        target.write(self.outputs.as_slice());
+        target.write(self.stack_inputs.as_slice());
+    }
+}
+
+pub(crate) struct ProverAir {
+    context: AirContext<Felt>,
+    advice: Vec<Felt>,
+    outputs: Vec<Felt>,
+    stack_inputs: [Felt; 4],
+}
+
+impl ProverAir {
+    pub(crate) fn last_step(&self) -> usize {
+        self.trace_length() - self.context().num_transition_exemptions()
+    }
+}
+
+impl Air for ProverAir {
+    type BaseField = Felt;
+    type PublicInputs = ProverInputs;
+
+    fn context(&self) -> &AirContext<Felt> {
+        &self.context
+    }
+
+    fn new(trace_info: TraceInfo, public_inputs: ProverInputs, options: WinterProofOptions) -> Self {
+        let main_degrees = vec![TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![];
+        let num_main_assertions = 4;
+        let num_aux_assertions = 0;
+
+        let context = AirContext::new_multi_segment(
+            trace_info,
+            main_degrees,
+            aux_degrees,
+            num_main_assertions,
+            num_aux_assertions,
+            options,
+        )
+        .set_num_transition_exemptions(2);
+        assert!(public_inputs.advice.len() >= 2, "expected public input advice to have at least 2 elements");
+        assert!(public_inputs.outputs.len() >= 3, "expected public input outputs to have at least 3 elements");
+        Self { context, advice: public_inputs.advice, outputs: public_inputs.outputs, stack_inputs: public_inputs.stack_inputs }
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+        vec![]
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        let mut result = Vec::new();
+        result.push(Assertion::single(0, 0, self.stack_inputs[0]));
+        result.push(Assertion::single(1, 0, self.advice[0]));
+        result.push(Assertion::single(0, self.last_step(), self.outputs[0]));
+        result.push(Assertion::single(2, self.last_step(), self.outputs[2]));
+        result
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
+        let mut result = Vec::new();
+        result
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
+        let main_current = frame.current();
+        let main_next = frame.next();
+        result[0] = main_next[0] - (main_current[0] + main_current[1] * E::from(self.advice[1]));
+    }
+
+    fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+    where F: FieldElement<BaseField = Felt>,
+          E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
+    {
+        let main_current = main_frame.current();
+        let main_next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+    }
+}
\ No newline at end of file
@@ -31,6 +31,16 @@ impl air_ir::CodeGenerator for CodeGenerator {
     type Output = String;
 
     fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        // public inputs are laid out one after another in memory, so their sizes must be known
+        if let Some(input) = ir.public_inputs().find(|input| input.is_variable_length()) {
+            return Err(CodegenError::VariableLengthPublicInput(input.name.to_string()).into());
+        }
+        // see "Multiple auxiliary segments" in docs/src/description/declarations.md
+        let num_aux_segments = ir.aux_segments().count();
+        if num_aux_segments > 1 {
+            return Err(CodegenError::MultipleAuxSegments(num_aux_segments).into());
+        }
+
         let generator = Backend::new(ir, self.config);
         generator.generate()
     }
@@ -102,17 +112,21 @@ impl<'ast> Backend<'ast> {
             .public_inputs()
             .scan(0, |public_input_count, input| {
                 let start_offset = *public_input_count;
-                *public_input_count += input.size;
+                *public_input_count += input
+                    .size
+                    .expect("variable-length public inputs are rejected before codegen");
                 Some((input.name, start_offset))
             })
             .collect();
 
-        // count the boundary constraints
+        // count the boundary constraints, the auxiliary segments are evaluated together as a
+        // single auxiliary trace
         let mut boundary_constraint_count = BTreeMap::new();
-        for segment in [MAIN_TRACE, AUX_TRACE] {
+        for segment in std::iter::once(MAIN_TRACE).chain(ir.aux_segments()) {
+            let group = segment.min(AUX_TRACE);
             for boundary in ir.boundary_constraints(segment) {
                 boundary_constraint_count
-                    .entry((segment, boundary.domain()))
+                    .entry((group, boundary.domain()))
                     .and_modify(|c| *c += 1)
                     .or_insert(1);
             }
@@ -401,7 +415,7 @@ impl<'ast> Backend<'ast> {
     /// stack. This is useful for testing the evaluation. Later on the value is aggregated.
     fn gen_compute_integrity_constraints(&mut self) -> Result<(), CodegenError> {
         let main_trace_count = self.ir.integrity_constraints(MAIN_TRACE).len();
-        let aux_trace_count = self.ir.integrity_constraints(AUX_TRACE).len();
+        let aux_trace_count = self.num_aux_integrity_constraints();
 
         self.writer
             .header("Procedure to evaluate numerators of all integrity constraints.");
@@ -430,7 +444,9 @@ impl<'ast> Backend<'ast> {
         self.writer.proc("compute_integrity_constraints");
         walk_integrity_constraints(self, self.ir, MAIN_TRACE)?;
         self.integrity_contraints = 0; // reset counter for the aux trace
-        walk_integrity_constraints(self, self.ir, AUX_TRACE)?;
+        for segment in self.ir.aux_segments() {
+            walk_integrity_constraints(self, self.ir, segment)?;
+        }
         self.writer.end();
 
         Ok(())
@@ -505,7 +521,9 @@ impl<'ast> Backend<'ast> {
                 "Where: (r_1, r_0) is one quadratic extension field element for each constraint",
             );
             self.writer.proc(name);
-            walk_boundary_constraints(self, self.ir, AUX_TRACE, ConstraintDomain::FirstRow)?;
+            for segment in self.ir.aux_segments() {
+                walk_boundary_constraints(self, self.ir, segment, ConstraintDomain::FirstRow)?;
+            }
             self.writer.end();
         }
 
@@ -524,7 +542,9 @@ impl<'ast> Backend<'ast> {
                 "Where: (r_1, r_0) is one quadratic extension field element for each constraint",
             );
             self.writer.proc(name);
-            walk_boundary_constraints(self, self.ir, AUX_TRACE, ConstraintDomain::LastRow)?;
+            for segment in self.ir.aux_segments() {
+                walk_boundary_constraints(self, self.ir, segment, ConstraintDomain::LastRow)?;
+            }
             self.writer.end();
         }
 
@@ -584,8 +604,8 @@ impl<'ast> Backend<'ast> {
         self.writer
             .header("Numerator of the transition constraint polynomial");
 
-        let total_len = self.ir.integrity_constraints(MAIN_TRACE).len()
-            + self.ir.integrity_constraints(AUX_TRACE).len();
+        let total_len =
+            self.ir.integrity_constraints(MAIN_TRACE).len() + self.num_aux_integrity_constraints();
 
         for _ in 0..total_len {
             self.writer.ext2add();
@@ -743,6 +763,14 @@ impl<'ast> Backend<'ast> {
         self.writer.end();
     }
 
+    /// Returns the number of integrity constraints across all auxiliary trace segments.
+    fn num_aux_integrity_constraints(&self) -> usize {
+        self.ir
+            .aux_segments()
+            .map(|segment| self.ir.integrity_constraints(segment).len())
+            .sum()
+    }
+
     /// Emits code to load the `log_2(trace_len)` onto the top of the stack.
     fn load_log2_trace_len(&mut self) {
         self.writer.mem_load(self.config.log2_trace_len_address);
@@ -1050,10 +1078,15 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                 // curr and next values of a single variable.
                 //
                 // Layout defined at: https://github.com/0xPolygonMiden/miden-vm/issues/875
-                let target_word: u32 = access
-                    .column
-                    .try_into()
-                    .map_err(|_| CodegenError::InvalidIndex)?;
+                //
+                // The auxiliary frame holds the columns of all auxiliary segments, in segment
+                // order.
+                let column = if access.segment == MAIN_TRACE {
+                    access.column
+                } else {
+                    self.ir.aux_column_offset(access.segment) + access.column
+                };
+                let target_word: u32 = column.try_into().map_err(|_| CodegenError::InvalidIndex)?;
                 let el_pos: u32 = access
                     .row_offset
                     .try_into()
@@ -1097,15 +1130,16 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                     .or(Err(CodegenError::InvalidIndex))?;
                 load_quadratic_element(&mut self.writer, self.config.public_inputs_address, index)?;
             }
-            Value::RandomValue(element) => {
+            Value::RandomValue(rv) => {
                 // Compute the target address for the random value. Each memory address contains
                 // two values.
                 //
                 // Layout defined at: https://github.com/0xPolygonMiden/miden-vm/blob/next/stdlib/asm/crypto/stark/random_coin.masm#L169-L172
+                let element = self.ir.random_value_offset(rv.segment) + rv.index;
                 load_quadratic_element(
                     &mut self.writer,
                     self.config.aux_rand_address,
-                    (*element).try_into().or(Err(CodegenError::InvalidIndex))?,
+                    element.try_into().or(Err(CodegenError::InvalidIndex))?,
                 )?;
             }
         };
@@ -45,6 +45,8 @@ pub struct CodegenConfig {
     // Memory range for the public inputs.
     pub public_inputs_address: u32,
 
+    // Memory range for the random values of the auxiliary trace, each address contains two values.
+    // Only a single auxiliary segment is supported, see `CodeGenerator::generate`.
     pub aux_rand_address: u32,
     pub periodic_values_address: u32,
 
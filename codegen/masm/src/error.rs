@@ -12,4 +12,8 @@ pub enum CodegenError {
     InvalidBoundaryConstraint,
     #[error("invalid integrity constraint")]
     InvalidIntegrityConstraint,
+    #[error("variable-length public input '{0}' is not supported by the Miden assembly backend")]
+    VariableLengthPublicInput(String),
+    #[error("{0} auxiliary trace segments are declared, but the Miden assembly backend supports only one")]
+    MultipleAuxSegments(usize),
 }
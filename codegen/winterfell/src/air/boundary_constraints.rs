@@ -19,6 +19,9 @@ pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air) {
         .arg_ref_self()
         .ret("Vec<Assertion<Felt>>");
 
+    // declare the result vector to be returned.
+    get_assertions.line("let mut result = Vec::new();");
+
     // add the boundary constraints
     add_assertions(get_assertions, ir, 0);
 
@@ -37,25 +40,30 @@ pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &Air) {
         .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
         .ret("Vec<Assertion<E>>");
 
-    // add the boundary constraints
-    add_assertions(get_aux_assertions, ir, 1);
+    // declare the result vector to be returned.
+    get_aux_assertions.line("let mut result = Vec::new();");
+
+    // add the boundary constraints of each auxiliary segment
+    for trace_segment in ir.aux_segments() {
+        add_assertions(get_aux_assertions, ir, trace_segment);
+    }
 
     // return the result
     get_aux_assertions.line("result");
 }
 
-/// Declares a result vector and adds assertions for boundary constraints to it for the specified
-/// trace segment
+/// Adds assertions for the boundary constraints of the specified trace segment to the result
+/// vector.
+///
+/// The columns of auxiliary segments are indexed across all auxiliary segments, as expected by
+/// Winterfell.
 fn add_assertions(func_body: &mut codegen::Function, ir: &Air, trace_segment: TraceSegmentId) {
-    let elem_type = if trace_segment == 0 {
-        ElemType::Base
+    let (elem_type, column_offset) = if trace_segment == 0 {
+        (ElemType::Base, 0)
     } else {
-        ElemType::Ext
+        (ElemType::Ext, ir.aux_column_offset(trace_segment))
     };
 
-    // declare the result vector to be returned.
-    func_body.line("let mut result = Vec::new();");
-
     // add the boundary constraints
     for constraint in ir.boundary_constraints(trace_segment) {
         let (trace_access, expr_root) =
@@ -64,7 +72,7 @@ fn add_assertions(func_body: &mut codegen::Function, ir: &Air, trace_segment: Tr
 
         let assertion = format!(
             "result.push(Assertion::single({}, {}, {}));",
-            trace_access.column,
+            column_offset + trace_access.column,
             domain_to_str(constraint.domain()),
             expr_root.to_string(ir, elem_type, trace_segment)
         );
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use air_ir::{
     Air, IntegrityConstraintDegree, NodeIndex, Operation, TraceAccess, TraceSegmentId, Value,
 };
@@ -35,14 +37,19 @@ impl Codegen for IntegrityConstraintDegree {
 }
 
 impl Codegen for TraceAccess {
-    fn to_string(&self, _ir: &Air, _elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
-        let frame = if self.segment == 0 { "main" } else { "aux" };
+    fn to_string(&self, ir: &Air, _elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
+        // the auxiliary frame holds the columns of all auxiliary segments, in segment order
+        let (frame, column) = if self.segment == 0 {
+            ("main", self.column)
+        } else {
+            ("aux", ir.aux_column_offset(self.segment) + self.column)
+        };
         let row_offset = match self.row_offset {
             0 => {
-                format!("current[{}]", self.column)
+                format!("current[{column}]")
             }
             1 => {
-                format!("next[{}]", self.column)
+                format!("next[{column}]")
             }
             _ => panic!("Winterfell doesn't support row offsets greater than 1."),
         };
@@ -56,42 +63,7 @@ impl Codegen for TraceAccess {
 
 impl Codegen for NodeIndex {
     fn to_string(&self, ir: &Air, elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
-        let op = ir.constraint_graph().node(self).op();
-        op.to_string(ir, elem_type, trace_segment)
-    }
-}
-
-impl Codegen for Operation {
-    fn to_string(&self, ir: &Air, elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
-        match self {
-            Operation::Value(value) => value.to_string(ir, elem_type, trace_segment),
-            Operation::Add(_, _) => binary_op_to_string(ir, self, elem_type, trace_segment),
-            Operation::Sub(_, _) => binary_op_to_string(ir, self, elem_type, trace_segment),
-            Operation::Mul(_, _) => binary_op_to_string(ir, self, elem_type, trace_segment),
-            // TODO: move this logic to a helper function
-            Operation::Exp(l_idx, r_idx) => {
-                let lhs = l_idx.to_string(ir, elem_type, trace_segment);
-                let lhs = if is_leaf(l_idx, ir) {
-                    lhs
-                } else {
-                    format!("({lhs})")
-                };
-                match r_idx {
-                    0 => match elem_type {
-                        // x^0 = 1
-                        ElemType::Base => "Felt::ONE".to_string(),
-                        ElemType::Ext => "E::ONE".to_string(),
-                    },
-                    1 => lhs, // x^1 = x
-                    _ => match elem_type {
-                        ElemType::Base => format!("{lhs}.exp(Felt::new({r_idx}))"),
-                        ElemType::Ext => {
-                            format!("{lhs}.exp(E::PositiveInteger::from({r_idx}_u64))")
-                        }
-                    },
-                }
-            }
-        }
+        SharedNodes::default().node_to_string(ir, self, elem_type, trace_segment)
     }
 }
 
@@ -101,14 +73,17 @@ impl Codegen for Value {
             // TODO: move constant handling to a helper function
             Value::Constant(0) => match elem_type {
                 ElemType::Base => "Felt::ZERO".to_string(),
+                ElemType::Main => "F::ZERO".to_string(),
                 ElemType::Ext => "E::ZERO".to_string(),
             },
             Value::Constant(1) => match elem_type {
                 ElemType::Base => "Felt::ONE".to_string(),
+                ElemType::Main => "F::ONE".to_string(),
                 ElemType::Ext => "E::ONE".to_string(),
             },
             Value::Constant(value) => match elem_type {
                 ElemType::Base => format!("Felt::new({value})"),
+                ElemType::Main => format!("F::from({value}_u64)"),
                 ElemType::Ext => format!("E::from({value}_u64)"),
             },
             Value::TraceAccess(trace_access) => {
@@ -120,61 +95,270 @@ impl Codegen for Value {
                     .iter()
                     .position(|(qid, _)| qid == &pc.name)
                     .unwrap();
-                format!("periodic_values[{index}]")
-            }
-            Value::PublicInput(air_ir::PublicInputAccess { name, index }) => {
-                format!("self.{name}[{index}]")
+                // the periodic values are base field elements in the auxiliary constraints
+                if trace_segment == 0 {
+                    format!("periodic_values[{index}]")
+                } else {
+                    format!("E::from(periodic_values[{index}])")
+                }
             }
-            Value::RandomValue(idx) => {
+            Value::PublicInput(air_ir::PublicInputAccess { name, index }) => match elem_type {
+                ElemType::Base => format!("self.{name}[{index}]"),
+                ElemType::Main => format!("F::from(self.{name}[{index}])"),
+                ElemType::Ext => format!("E::from(self.{name}[{index}])"),
+            },
+            Value::RandomValue(rv) => {
+                // only a single auxiliary segment is supported, see `CodeGenerator::generate`
+                let idx = ir.random_value_offset(rv.segment) + rv.index;
                 format!("aux_rand_elements.get_segment_elements(0)[{idx}]")
             }
         }
     }
 }
 
-/// Returns true if the operation at the specified node index is a leaf node in the constraint graph.
-fn is_leaf(idx: &NodeIndex, ir: &Air) -> bool {
-    !matches!(
-        ir.constraint_graph().node(idx).op(),
-        Operation::Add(_, _) | Operation::Sub(_, _) | Operation::Mul(_, _) | Operation::Exp(_, _)
-    )
+// SHARED SUBEXPRESSIONS
+// ================================================================================================
+
+/// The minimum number of operations in a subexpression shared by several constraints for it to be
+/// bound to a variable, rather than being recomputed by each constraint referencing it.
+const MIN_SHARED_NODE_SIZE: usize = 2;
+
+/// The subexpressions shared by the constraints of a generated function.
+///
+/// The [AlgebraicGraph] shares nodes between constraints, but expanding each constraint into a
+/// single expression recomputes the shared nodes everywhere they are referenced. Instead, these
+/// are bound to variables `t0`, `t1`, ... ahead of the constraints, in dependency order.
+///
+/// In the auxiliary transition constraints, a shared subexpression which only depends on the main
+/// trace is computed over the elements of the main frame, and only converted to the extension
+/// field where it is referenced.
+///
+/// [AlgebraicGraph]: air_ir::AlgebraicGraph
+#[derive(Default)]
+pub struct SharedNodes {
+    /// The name and element type of the variable each shared node is bound to
+    bindings: HashMap<NodeIndex, (String, ElemType)>,
+    /// The shared nodes, in the order in which they must be bound
+    order: Vec<NodeIndex>,
 }
+impl SharedNodes {
+    /// Finds the subexpressions shared by the given constraint roots, where `aux` indicates whether
+    /// the constraints are evaluated by `evaluate_aux_transition`.
+    pub fn new(ir: &Air, roots: &[NodeIndex], aux: bool) -> Self {
+        let graph = ir.constraint_graph();
 
-/// Returns a string representation of a binary operation.
-fn binary_op_to_string(
-    ir: &Air,
-    op: &Operation,
-    elem_type: ElemType,
-    trace_segment: TraceSegmentId,
-) -> String {
-    match op {
-        Operation::Add(l_idx, r_idx) => {
-            let lhs = l_idx.to_string(ir, elem_type, trace_segment);
-            let rhs = r_idx.to_string(ir, elem_type, trace_segment);
-            format!("{lhs} + {rhs}")
+        // count the references to each node, where the children of a node are only counted once,
+        // since a shared node is only expanded once
+        let mut uses = HashMap::<NodeIndex, usize>::default();
+        let mut postorder = Vec::new();
+        for root in roots.iter() {
+            count_uses(ir, *root, &mut uses, &mut postorder);
         }
-        Operation::Sub(l_idx, r_idx) => {
-            let lhs = l_idx.to_string(ir, elem_type, trace_segment);
-            let rhs = if ir.constraint_graph().node(r_idx).op().precedence() <= op.precedence() {
-                format!("({})", r_idx.to_string(ir, elem_type, trace_segment))
-            } else {
-                r_idx.to_string(ir, elem_type, trace_segment)
+
+        // compute the number of operations in the expansion of each node, children first
+        let mut sizes = HashMap::<NodeIndex, usize>::default();
+        let mut main_only = HashMap::<NodeIndex, bool>::default();
+        for idx in postorder.iter() {
+            let (size, is_main) = match graph.node(idx).op() {
+                Operation::Value(Value::TraceAccess(access)) => (0, access.segment == 0),
+                Operation::Value(Value::RandomValue(_)) => (0, false),
+                Operation::Value(_) => (0, true),
+                Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => (
+                    1usize.saturating_add(sizes[lhs]).saturating_add(sizes[rhs]),
+                    main_only[lhs] && main_only[rhs],
+                ),
+                Operation::Exp(lhs, _) => (1usize.saturating_add(sizes[lhs]), main_only[lhs]),
             };
-            format!("{lhs} - {rhs}")
+            sizes.insert(*idx, size);
+            main_only.insert(*idx, is_main);
         }
-        Operation::Mul(l_idx, r_idx) => {
-            let lhs = if ir.constraint_graph().node(l_idx).op().precedence() < op.precedence() {
-                format!("({})", l_idx.to_string(ir, elem_type, trace_segment))
-            } else {
-                l_idx.to_string(ir, elem_type, trace_segment)
-            };
-            let rhs = if ir.constraint_graph().node(r_idx).op().precedence() < op.precedence() {
-                format!("({})", r_idx.to_string(ir, elem_type, trace_segment))
-            } else {
-                r_idx.to_string(ir, elem_type, trace_segment)
-            };
-            format!("{lhs} * {rhs}")
+
+        let mut shared = Self::default();
+        for idx in postorder.into_iter() {
+            if uses[&idx] > 1 && sizes[&idx] >= MIN_SHARED_NODE_SIZE {
+                let elem_type = if aux && main_only[&idx] {
+                    ElemType::Main
+                } else {
+                    ElemType::Ext
+                };
+                let name = format!("t{}", shared.order.len());
+                shared.bindings.insert(idx, (name, elem_type));
+                shared.order.push(idx);
+            }
+        }
+        shared
+    }
+
+    /// Returns the `let` statements binding the shared subexpressions, in dependency order.
+    pub fn bindings(&self, ir: &Air, trace_segment: TraceSegmentId) -> Vec<String> {
+        self.order
+            .iter()
+            .map(|idx| {
+                let (name, elem_type) = &self.bindings[idx];
+                // expressions over the main frame never need to be converted
+                let trace_segment = match elem_type {
+                    ElemType::Main => 0,
+                    _ => trace_segment,
+                };
+                let expr = self.op_to_string(
+                    ir,
+                    ir.constraint_graph().node(idx).op(),
+                    *elem_type,
+                    trace_segment,
+                );
+                format!("let {name} = {expr};")
+            })
+            .collect()
+    }
+
+    /// Returns a string representation of the expression rooted at the specified node, in which
+    /// shared subexpressions are referenced by the name of their variable.
+    pub fn node_to_string(
+        &self,
+        ir: &Air,
+        idx: &NodeIndex,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> String {
+        match self.bindings.get(idx) {
+            Some((name, ElemType::Main)) if matches!(elem_type, ElemType::Ext) => {
+                format!("E::from({name})")
+            }
+            Some((name, _)) => name.clone(),
+            None => {
+                let op = ir.constraint_graph().node(idx).op();
+                self.op_to_string(ir, op, elem_type, trace_segment)
+            }
+        }
+    }
+
+    fn op_to_string(
+        &self,
+        ir: &Air,
+        op: &Operation,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> String {
+        match op {
+            Operation::Value(value) => value.to_string(ir, elem_type, trace_segment),
+            Operation::Add(_, _) => self.binary_op_to_string(ir, op, elem_type, trace_segment),
+            Operation::Sub(_, _) => self.binary_op_to_string(ir, op, elem_type, trace_segment),
+            Operation::Mul(_, _) => self.binary_op_to_string(ir, op, elem_type, trace_segment),
+            // TODO: move this logic to a helper function
+            Operation::Exp(l_idx, r_idx) => {
+                let lhs = self.node_to_string(ir, l_idx, elem_type, trace_segment);
+                let lhs = if self.is_leaf(l_idx, ir) {
+                    lhs
+                } else {
+                    format!("({lhs})")
+                };
+                match r_idx {
+                    0 => match elem_type {
+                        // x^0 = 1
+                        ElemType::Base => "Felt::ONE".to_string(),
+                        ElemType::Main => "F::ONE".to_string(),
+                        ElemType::Ext => "E::ONE".to_string(),
+                    },
+                    1 => lhs, // x^1 = x
+                    _ => match elem_type {
+                        ElemType::Base => format!("{lhs}.exp(Felt::new({r_idx}))"),
+                        ElemType::Main => {
+                            format!("{lhs}.exp(F::PositiveInteger::from({r_idx}_u64))")
+                        }
+                        ElemType::Ext => {
+                            format!("{lhs}.exp(E::PositiveInteger::from({r_idx}_u64))")
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    /// Returns true if the specified node is rendered as a leaf, i.e. it is a value or it is bound
+    /// to a variable.
+    fn is_leaf(&self, idx: &NodeIndex, ir: &Air) -> bool {
+        self.precedence(idx, ir) == Operation::Value(Value::Constant(0)).precedence()
+    }
+
+    /// Returns the precedence of the specified node as it is rendered.
+    fn precedence(&self, idx: &NodeIndex, ir: &Air) -> usize {
+        if self.bindings.contains_key(idx) {
+            Operation::Value(Value::Constant(0)).precedence()
+        } else {
+            ir.constraint_graph().node(idx).op().precedence()
+        }
+    }
+
+    /// Returns a string representation of a binary operation.
+    fn binary_op_to_string(
+        &self,
+        ir: &Air,
+        op: &Operation,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> String {
+        match op {
+            Operation::Add(l_idx, r_idx) => {
+                let lhs = self.node_to_string(ir, l_idx, elem_type, trace_segment);
+                let rhs = self.node_to_string(ir, r_idx, elem_type, trace_segment);
+                format!("{lhs} + {rhs}")
+            }
+            Operation::Sub(l_idx, r_idx) => {
+                let lhs = self.node_to_string(ir, l_idx, elem_type, trace_segment);
+                let rhs = if self.precedence(r_idx, ir) <= op.precedence() {
+                    format!(
+                        "({})",
+                        self.node_to_string(ir, r_idx, elem_type, trace_segment)
+                    )
+                } else {
+                    self.node_to_string(ir, r_idx, elem_type, trace_segment)
+                };
+                format!("{lhs} - {rhs}")
+            }
+            Operation::Mul(l_idx, r_idx) => {
+                let lhs = if self.precedence(l_idx, ir) < op.precedence() {
+                    format!(
+                        "({})",
+                        self.node_to_string(ir, l_idx, elem_type, trace_segment)
+                    )
+                } else {
+                    self.node_to_string(ir, l_idx, elem_type, trace_segment)
+                };
+                let rhs = if self.precedence(r_idx, ir) < op.precedence() {
+                    format!(
+                        "({})",
+                        self.node_to_string(ir, r_idx, elem_type, trace_segment)
+                    )
+                } else {
+                    self.node_to_string(ir, r_idx, elem_type, trace_segment)
+                };
+                format!("{lhs} * {rhs}")
+            }
+            _ => panic!("unsupported operation"),
+        }
+    }
+}
+
+/// Counts the references to the nodes of the subgraph rooted at `idx`, and appends the nodes of the
+/// subgraph to `postorder` the first time they are visited, children first.
+fn count_uses(
+    ir: &Air,
+    idx: NodeIndex,
+    uses: &mut HashMap<NodeIndex, usize>,
+    postorder: &mut Vec<NodeIndex>,
+) {
+    let count = uses.entry(idx).or_default();
+    *count += 1;
+    if *count > 1 {
+        return;
+    }
+    match ir.constraint_graph().node(&idx).op() {
+        Operation::Value(_) => (),
+        Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => {
+            count_uses(ir, *lhs, uses, postorder);
+            count_uses(ir, *rhs, uses, postorder);
         }
-        _ => panic!("unsupported operation"),
+        Operation::Exp(lhs, _) => count_uses(ir, *lhs, uses, postorder),
     }
+    postorder.push(idx);
 }
@@ -1,11 +1,11 @@
 mod public_inputs;
-use public_inputs::add_public_inputs_struct;
+use public_inputs::{add_public_inputs_struct, public_input_type};
 
 mod periodic_columns;
 use periodic_columns::add_fn_get_periodic_column_values;
 
 mod graph;
-use graph::Codegen;
+use graph::{Codegen, SharedNodes};
 
 mod boundary_constraints;
 use boundary_constraints::{add_fn_get_assertions, add_fn_get_aux_assertions};
@@ -15,14 +15,18 @@ use transition_constraints::{add_fn_evaluate_aux_transition, add_fn_evaluate_tra
 
 use air_ir::{Air, TraceSegmentId};
 
-use super::{Impl, Scope};
+use super::{CodegenConfig, Impl, Scope};
 
 // HELPER TYPES
 // ================================================================================================
 
 #[derive(Debug, Clone, Copy)]
 pub enum ElemType {
+    /// Elements of the base field, i.e. `Felt`
     Base,
+    /// Elements of the main trace frame in `evaluate_aux_transition`, i.e. `F`
+    Main,
+    /// Elements of the field the constraints are evaluated over, i.e. `E`
     Ext,
 }
 
@@ -31,55 +35,54 @@ pub enum ElemType {
 
 /// Updates the provided scope with a new Air struct and Winterfell Air trait implementation
 /// which are equivalent the provided AirIR.
-pub(super) fn add_air(scope: &mut Scope, ir: &Air) {
+pub(super) fn add_air(scope: &mut Scope, ir: &Air, config: &CodegenConfig) {
     // add the Public Inputs struct and its base implementation.
-    add_public_inputs_struct(scope, ir);
+    add_public_inputs_struct(scope, ir, config);
 
-    let name = ir.name();
+    let name = config.air_name.as_deref().unwrap_or(ir.name());
 
     // add the Air struct and its base implementation.
-    add_air_struct(scope, ir, name);
+    add_air_struct(scope, ir, name, config);
 
     // add Winterfell Air trait implementation for the provided AirIR.
-    add_air_trait(scope, ir, name);
+    add_air_trait(scope, ir, name, config);
 }
 
 /// Updates the provided scope with a custom Air struct.
-fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str) {
+fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str, config: &CodegenConfig) {
     // define the custom Air struct.
-    let air_struct = scope
-        .new_struct(name)
-        .vis("pub")
-        .field("context", "AirContext<Felt>");
+    let air_struct = scope.new_struct(name).field("context", "AirContext<Felt>");
+    if let Some(vis) = config.visibility() {
+        air_struct.vis(vis);
+    }
 
     // add public inputs
     for public_input in ir.public_inputs() {
-        air_struct.field(
-            public_input.name.as_str(),
-            format!("[Felt; {}]", public_input.size),
-        );
+        air_struct.field(public_input.name.as_str(), public_input_type(public_input));
     }
 
     // add the custom Air implementation block
     let base_impl = scope.new_impl(name);
     // add a simple method to get the last step.
-    base_impl
+    let last_step = base_impl
         .new_fn("last_step")
         .arg_ref_self()
-        .vis("pub")
         .ret("usize")
         .line("self.trace_length() - self.context().num_transition_exemptions()");
+    if let Some(vis) = config.visibility() {
+        last_step.vis(vis);
+    }
 }
 
 /// Updates the provided scope with the custom Air struct and an Air trait implementation based on
 /// the provided AirIR.
-fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
+fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str, config: &CodegenConfig) {
     // add the implementation block for the Air trait.
     let air_impl = scope
         .new_impl(name)
         .impl_trait("Air")
         .associate_type("BaseField", "Felt")
-        .associate_type("PublicInputs", "PublicInputs");
+        .associate_type("PublicInputs", &config.public_inputs_name);
 
     // add default function "context".
     let fn_context = air_impl
@@ -89,7 +92,7 @@ fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
     fn_context.line("&self.context");
 
     // add the method implementations required by the AIR trait.
-    add_fn_new(air_impl, ir);
+    add_fn_new(air_impl, ir, config);
 
     add_fn_get_periodic_column_values(air_impl, ir);
 
@@ -104,20 +107,20 @@ fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
 
 /// Adds an implementation of the "new" method to the referenced Air implementation based on the
 /// data in the provided AirIR.
-fn add_fn_new(impl_ref: &mut Impl, ir: &Air) {
+fn add_fn_new(impl_ref: &mut Impl, ir: &Air, config: &CodegenConfig) {
     // define the function.
     let new = impl_ref
         .new_fn("new")
         .arg("trace_info", "TraceInfo")
-        .arg("public_inputs", "PublicInputs")
+        .arg("public_inputs", &config.public_inputs_name)
         .arg("options", "WinterProofOptions")
         .ret("Self");
 
     // define the integrity constraint degrees of the main trace `main_degrees`.
-    add_constraint_degrees(new, ir, 0, "main_degrees");
+    add_constraint_degrees(new, ir, [0], "main_degrees");
 
-    // define the integrity constraint degrees of the aux trace `aux_degrees`.
-    add_constraint_degrees(new, ir, 1, "aux_degrees");
+    // define the integrity constraint degrees of all aux trace segments `aux_degrees`.
+    add_constraint_degrees(new, ir, ir.aux_segments(), "aux_degrees");
 
     // define the number of main trace boundary constraints `num_main_assertions`.
     new.line(format!(
@@ -128,7 +131,9 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &Air) {
     // define the number of aux trace boundary constraints `num_aux_assertions`.
     new.line(format!(
         "let num_aux_assertions = {};",
-        ir.num_boundary_constraints(1)
+        ir.aux_segments()
+            .map(|trace_segment| ir.num_boundary_constraints(trace_segment))
+            .sum::<usize>()
     ));
 
     // define the context.
@@ -145,6 +150,21 @@ let context = AirContext::new_multi_segment(
 
     new.line(context);
 
+    // check that variable-length public inputs have enough elements for the constraints which
+    // index them, since their length is only known at runtime.
+    for public_input in ir
+        .public_inputs()
+        .filter(|input| input.is_variable_length())
+    {
+        let min_len = ir.public_input_min_len(public_input.name);
+        if min_len > 0 {
+            new.line(format!(
+                "assert!(public_inputs.{0}.len() >= {min_len}, \"expected public input {0} to have at least {min_len} elements\");",
+                public_input.name
+            ));
+        }
+    }
+
     // get public inputs
     let mut pub_inputs = Vec::new();
     for public_input in ir.public_inputs() {
@@ -154,18 +174,23 @@ let context = AirContext::new_multi_segment(
     new.line(format!("Self {{ context, {} }}", pub_inputs.join(", ")));
 }
 
-/// Iterates through the degrees of the integrity constraints in the IR, and appends a line of
-/// generated code to the function body that declares all of the constraint degrees.
+/// Iterates through the degrees of the integrity constraints of the given trace segments in the
+/// IR, and appends a line of generated code to the function body that declares all of the
+/// constraint degrees.
 fn add_constraint_degrees(
     func_body: &mut codegen::Function,
     ir: &Air,
-    trace_segment: TraceSegmentId,
+    trace_segments: impl IntoIterator<Item = TraceSegmentId>,
     decl_name: &str,
 ) {
-    let degrees = ir
-        .integrity_constraint_degrees(trace_segment)
-        .iter()
-        .map(|degree| degree.to_string(ir, ElemType::Ext, trace_segment))
+    let degrees = trace_segments
+        .into_iter()
+        .flat_map(|trace_segment| {
+            ir.integrity_constraint_degrees(trace_segment)
+                .iter()
+                .map(|degree| degree.to_string(ir, ElemType::Ext, trace_segment))
+                .collect::<Vec<_>>()
+        })
         .collect::<Vec<_>>();
     func_body.line(format!("let {decl_name} = vec![{}];", degrees.join(", ")));
 }
@@ -1,18 +1,18 @@
-use air_ir::Air;
+use air_ir::{Air, PublicInput};
 
-use super::Scope;
+use super::{CodegenConfig, Scope};
 
 /// Updates the provided scope with a public inputs.
-pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air) {
-    let name = "PublicInputs";
+pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air, config: &CodegenConfig) {
+    let name = config.public_inputs_name.as_str();
     // define the PublicInputs struct.
-    let pub_inputs_struct = scope.new_struct(name).vis("pub");
+    let pub_inputs_struct = scope.new_struct(name);
+    if let Some(vis) = config.visibility() {
+        pub_inputs_struct.vis(vis);
+    }
 
     for public_input in ir.public_inputs() {
-        pub_inputs_struct.field(
-            public_input.name.as_str(),
-            format!("[Felt; {}]", public_input.size),
-        );
+        pub_inputs_struct.field(public_input.name.as_str(), public_input_type(public_input));
     }
 
     // add the public inputs implementation block
@@ -26,28 +26,47 @@ pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air) {
     // add a constructor for public inputs
     let new_fn = base_impl
         .new_fn("new")
-        .vis("pub")
         .ret("Self")
         .line(format!("Self {{ {} }}", pub_inputs_values.join(", ")));
+    if let Some(vis) = config.visibility() {
+        new_fn.vis(vis);
+    }
     for public_input in ir.public_inputs() {
-        new_fn.arg(
-            public_input.name.as_str(),
-            format!("[Felt; {}]", public_input.size),
-        );
+        new_fn.arg(public_input.name.as_str(), public_input_type(public_input));
     }
 
-    add_serializable_impl(scope, pub_inputs_values)
+    add_serializable_impl(scope, ir, name)
+}
+
+/// Returns the Rust type of the given public input, i.e. `[Felt; N]`, or `Vec<Felt>` if it is
+/// variable-length.
+pub(super) fn public_input_type(public_input: &PublicInput) -> String {
+    match public_input.size {
+        Some(size) => format!("[Felt; {size}]"),
+        None => "Vec<Felt>".to_string(),
+    }
 }
 
 /// Adds Serialization implementation for PublicInputs to the scope
-fn add_serializable_impl(scope: &mut Scope, pub_input_values: Vec<String>) {
-    let serializable_impl = scope.new_impl("PublicInputs").impl_trait("Serializable");
+///
+/// Variable-length public inputs are prefixed by their length, so that they can be deserialized.
+fn add_serializable_impl(scope: &mut Scope, ir: &Air, name: &str) {
+    let serializable_impl = scope.new_impl(name).impl_trait("Serializable");
     let write_into_fn = serializable_impl
         .new_fn("write_into")
         .generic("W: ByteWriter")
         .arg_ref_self()
         .arg("target", "&mut W");
-    for pub_input_value in pub_input_values {
-        write_into_fn.line(format!("target.write(self.{pub_input_value}.as_slice());"));
+    for public_input in ir.public_inputs() {
+        if public_input.is_variable_length() {
+            write_into_fn.line(format!(
+                "target.write_u32(self.{}.len() as u32);",
+                public_input.name
+            ));
+        }
+        write_into_fn.line(format!(
+            "target.write(self.{}.as_slice());",
+            public_input.name
+        ));
     }
 }
@@ -1,6 +1,6 @@
 use air_ir::{Air, TraceSegmentId};
 
-use super::{Codegen, ElemType, Impl};
+use super::{ElemType, Impl, SharedNodes};
 
 // HELPERS TO GENERATE THE WINTERFELL TRANSITION CONSTRAINT METHODS
 // ================================================================================================
@@ -22,12 +22,25 @@ pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &Air) {
     evaluate_transition.line("let main_next = frame.next();");
 
     // output the constraints.
-    add_constraints(evaluate_transition, ir, 0);
+    add_constraints(evaluate_transition, ir, [0]);
 }
 
 /// Adds an implementation of the "evaluate_aux_transition" method to the referenced Air implementation
 /// based on the data in the provided IR.
 pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
+    // the periodic values are only bound if an auxiliary constraint references a periodic column,
+    // which is the case if and only if its degree depends on the cycle of that column.
+    let uses_periodic_values = ir.aux_segments().any(|trace_segment| {
+        ir.integrity_constraint_degrees(trace_segment)
+            .iter()
+            .any(|degree| !degree.cycles().is_empty())
+    });
+    let periodic_values = if uses_periodic_values {
+        "periodic_values"
+    } else {
+        "_periodic_values"
+    };
+
     // define the function.
     let evaluate_aux_transition = impl_ref
         .new_fn("evaluate_aux_transition")
@@ -35,7 +48,7 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
         .arg_ref_self()
         .arg("main_frame", "&EvaluationFrame<F>")
         .arg("aux_frame", "&EvaluationFrame<E>")
-        .arg("_periodic_values", "&[F]")
+        .arg(periodic_values, "&[F]")
         .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
         .arg("result", "&mut [E]")
         .bound("F", "FieldElement<BaseField = Felt>")
@@ -47,20 +60,48 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
     evaluate_aux_transition.line("let aux_current = aux_frame.current();");
     evaluate_aux_transition.line("let aux_next = aux_frame.next();");
 
-    // output the constraints.
-    add_constraints(evaluate_aux_transition, ir, 1);
+    // output the constraints of all auxiliary segments.
+    add_constraints(evaluate_aux_transition, ir, ir.aux_segments());
 }
 
-/// Iterates through the integrity constraints in the IR, and appends a line of generated code to
-/// the provided codegen function body for each constraint.
-fn add_constraints(func_body: &mut codegen::Function, ir: &Air, trace_segment: TraceSegmentId) {
-    for (idx, constraint) in ir.integrity_constraints(trace_segment).iter().enumerate() {
+/// Iterates through the integrity constraints of the given trace segments in the IR, and appends
+/// a line of generated code to the provided codegen function body for each constraint.
+///
+/// The subexpressions shared by several constraints are bound to variables beforehand, see
+/// [SharedNodes].
+fn add_constraints(
+    func_body: &mut codegen::Function,
+    ir: &Air,
+    trace_segments: impl IntoIterator<Item = TraceSegmentId>,
+) {
+    let constraints = trace_segments
+        .into_iter()
+        .flat_map(|trace_segment| {
+            ir.integrity_constraints(trace_segment)
+                .iter()
+                .map(move |constraint| (trace_segment, *constraint.node_index()))
+        })
+        .collect::<Vec<_>>();
+    let Some(&(trace_segment, _)) = constraints.first() else {
+        return;
+    };
+
+    // the constraints of all auxiliary segments are evaluated over the same extension field, so
+    // the shared subexpressions are rendered as in the first constraint
+    let roots = constraints
+        .iter()
+        .map(|(_, root)| *root)
+        .collect::<Vec<_>>();
+    let shared = SharedNodes::new(ir, &roots, trace_segment != 0);
+    for binding in shared.bindings(ir, trace_segment) {
+        func_body.line(binding);
+    }
+
+    for (idx, (trace_segment, root)) in constraints.iter().enumerate() {
         func_body.line(format!(
             "result[{}] = {};",
             idx,
-            constraint
-                .node_index()
-                .to_string(ir, ElemType::Ext, trace_segment)
+            shared.node_to_string(ir, root, ElemType::Ext, *trace_segment)
         ));
     }
 }
@@ -0,0 +1,104 @@
+/// Options controlling the paths, names, and visibility used in the generated Winterfell code.
+///
+/// The defaults generate code for a standard library crate depending directly on the Winterfell
+/// crates.
+#[derive(Clone)]
+pub struct CodegenConfig {
+    /// Path of the crate providing the `Air` trait and its associated types.
+    ///
+    /// Defaults to `winter_air`, but may be any path re-exporting these, e.g. `my_prover::air`.
+    pub air_crate: String,
+
+    /// Path of the crate providing the field types, i.e. `FieldElement`, `ExtensionOf`, and the
+    /// base field in `fields::f64`.
+    pub math_crate: String,
+
+    /// Path of the crate providing the serialization traits, and `collections::Vec` outside of
+    /// `no_std` mode.
+    pub utils_crate: String,
+
+    /// Name of the generated struct implementing the `Air` trait.
+    ///
+    /// Defaults to the name of the AirScript program.
+    pub air_name: Option<String>,
+
+    /// Name of the generated public inputs struct.
+    pub public_inputs_name: String,
+
+    /// Visibility of the generated structs and their inherent methods, e.g. `pub` or `pub(crate)`.
+    ///
+    /// An empty string makes them private to the module they are generated in.
+    pub visibility: String,
+
+    /// Whether to generate code for a `#![no_std]` crate, in which case `Vec` and `vec!` are
+    /// imported from `alloc`, which the crate must declare with `extern crate alloc`.
+    pub no_std: bool,
+}
+
+impl Default for CodegenConfig {
+    fn default() -> Self {
+        Self {
+            air_crate: "winter_air".to_string(),
+            math_crate: "winter_math".to_string(),
+            utils_crate: "winter_utils".to_string(),
+            air_name: None,
+            public_inputs_name: "PublicInputs".to_string(),
+            visibility: "pub".to_string(),
+            no_std: false,
+        }
+    }
+}
+
+impl CodegenConfig {
+    /// Checks that the names, paths, and visibility are valid in the generated Rust code.
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        for (option, path) in [
+            ("air crate", &self.air_crate),
+            ("math crate", &self.math_crate),
+            ("utils crate", &self.utils_crate),
+        ] {
+            let segments = path.strip_prefix("::").unwrap_or(path);
+            if !segments.split("::").all(is_identifier) {
+                anyhow::bail!(
+                    "invalid {option} path `{path}`, expected a Rust path such as `winter_air`"
+                );
+            }
+        }
+        for (option, name) in [
+            ("Air struct name", self.air_name.as_ref()),
+            ("public inputs struct name", Some(&self.public_inputs_name)),
+        ] {
+            if let Some(name) = name.filter(|name| !is_identifier(name)) {
+                anyhow::bail!("invalid {option} `{name}`, expected a Rust identifier");
+            }
+        }
+        if !matches!(
+            self.visibility.trim(),
+            "" | "pub" | "pub(crate)" | "pub(super)"
+        ) {
+            anyhow::bail!(
+                "invalid visibility `{}`, expected one of `pub`, `pub(crate)`, `pub(super)`, or an empty string",
+                self.visibility
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the visibility modifier of the generated items, if they are not private.
+    pub(crate) fn visibility(&self) -> Option<&str> {
+        match self.visibility.trim() {
+            "" => None,
+            vis => Some(vis),
+        }
+    }
+}
+
+/// Returns true if `name` is a valid Rust identifier, ignoring keywords.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some('_') => name.len() > 1 && chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        Some(c) if c.is_ascii_alphabetic() => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
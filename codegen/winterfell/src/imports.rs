@@ -1,20 +1,30 @@
-use super::Scope;
+use super::{CodegenConfig, Scope};
 
 /// Adds the required imports to the provided scope.
-pub(super) fn add_imports(scope: &mut Scope) {
+pub(super) fn add_imports(scope: &mut Scope, config: &CodegenConfig) {
+    let air = config.air_crate.as_str();
+    let math = config.math_crate.as_str();
+    let utils = config.utils_crate.as_str();
+
     // add winterfell imports
-    scope.import("winter_air", "Air");
-    scope.import("winter_air", "AirContext");
-    scope.import("winter_air", "Assertion");
-    scope.import("winter_air", "AuxTraceRandElements");
-    scope.import("winter_air", "EvaluationFrame");
-    scope.import("winter_air", "ProofOptions as WinterProofOptions");
-    scope.import("winter_air", "TransitionConstraintDegree");
-    scope.import("winter_air", "TraceInfo");
-    scope.import("winter_math::fields::f64", "BaseElement as Felt");
-    scope.import("winter_math", "ExtensionOf");
-    scope.import("winter_math", "FieldElement");
-    scope.import("winter_utils::collections", "Vec");
-    scope.import("winter_utils", "ByteWriter");
-    scope.import("winter_utils", "Serializable");
+    scope.import(air, "Air");
+    scope.import(air, "AirContext");
+    scope.import(air, "Assertion");
+    scope.import(air, "AuxTraceRandElements");
+    scope.import(air, "EvaluationFrame");
+    scope.import(air, "ProofOptions as WinterProofOptions");
+    scope.import(air, "TransitionConstraintDegree");
+    scope.import(air, "TraceInfo");
+    scope.import(&format!("{math}::fields::f64"), "BaseElement as Felt");
+    scope.import(math, "ExtensionOf");
+    scope.import(math, "FieldElement");
+    if config.no_std {
+        // the `vec!` macro is not in the prelude of `no_std` crates
+        scope.import("alloc", "vec");
+        scope.import("alloc::vec", "Vec");
+    } else {
+        scope.import(&format!("{utils}::collections"), "Vec");
+    }
+    scope.import(utils, "ByteWriter");
+    scope.import(utils, "Serializable");
 }
@@ -2,26 +2,48 @@ use air_ir::Air;
 use codegen::{Impl, Scope};
 
 mod air;
+mod config;
 mod imports;
 
+pub use config::CodegenConfig;
+
 // GENERATE RUST CODE FOR WINTERFELL AIR
 // ================================================================================================
 
 /// CodeGenerator is used to generate a Rust implementation of the Winterfell STARK prover library's
 /// Air trait. The generated Air expresses the constraints specified by the AirIR used to build the
 /// CodeGenerator.
-pub struct CodeGenerator;
+#[derive(Default)]
+pub struct CodeGenerator {
+    config: CodegenConfig,
+}
+impl CodeGenerator {
+    pub fn new(config: CodegenConfig) -> Self {
+        Self { config }
+    }
+}
 impl air_ir::CodeGenerator for CodeGenerator {
     type Output = String;
 
     fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        // see "Multiple auxiliary segments" in docs/src/description/declarations.md
+        let num_aux_segments = ir.aux_segments().count();
+        if num_aux_segments > 1 {
+            anyhow::bail!(
+                "{} auxiliary trace segments are declared, but the Winterfell backend supports only one",
+                num_aux_segments
+            );
+        }
+
+        self.config.validate()?;
+
         let mut scope = Scope::new();
 
         // add winterfell imports.
-        imports::add_imports(&mut scope);
+        imports::add_imports(&mut scope, &self.config);
 
         // add an Air struct and Winterfell Air trait implementation for the provided AirIR.
-        air::add_air(&mut scope, ir);
+        air::add_air(&mut scope, ir, &self.config);
 
         Ok(scope.to_string())
     }
@@ -6,7 +6,7 @@ use crate::ir::*;
 ///
 /// The raw value of this identifier is an index in the `nodes` vector
 /// of the [AlgebraicGraph] struct.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct NodeIndex(usize);
 impl core::ops::Add<usize> for NodeIndex {
     type Output = NodeIndex;
@@ -65,6 +65,11 @@ impl AlgebraicGraph {
         &self.nodes[index.0]
     }
 
+    /// Returns all of the nodes in the graph.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
     /// Returns the number of nodes in the graph.
     pub fn num_nodes(&self) -> usize {
         self.nodes.len()
@@ -82,6 +87,14 @@ impl AlgebraicGraph {
         }
     }
 
+    /// Returns the degree of the subgraph which has the specified node as its tip, ignoring the
+    /// cycles of any periodic columns it references.
+    ///
+    /// Unlike [Self::degree], this is zero for subgraphs which do not reference the trace.
+    pub(crate) fn base_degree(&self, index: &NodeIndex) -> usize {
+        self.accumulate_degree(&mut BTreeMap::default(), index)
+    }
+
     /// TODO: docs
     pub fn node_details(
         &self,
@@ -100,14 +113,8 @@ impl AlgebraicGraph {
                     // the default domain for [IntegrityConstraints] is `EveryRow`
                     Ok((DEFAULT_SEGMENT, ConstraintDomain::EveryRow))
                 }
-                Value::PublicInput(_) => {
-                    assert!(
-                        !default_domain.is_integrity(),
-                        "unexpected access to public input in integrity constraint"
-                    );
-                    Ok((DEFAULT_SEGMENT, default_domain))
-                }
-                Value::RandomValue(_) => Ok((AUX_SEGMENT, default_domain)),
+                Value::PublicInput(_) => Ok((DEFAULT_SEGMENT, default_domain)),
+                Value::RandomValue(rv) => Ok((rv.segment, default_domain)),
                 Value::TraceAccess(trace_access) => {
                     let domain = if default_domain.is_boundary() {
                         assert_eq!(
@@ -105,8 +105,25 @@ impl Constraints {
         root: NodeIndex,
         domain: ConstraintDomain,
     ) {
-        let root = ConstraintRoot::new(root, domain);
-        if domain.is_boundary() {
+        self.insert_root(trace_segment, ConstraintRoot::new(root, domain));
+    }
+
+    /// Inserts a new constraint against `trace_segment`, just like `insert_constraint`, but
+    /// tags it as implied by the declaration of the trace binding `binding`, e.g. `s: bool`.
+    pub fn insert_implied_constraint(
+        &mut self,
+        trace_segment: TraceSegmentId,
+        root: NodeIndex,
+        domain: ConstraintDomain,
+        binding: Identifier,
+    ) {
+        let mut root = ConstraintRoot::new(root, domain);
+        root.implied_by = Some(binding);
+        self.insert_root(trace_segment, root);
+    }
+
+    fn insert_root(&mut self, trace_segment: TraceSegmentId, root: ConstraintRoot) {
+        if root.domain.is_boundary() {
             if self.boundary_constraints.len() <= trace_segment {
                 self.boundary_constraints.resize(trace_segment + 1, vec![]);
             }
@@ -139,11 +156,16 @@ impl Constraints {
 pub struct ConstraintRoot {
     index: NodeIndex,
     domain: ConstraintDomain,
+    implied_by: Option<Identifier>,
 }
 impl ConstraintRoot {
     /// Creates a new [ConstraintRoot] with the specified entry index and row offset.
     pub const fn new(index: NodeIndex, domain: ConstraintDomain) -> Self {
-        Self { index, domain }
+        Self {
+            index,
+            domain,
+            implied_by: None,
+        }
     }
 
     /// Returns the index of the entry node of the subgraph representing the constraint.
@@ -156,6 +178,14 @@ impl ConstraintRoot {
     pub const fn domain(&self) -> ConstraintDomain {
         self.domain
     }
+
+    /// Returns the trace binding whose declaration implies this constraint, if it was not written
+    /// explicitly, e.g. `s` for the constraint `s^2 = s` implied by the declaration `s: bool`.
+    ///
+    /// The span of the returned identifier points back to the declaration of the column.
+    pub const fn implied_by(&self) -> Option<Identifier> {
+        self.implied_by
+    }
 }
 
 /// [ConstraintDomain] corresponds to the domain over which a constraint is applied.
@@ -8,7 +8,7 @@ pub use self::constraints::{ConstraintDomain, ConstraintError, ConstraintRoot, C
 pub use self::degree::IntegrityConstraintDegree;
 pub use self::operation::Operation;
 pub use self::trace::TraceAccess;
-pub use self::value::{PeriodicColumnAccess, PublicInputAccess, Value};
+pub use self::value::{PeriodicColumnAccess, PublicInputAccess, RandomValueAccess, Value};
 
 pub use air_parser::{
     ast::{
@@ -20,7 +20,7 @@ pub use air_parser::{
 
 /// The default segment against which a constraint is applied is the main trace segment.
 pub const DEFAULT_SEGMENT: TraceSegmentId = 0;
-/// The auxiliary trace segment.
+/// The first auxiliary trace segment.
 pub const AUX_SEGMENT: TraceSegmentId = 1;
 /// The offset of the "current" row during constraint evaluation.
 pub const CURRENT_ROW: usize = 0;
@@ -56,8 +56,11 @@ pub struct Air {
     ///
     /// These are taken straight from the [air_parser::ast::Program] without modification.
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
-    /// The total number of elements in the random values array
-    pub num_random_values: u16,
+    /// The number of random values drawn for each segment of the trace, in segment order (i.e. the
+    /// index in this vector matches the index of the segment in the program).
+    ///
+    /// Random values are never drawn for the main trace segment, so its entry is always zero.
+    pub num_random_values: Vec<u16>,
     /// The constraints enforced by this program, in their algebraic graph representation.
     pub constraints: Constraints,
 }
@@ -82,7 +85,7 @@ impl Air {
             trace_segment_widths: vec![],
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
-            num_random_values: 0,
+            num_random_values: vec![],
             constraints: Default::default(),
         }
     }
@@ -97,10 +100,58 @@ impl Air {
         self.public_inputs.values()
     }
 
+    /// Returns the number of elements the public input `name` must have for every access to it
+    /// by the constraints to be in bounds.
+    ///
+    /// This is checked during compilation for fixed-size public inputs, but backends must check
+    /// it at runtime for variable-length public inputs.
+    pub fn public_input_min_len(&self, name: Identifier) -> usize {
+        self.constraint_graph()
+            .nodes()
+            .iter()
+            .filter_map(|node| match node.op() {
+                Operation::Value(Value::PublicInput(access)) if access.name == name => {
+                    Some(access.index + 1)
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn periodic_columns(&self) -> impl Iterator<Item = &PeriodicColumn> + '_ {
         self.periodic_columns.values()
     }
 
+    /// Returns an [Iterator] over the ids of the auxiliary trace segments, in segment order
+    pub fn aux_segments(&self) -> impl Iterator<Item = TraceSegmentId> {
+        AUX_SEGMENT..self.trace_segment_widths.len().max(AUX_SEGMENT)
+    }
+
+    /// Returns the index of the first column of `trace_segment` when the columns of all auxiliary
+    /// trace segments are laid out one after another, in segment order.
+    ///
+    /// Backends which treat the auxiliary segments as a single trace use this to locate columns.
+    pub fn aux_column_offset(&self, trace_segment: TraceSegmentId) -> usize {
+        debug_assert!(trace_segment >= AUX_SEGMENT);
+        self.trace_segment_widths[AUX_SEGMENT..trace_segment]
+            .iter()
+            .map(|width| *width as usize)
+            .sum()
+    }
+
+    /// Returns the index of the first random value drawn for `trace_segment` when the random values
+    /// of all auxiliary trace segments are laid out one after another, in segment order.
+    ///
+    /// This is the random values counterpart of [Self::aux_column_offset].
+    pub fn random_value_offset(&self, trace_segment: TraceSegmentId) -> usize {
+        debug_assert!(trace_segment >= AUX_SEGMENT);
+        self.num_random_values[AUX_SEGMENT..trace_segment]
+            .iter()
+            .map(|n| *n as usize)
+            .sum()
+    }
+
     /// Return the number of boundary constraints
     pub fn num_boundary_constraints(&self, trace_segment: TraceSegmentId) -> usize {
         self.constraints.num_boundary_constraints(trace_segment)
@@ -16,8 +16,8 @@ pub enum Value {
     PeriodicColumn(PeriodicColumnAccess),
     /// A reference to a specific element of a given public input
     PublicInput(PublicInputAccess),
-    /// A reference to the `random_values` array, specifically the element at the given index
-    RandomValue(usize),
+    /// A reference to a specific element of the random values drawn for a given trace segment
+    RandomValue(RandomValueAccess),
 }
 
 /// Represents an access of a [PeriodicColumn], similar in nature to [TraceAccess]
@@ -45,3 +45,17 @@ impl PublicInputAccess {
         Self { name, index }
     }
 }
+
+/// Represents an access of the random values drawn for an auxiliary trace segment
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RandomValueAccess {
+    /// The auxiliary trace segment the random values are drawn for
+    pub segment: TraceSegmentId,
+    /// The index of the element in the random values of that segment
+    pub index: usize,
+}
+impl RandomValueAccess {
+    pub const fn new(segment: TraceSegmentId, index: usize) -> Self {
+        Self { segment, index }
+    }
+}
@@ -1,9 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use air_parser::ast;
 use air_pass::Pass;
 
-use miden_diagnostics::{DiagnosticsHandler, Severity, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
 use crate::{graph::NodeIndex, ir::*, CompileError};
 
@@ -29,9 +29,23 @@ impl<'p> Pass for AstToAir<'p> {
         let trace_columns = program.trace_columns;
         let boundary_constraints = program.boundary_constraints;
         let integrity_constraints = program.integrity_constraints;
+        let buses = program
+            .buses
+            .values()
+            .map(|bus| {
+                let factors = BusFactors {
+                    range_check: bus.range_check,
+                    ..Default::default()
+                };
+                (bus.name, factors)
+            })
+            .collect();
 
         air.trace_segment_widths = trace_columns.iter().map(|ts| ts.size as u16).collect();
-        air.num_random_values = random_values.as_ref().map(|rv| rv.size as u16).unwrap_or(0);
+        air.num_random_values = trace_columns
+            .iter()
+            .map(|ts| random_values.get(&ts.id).map_or(0, |rv| rv.size as u16))
+            .collect();
         air.periodic_columns = program.periodic_columns;
         air.public_inputs = program.public_inputs;
 
@@ -40,6 +54,7 @@ impl<'p> Pass for AstToAir<'p> {
             air: &mut air,
             random_values,
             trace_columns,
+            buses,
             bindings: Default::default(),
         };
 
@@ -51,6 +66,9 @@ impl<'p> Pass for AstToAir<'p> {
             builder.build_integrity_constraint(bc)?;
         }
 
+        builder.build_column_types()?;
+        builder.build_buses();
+
         Ok(air)
     }
 }
@@ -65,11 +83,26 @@ enum MemoizedBinding {
     Matrix(Vec<Vec<NodeIndex>>),
 }
 
+/// The factors contributed to a bus by its interactions, recorded during lowering of the
+/// integrity constraints, and used to build the bus column constraints once all of them are known.
+#[derive(Default)]
+struct BusFactors {
+    /// The number of elements in the tuples sent/received on this bus, and where it was inferred from
+    arity: Option<(usize, SourceSpan)>,
+    /// The factors contributed by each `send`
+    send: Vec<NodeIndex>,
+    /// The factors contributed by each `receive`
+    receive: Vec<NodeIndex>,
+    /// The type of values range checked by this bus, if any, e.g. `u16` in `rc: multiset for u16`
+    range_check: Option<ast::ColumnType>,
+}
+
 struct AirBuilder<'a> {
     diagnostics: &'a DiagnosticsHandler,
     air: &'a mut Air,
-    random_values: Option<ast::RandomValues>,
+    random_values: BTreeMap<TraceSegmentId, ast::RandomValues>,
     trace_columns: Vec<ast::TraceSegment>,
+    buses: BTreeMap<Identifier, BusFactors>,
     bindings: HashMap<Identifier, MemoizedBinding>,
 }
 impl<'a> AirBuilder<'a> {
@@ -101,20 +134,22 @@ impl<'a> AirBuilder<'a> {
     fn build_integrity_constraint(&mut self, bc: &ast::Statement) -> Result<(), CompileError> {
         match bc {
             ast::Statement::Enforce(ast::ScalarExpr::Binary(ast::BinaryExpr {
+                span,
                 op: ast::BinaryOp::Eq,
                 ref lhs,
                 ref rhs,
-                ..
-            })) => self.build_integrity_equality(lhs, rhs, None),
+            })) => self.build_integrity_equality(*span, lhs, rhs, None),
             ast::Statement::EnforceIf(
                 ast::ScalarExpr::Binary(ast::BinaryExpr {
+                    span,
                     op: ast::BinaryOp::Eq,
                     ref lhs,
                     ref rhs,
-                    ..
                 }),
                 ref condition,
-            ) => self.build_integrity_equality(lhs, rhs, Some(condition)),
+            ) => self.build_integrity_equality(*span, lhs, rhs, Some(condition)),
+            ast::Statement::BusInteraction(expr) => self.build_bus_interaction(expr),
+            ast::Statement::Lookup(expr) => self.build_lookup(expr),
             ast::Statement::Let(expr) => {
                 self.build_let(expr, |bldr, stmt| bldr.build_integrity_constraint(stmt))
             }
@@ -164,8 +199,8 @@ impl<'a> AirBuilder<'a> {
             },
             ast::Expr::Range(ref values) => {
                 let values = values
-                    .item
-                    .clone()
+                    .values()
+                    .unwrap()
                     .map(|v| self.insert_constant(v as u64))
                     .collect();
                 self.bindings
@@ -271,7 +306,11 @@ impl<'a> AirBuilder<'a> {
                     }
                 }
             }
-            ast::Expr::Call(_) | ast::Expr::ListComprehension(_) => unreachable!(),
+            // Conditionals are resolved during inlining
+            ast::Expr::If(_)
+            | ast::Expr::Call(_)
+            | ast::Expr::ListComprehension(_)
+            | ast::Expr::Lambda(_) => unreachable!(),
         }
 
         for statement in expr.body.iter() {
@@ -368,6 +407,7 @@ impl<'a> AirBuilder<'a> {
 
     fn build_integrity_equality(
         &mut self,
+        span: SourceSpan,
         lhs: &ast::ScalarExpr,
         rhs: &ast::ScalarExpr,
         condition: Option<&ast::ScalarExpr>,
@@ -376,6 +416,17 @@ impl<'a> AirBuilder<'a> {
         let rhs = self.insert_scalar_expr(rhs);
         let condition = condition.as_ref().map(|cond| self.insert_scalar_expr(cond));
         let root = self.merge_equal_exprs(lhs, rhs, condition);
+        // A constraint which does not reference the trace, e.g. one over public inputs alone,
+        // cannot be enforced by the AIR, as it does not constrain the execution of the program.
+        if self.air.constraint_graph().base_degree(&root) == 0 {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid integrity constraint")
+                .with_primary_label(span, "this constraint does not reference the trace")
+                .with_note("Integrity constraints must reference at least one trace column. Constraints on public inputs alone must be checked by the verifier.")
+                .emit();
+            return Err(CompileError::Failed);
+        }
         // Get the trace segment and domain of the constraint.
         //
         // The default domain for integrity constraints is `EveryRow`
@@ -391,6 +442,272 @@ impl<'a> AirBuilder<'a> {
         Ok(())
     }
 
+    /// Lowers a bus interaction to the factor it contributes to the bus column.
+    ///
+    /// The factor is the encoding of the tuple (see [Self::encode_tuple]), and when a selector `s`
+    /// is present, the factor is `s * encoding + (1 - s)`, i.e. the
+    /// interaction has no effect on rows where the selector is zero.
+    fn build_bus_interaction(&mut self, expr: &ast::BusInteraction) -> Result<(), CompileError> {
+        let span = expr.span();
+        let arity = expr.args.len();
+
+        self.check_bus_arity(expr.bus, arity, span)?;
+
+        let encoding = self.encode_tuple(&expr.args, span)?;
+        let factor = match expr.selector.as_ref() {
+            None => encoding,
+            Some(selector) => {
+                let selector = self.insert_scalar_expr(selector);
+                let selected = self.insert_op(Operation::Mul(selector, encoding));
+                let one = self.insert_constant(1);
+                let unselected = self.insert_op(Operation::Sub(one, selector));
+                self.insert_op(Operation::Add(selected, unselected))
+            }
+        };
+
+        let bus = self.buses.get_mut(&expr.bus).unwrap();
+        match expr.op {
+            ast::BusOp::Send => bus.send.push(factor),
+            ast::BusOp::Receive => bus.receive.push(factor),
+        }
+
+        Ok(())
+    }
+
+    /// Records the arity of the tuples on `bus`, raising an error if it differs from the arity
+    /// of a previous interaction on the same bus.
+    fn check_bus_arity(
+        &mut self,
+        bus: Identifier,
+        arity: usize,
+        span: SourceSpan,
+    ) -> Result<(), CompileError> {
+        let bus = self
+            .buses
+            .get_mut(&bus)
+            .expect("reference to undeclared bus");
+        match bus.arity {
+            None => bus.arity = Some((arity, span)),
+            Some((expected, prev)) if expected != arity => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid bus interaction")
+                    .with_primary_label(span, format!("this tuple has {arity} elements"))
+                    .with_secondary_label(
+                        prev,
+                        format!("but this interaction on the same bus has {expected}"),
+                    )
+                    .with_note("All tuples sent or received on a bus must have the same number of elements.")
+                    .emit();
+                return Err(CompileError::Failed);
+            }
+            Some(_) => (),
+        }
+        Ok(())
+    }
+
+    /// Lowers a lookup to a running sum column appended to the first auxiliary trace segment,
+    /// following the LogUp argument.
+    ///
+    /// Given the encodings `t` and `q` of the table and query tuples (see [Self::encode_tuple]),
+    /// the multiplicity `m`, and the selector `s` of the query, the column `h` accumulates
+    /// `m / t - s / q` on each row, and must start and end with 0. As the encodings are in the
+    /// denominators, the transition constraint is `(h' - h) * t * q = m * q - s * t`.
+    fn build_lookup(&mut self, expr: &ast::Lookup) -> Result<(), CompileError> {
+        let span = expr.span();
+        let table = self.encode_tuple(&expr.table, span)?;
+        let query = self.encode_tuple(&expr.query, span)?;
+        let multiplicity = self.insert_scalar_expr(&expr.multiplicity);
+        let selected = match expr.selector.as_ref() {
+            None => table,
+            Some(selector) => {
+                let selector = self.insert_scalar_expr(selector);
+                self.insert_op(Operation::Mul(selector, table))
+            }
+        };
+
+        let column = self.air.trace_segment_widths[AUX_SEGMENT] as usize;
+        self.air.trace_segment_widths[AUX_SEGMENT] += 1;
+        let current = self.insert_op(Operation::Value(Value::TraceAccess(TraceAccess::new(
+            AUX_SEGMENT,
+            column,
+            0,
+        ))));
+        let next = self.insert_op(Operation::Value(Value::TraceAccess(TraceAccess::new(
+            AUX_SEGMENT,
+            column,
+            1,
+        ))));
+
+        // Boundary constraints: the running sum starts and ends with 0
+        let zero = self.insert_constant(0);
+        for domain in [ConstraintDomain::FirstRow, ConstraintDomain::LastRow] {
+            let root = self.merge_equal_exprs(current, zero, None);
+            self.air
+                .constraints
+                .insert_constraint(AUX_SEGMENT, root, domain);
+        }
+
+        // Transition constraint: (h' - h) * t * q = m * q - s * t
+        let delta = self.insert_op(Operation::Sub(next, current));
+        let lhs = self.insert_op(Operation::Mul(delta, table));
+        let lhs = self.insert_op(Operation::Mul(lhs, query));
+        let added = self.insert_op(Operation::Mul(multiplicity, query));
+        let rhs = self.insert_op(Operation::Sub(added, selected));
+        let root = self.merge_equal_exprs(lhs, rhs, None);
+        self.air
+            .constraints
+            .insert_constraint(AUX_SEGMENT, root, ConstraintDomain::EveryFrame(2));
+
+        Ok(())
+    }
+
+    /// Encodes a tuple `(a_0, .., a_n)` as a single value, i.e. `rand[0] + rand[1] * a_0 + .. + rand[n + 1] * a_n`
+    ///
+    /// Raises a diagnostic if there are not enough random values declared to encode the tuple.
+    fn encode_tuple(
+        &mut self,
+        values: &[ast::ScalarExpr],
+        span: SourceSpan,
+    ) -> Result<NodeIndex, CompileError> {
+        let values = values
+            .iter()
+            .map(|value| self.insert_scalar_expr(value))
+            .collect::<Vec<_>>();
+        self.encode_nodes(&values, span)
+    }
+
+    /// Encodes a tuple of values which have already been lowered, see [Self::encode_tuple].
+    fn encode_nodes(
+        &mut self,
+        values: &[NodeIndex],
+        span: SourceSpan,
+    ) -> Result<NodeIndex, CompileError> {
+        // Semantic analysis guarantees that random values are declared for the first auxiliary
+        // segment when buses or lookups are, as that is where their columns are allocated
+        let num_random_values = self.random_values.get(&AUX_SEGMENT).map_or(0, |rv| rv.size);
+        if values.len() >= num_random_values {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid tuple")
+                .with_primary_label(
+                    span,
+                    format!(
+                        "encoding this tuple requires {} random values, but only {num_random_values} are declared",
+                        values.len() + 1
+                    ),
+                )
+                .emit();
+            return Err(CompileError::Failed);
+        }
+
+        let mut encoding = self.insert_op(Operation::Value(Value::RandomValue(
+            RandomValueAccess::new(AUX_SEGMENT, 0),
+        )));
+        for (i, value) in values.iter().enumerate() {
+            let alpha = self.insert_op(Operation::Value(Value::RandomValue(
+                RandomValueAccess::new(AUX_SEGMENT, i + 1),
+            )));
+            let term = self.insert_op(Operation::Mul(alpha, *value));
+            encoding = self.insert_op(Operation::Add(encoding, term));
+        }
+        Ok(encoding)
+    }
+
+    /// Builds the constraints implied by the declared types of the trace columns.
+    ///
+    /// Columns of type `bool` are constrained such that `x^2 = x`, while columns of a bounded
+    /// integer type are sent on the bus which range checks values of that type, i.e. `rc.send(x)`.
+    fn build_column_types(&mut self) -> Result<(), CompileError> {
+        let bindings = self
+            .trace_columns
+            .iter()
+            .flat_map(|segment| segment.bindings.iter())
+            .filter(|binding| binding.column_type.is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for binding in bindings {
+            let name = binding.name.expect("typed trace bindings are always named");
+            let column_type = binding.column_type.unwrap();
+            for column in binding.offset..(binding.offset + binding.size) {
+                let value = self.insert_op(Operation::Value(Value::TraceAccess(TraceAccess::new(
+                    binding.segment,
+                    column,
+                    0,
+                ))));
+                if column_type.is_bounded_integer() {
+                    let bus = self
+                        .buses
+                        .iter()
+                        .find_map(|(bus, factors)| {
+                            (factors.range_check == Some(column_type)).then_some(*bus)
+                        })
+                        .expect("missing range check for bounded integer column");
+                    self.check_bus_arity(bus, 1, binding.span)?;
+                    let factor = self.encode_nodes(&[value], binding.span)?;
+                    self.buses.get_mut(&bus).unwrap().send.push(factor);
+                } else {
+                    let square = self.insert_op(Operation::Exp(value, 2));
+                    let root = self.insert_op(Operation::Sub(square, value));
+                    self.air.constraints.insert_implied_constraint(
+                        binding.segment,
+                        root,
+                        ConstraintDomain::EveryRow,
+                        name,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Allocates a column at the end of the first auxiliary trace segment for each bus, in order
+    /// of their names, and constrains it such that it starts and ends with 1, and on each row is
+    /// multiplied by the tuples received, and divided by the tuples sent, i.e. `p' * receives = p * sends`.
+    fn build_buses(&mut self) {
+        let buses = core::mem::take(&mut self.buses);
+        for bus in buses.into_values() {
+            let column = self.air.trace_segment_widths[AUX_SEGMENT] as usize;
+            self.air.trace_segment_widths[AUX_SEGMENT] += 1;
+
+            let current = self.insert_op(Operation::Value(Value::TraceAccess(TraceAccess::new(
+                AUX_SEGMENT,
+                column,
+                0,
+            ))));
+            let next = self.insert_op(Operation::Value(Value::TraceAccess(TraceAccess::new(
+                AUX_SEGMENT,
+                column,
+                1,
+            ))));
+
+            // Boundary constraints: the bus column starts and ends with 1
+            let one = self.insert_constant(1);
+            for domain in [ConstraintDomain::FirstRow, ConstraintDomain::LastRow] {
+                let root = self.merge_equal_exprs(current, one, None);
+                self.air
+                    .constraints
+                    .insert_constraint(AUX_SEGMENT, root, domain);
+            }
+
+            // Transition constraint: p' * receives = p * sends
+            let lhs = bus.receive.into_iter().fold(next, |acc, factor| {
+                self.insert_op(Operation::Mul(acc, factor))
+            });
+            let rhs = bus.send.into_iter().fold(current, |acc, factor| {
+                self.insert_op(Operation::Mul(acc, factor))
+            });
+            let root = self.merge_equal_exprs(lhs, rhs, None);
+            self.air.constraints.insert_constraint(
+                AUX_SEGMENT,
+                root,
+                ConstraintDomain::EveryFrame(2),
+            );
+        }
+    }
+
     fn merge_equal_exprs(
         &mut self,
         lhs: NodeIndex,
@@ -412,7 +729,9 @@ impl<'a> AirBuilder<'a> {
             }
             ast::ScalarExpr::SymbolAccess(access) => self.insert_symbol_access(access),
             ast::ScalarExpr::Binary(expr) => self.insert_binary_expr(expr),
-            ast::ScalarExpr::Call(_) | ast::ScalarExpr::BoundedSymbolAccess(_) => unreachable!(),
+            ast::ScalarExpr::If(_)
+            | ast::ScalarExpr::Call(_)
+            | ast::ScalarExpr::BoundedSymbolAccess(_) => unreachable!(),
         }
     }
 
@@ -523,25 +842,35 @@ impl<'a> AirBuilder<'a> {
         }
     }
 
-    fn random_value_access(&self, access: &ast::SymbolAccess) -> Option<usize> {
-        let rv = self.random_values.as_ref()?;
+    fn random_value_access(&self, access: &ast::SymbolAccess) -> Option<RandomValueAccess> {
         let id = access.name.as_ref();
-        if rv.name == id {
-            if let AccessType::Index(index) = access.access_type {
-                assert!(index < rv.size);
-                return Some(index);
-            } else {
-                // This should have been caught earlier during compilation
-                unreachable!("invalid access to random values array: {:#?}", access);
+        for rv in self.random_values.values() {
+            if rv.name == id {
+                if let AccessType::Index(index) = access.access_type {
+                    assert!(index < rv.size);
+                    return Some(RandomValueAccess::new(rv.segment, index));
+                } else {
+                    // This should have been caught earlier during compilation
+                    unreachable!("invalid access to random values array: {:#?}", access);
+                }
             }
         }
 
         // This must be a reference to a binding, if it is a random value access
-        let binding = rv.bindings.iter().find(|rb| rb.name == id)?;
+        let (segment, binding) = self.random_values.values().find_map(|rv| {
+            rv.bindings
+                .iter()
+                .find(|rb| rb.name == id)
+                .map(|rb| (rv.segment, rb))
+        })?;
 
         match access.access_type {
-            AccessType::Default if binding.size == 1 => Some(binding.offset),
-            AccessType::Index(extra) if binding.size > 1 => Some(binding.offset + extra),
+            AccessType::Default if binding.size == 1 => {
+                Some(RandomValueAccess::new(segment, binding.offset))
+            }
+            AccessType::Index(extra) if binding.size > 1 => {
+                Some(RandomValueAccess::new(segment, binding.offset + extra))
+            }
             // This should have been caught earlier during compilation
             _ => unreachable!(
                 "unexpected random value access type encountered during lowering: {:#?}",
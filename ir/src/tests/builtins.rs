@@ -0,0 +1,161 @@
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn constraint_builtins() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s, op[3], limbs[2], word]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf is_binary(s)
+        enf one_hot(op)
+        enf decompose(word, limbs, 256)";
+
+    let air = compile(source).expect("compilation failed");
+    // 1 for is_binary, 3 + 1 for one_hot, and 1 for decompose
+    assert_eq!(air.validity_constraints(0).count(), 6);
+}
+
+#[test]
+fn constraint_builtins_in_evaluator() {
+    let source = "
+    def test
+    ev limbs<N>([x, l[N]]):
+        enf is_binary(b) for b in l
+        enf decompose(x, l, 2)
+    trace_columns:
+        main: [clk, x, l[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf limbs([x, l])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.validity_constraints(0).count(), 5);
+}
+
+#[test]
+fn bits_to_field() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a, bits[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        let x = bits_to_field(bits)
+        let y = bits_to_field([1, 0, 1])
+        enf a = x + y";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn bits_to_field_in_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a, bits[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf a = bits_to_field(bits)
+        enf bits_to_field(bits) = a'
+        let v = bits_to_field(bits) * 2
+        enf clk' = v
+        enf b' = b + bits_to_field([b, a]) for b in bits
+        enf clk' = clk when bits_to_field(bits)";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 8);
+}
+
+#[test]
+fn err_constraint_builtin_as_value() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = is_binary(a)
+        enf clk' = x";
+
+    expect_diagnostic(
+        source,
+        "this function is a constraint, and may only be used with `enf`",
+    );
+}
+
+#[test]
+fn err_constraint_builtin_invalid_argument() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf one_hot(clk)";
+
+    expect_diagnostic(source, "this function expects an argument of vector type");
+}
+
+#[test]
+fn err_decompose_non_constant_base() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, x, l[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf decompose(x, l, clk)";
+
+    expect_diagnostic(
+        source,
+        "the base of a decomposition must be a constant, and at least 2",
+    );
+}
+
+#[test]
+fn err_value_builtin_as_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf sum(a)";
+
+    expect_diagnostic(
+        source,
+        "calls in constraints must be to evaluator functions",
+    );
+}
@@ -0,0 +1,80 @@
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn buses_lowered_to_aux_columns() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s, a, b]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [3]
+    buses:
+        p: multiset
+        q: multiset
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = c * $rand[0]
+        p.send(a, b) when s
+        p.receive(b, a) when 1 - s
+        q.receive(a)";
+
+    let air = compile(source).expect("compilation failed");
+    // One column is appended to the first auxiliary segment for each bus
+    assert_eq!(air.trace_segment_widths, vec![3, 3]);
+    // Each bus column is constrained to start and end with 1
+    assert_eq!(air.num_boundary_constraints(1), 5);
+    // Each bus column has a single transition constraint
+    assert_eq!(air.transition_constraints(1).count(), 3);
+}
+
+#[test]
+fn err_bus_tuple_too_large() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    buses:
+        p: multiset
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = c * $rand[0]
+        p.send(a, b)";
+
+    expect_diagnostic(
+        source,
+        "encoding this tuple requires 3 random values, but only 2 are declared",
+    );
+}
+
+#[test]
+fn err_bus_tuple_arity_mismatch() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [3]
+    buses:
+        p: multiset
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = c * $rand[0]
+        p.send(a, b)
+        p.receive(a)";
+
+    expect_diagnostic(source, "but this interaction on the same bus has 2");
+}
@@ -0,0 +1,58 @@
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn conditionals_in_comprehension() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[4]]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [if i == 0 then 1 else 0 for i in 0..8]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + k
+        enf x' = x * (if i < 2 then 2 else 3) for (x, i) in (a, 0..4)";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.transition_constraints(0).count(), 5);
+}
+
+#[test]
+fn conditionals_on_const_params() {
+    let source = "
+    def test
+    ev shift<N>([x[N]]):
+        enf y' = y * (if j == N - 1 then 0 else 1) for (y, j) in (x, 0..N)
+    trace_columns:
+        main: [clk, a[3]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf shift([a])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.transition_constraints(0).count(), 4);
+}
+
+#[test]
+fn err_conditional_on_trace_values() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = if a == 0 then clk else 1
+        enf clk' = x";
+
+    expect_diagnostic(source, "this condition is not known at compile-time");
+}
@@ -1,5 +1,22 @@
+use crate::{Air, Operation, Value};
+
 use super::{compile, expect_diagnostic};
 
+/// The modulus of the field over which constants are folded
+const MODULUS: u64 = 0xffff_ffff_0000_0001;
+
+/// Returns the values of all constants in the constraint graph of `air`
+fn constants(air: &Air) -> Vec<u64> {
+    air.constraint_graph()
+        .nodes()
+        .iter()
+        .filter_map(|node| match node.op() {
+            Operation::Value(Value::Constant(value)) => Some(*value),
+            _ => None,
+        })
+        .collect()
+}
+
 #[test]
 fn boundary_constraint_with_constants() {
     let source = "
@@ -56,3 +73,120 @@ fn invalid_matrix_constant() {
 
     expect_diagnostic(source, "invalid matrix literal: mismatched dimensions");
 }
+
+#[test]
+fn integrity_constraint_with_negated_constants() {
+    let source = "
+    def test
+    const A = 123
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + -A
+        enf a' = -a * -1";
+
+    let air = compile(source).expect("compilation failed");
+    // Negated constants are folded to their field representation, i.e. `p - n`
+    let constants = constants(&air);
+    assert!(constants.contains(&(MODULUS - 123)));
+    assert!(constants.contains(&(MODULUS - 1)));
+}
+
+#[test]
+fn integrity_constraint_with_large_constants() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c, d]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a * (2^40 - 1)
+        enf b' = b * (2^32 - 2^40)
+        enf c' = c * (2^63 + 2^63 + 2^63)
+        enf d' = d * (2^33 - 2^63 - 2^63)";
+
+    let air = compile(source).expect("compilation failed");
+    let constants = constants(&air);
+    assert!(constants.contains(&((1 << 40) - 1)));
+    assert!(constants.contains(&(MODULUS - (1 << 40) + (1 << 32))));
+    // 3 * 2^63 = 2^64 + 2^63 = 2^63 + 2^32 - 1 (mod p)
+    assert!(constants.contains(&((1 << 63) + (1 << 32) - 1)));
+    // 2^33 - 2^64 = 2^33 - 2^32 + 1 = 2^32 + 1 (mod p)
+    assert!(constants.contains(&((1 << 32) + 1)));
+}
+
+#[test]
+fn integrity_constraint_with_constant_division() {
+    let source = "
+    def test
+    const A = 3
+    trace_columns:
+        main: [clk, a, b[2]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 1 / 2
+    integrity_constraints:
+        let halves = [x / 2 for x in 1..3]
+        enf clk' = clk * inv(A) + 2 / A
+        enf a' = a * halves[1]
+        enf x' = x * (1 / (i + 1)) for (x, i) in (b, 0..2)";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn err_constant_division_by_zero() {
+    let source = "
+    def test
+    const A = 0
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk * (1 / A)";
+
+    expect_diagnostic(source, "division by zero");
+}
+
+#[test]
+fn err_non_constant_division() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = 1 / clk";
+
+    expect_diagnostic(source, "expected this to be a constant");
+}
+
+#[test]
+fn err_non_constant_inverse() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = inv(clk)";
+
+    expect_diagnostic(source, "expected this to be a constant");
+}
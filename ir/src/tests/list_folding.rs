@@ -1,4 +1,4 @@
-use super::compile;
+use super::{compile, expect_diagnostic};
 
 #[test]
 fn list_folding_on_const() {
@@ -99,3 +99,136 @@ fn list_folding_in_lc() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn list_folding_in_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[4], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf c[2].first = 0
+
+    integrity_constraints:
+        enf clk' = sum(fmp) + prod([c * d for (c, d) in (c, d)])
+        enf m' = m * sum([m, ctx]) for m in fmp
+        enf a' = a + fold(c, 0, |acc, ci| acc * 2 + ci)";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn fold_on_const() {
+    let source = "
+    def test
+    const A = [1, 2, 3]
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = fold(A, 5, |acc, a| acc * 2 + a)
+        enf clk = x";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn fold_on_variable() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = fold(c, 0, |acc, ci| acc * $rand[0] + ci)
+        let y = [a, b, clk]
+        let z = fold(y, x, |acc, v| v - acc)
+        enf a' = a * z";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn fold_on_lc() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+        aux: [a, b, c[4], d[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf c[2].first = 0
+    integrity_constraints:
+        let x = fold([c * d for (c, d) in (c, d)], 1, |acc, cd| acc * cd + 1)
+        enf clk = x";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn err_fold_invalid_lambda() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = fold(fmp, 0, |acc| acc + 1)
+        enf clk = x";
+
+    expect_diagnostic(source, "this function expects a lambda of 2 parameters");
+}
+
+#[test]
+fn err_fold_lambda_shadows_binding() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = fold(fmp, 0, |clk, f| clk + f)
+        enf clk = x";
+
+    expect_diagnostic(source, "this name is already bound in this scope");
+}
+
+#[test]
+fn err_lambda_outside_fold() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = sum(fmp, |acc, f| acc + f)
+        enf clk = x";
+
+    expect_diagnostic(
+        source,
+        "lambdas may only be used as the combining function of `fold`",
+    );
+}
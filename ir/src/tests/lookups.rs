@@ -0,0 +1,84 @@
+use crate::IntegrityConstraintDegree;
+
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn lookup_lowered_to_running_sum_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s, a, b, t0, t1, m]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [3]
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = c * $rand[0]
+        enf lookup([t0, t1], [a, b], m) when s";
+
+    let air = compile(source).expect("compilation failed");
+    // The running sum column is appended to the first auxiliary segment
+    assert_eq!(air.trace_segment_widths, vec![6, 2]);
+    // The running sum is constrained to start and end with 0
+    assert_eq!(air.num_boundary_constraints(1), 3);
+    // (h' - h) * t * q = m * q - s * t has the degree of (h' - h) * t * q
+    assert_eq!(
+        air.integrity_constraint_degrees(1),
+        vec![
+            IntegrityConstraintDegree::new(1),
+            IntegrityConstraintDegree::new(3)
+        ]
+    );
+}
+
+#[test]
+fn lookup_with_periodic_table() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, m]
+        aux: [c]
+    periodic_columns:
+        k: [0, 1, 2, 3]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = c * $rand[0]
+        enf lookup([k], [a], m)";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(1)[1],
+        IntegrityConstraintDegree::with_cycles(2, vec![4])
+    );
+}
+
+#[test]
+fn err_lookup_tuple_too_large() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, t0, t1, m]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf c.first = 1
+    integrity_constraints:
+        enf c' = c * $rand[0]
+        enf lookup([t0, t1], [a, b], m)";
+
+    expect_diagnostic(
+        source,
+        "encoding this tuple requires 3 random values, but only 2 are declared",
+    );
+}
@@ -1,9 +1,13 @@
 mod access;
 mod boundary_constraints;
+mod builtins;
+mod buses;
+mod conditionals;
 mod constant;
 mod evaluators;
 mod integrity_constraints;
 mod list_folding;
+mod lookups;
 mod pub_inputs;
 mod random_values;
 mod selectors;
@@ -1,4 +1,8 @@
-use super::compile;
+use miden_diagnostics::SourceSpan;
+
+use crate::{Identifier, Symbol};
+
+use super::{compile, expect_diagnostic};
 
 #[test]
 fn bc_with_public_inputs() {
@@ -15,3 +19,67 @@ fn bc_with_public_inputs() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn ic_with_public_inputs() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+        aux: [p]
+    public_inputs:
+        program_hash: [4]
+        modulus: [1]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf a' = a * modulus[0] + program_hash[0]
+        enf p' = p * (program_hash[1] + $rand[0])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.transition_constraints(0).count(), 2);
+    assert_eq!(air.transition_constraints(1).count(), 1);
+}
+
+#[test]
+fn err_ic_with_public_inputs_only() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        modulus: [1]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf modulus[0] = 7";
+
+    expect_diagnostic(source, "this constraint does not reference the trace");
+}
+
+#[test]
+fn variable_length_public_inputs() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+        outputs: [_]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+        enf a.last = outputs[5]
+    integrity_constraints:
+        enf a' = a + clk * outputs[1]";
+
+    let air = compile(source).expect("compilation failed");
+    // The highest index accessed determines how many elements must be provided at runtime
+    let outputs = Identifier::new(SourceSpan::UNKNOWN, Symbol::intern("outputs"));
+    assert_eq!(air.public_input_min_len(outputs), 6);
+    let stack_inputs = Identifier::new(SourceSpan::UNKNOWN, Symbol::intern("stack_inputs"));
+    assert_eq!(air.public_input_min_len(stack_inputs), 1);
+}
@@ -60,6 +60,51 @@ fn random_values_named_access() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn random_values_per_segment() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+        aux2: [e]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+        gammas: [m, n[3]] for aux2
+    boundary_constraints:
+        enf c.first = $rand[1] * 2
+        enf e.last = n[2]
+    integrity_constraints:
+        enf e' = e * (c + m) + $gammas[3]";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.num_random_values, vec![0, 2, 4]);
+    assert_eq!(air.random_value_offset(2), 2);
+}
+
+#[test]
+fn err_random_values_in_bc_against_earlier_segment() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+        aux2: [e]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+        gammas: [4] for aux2
+    boundary_constraints:
+        enf c.first = $gammas[0]
+    integrity_constraints:
+        enf e' = e * $rand[0]";
+
+    expect_diagnostic(source, "Boundary constraints require both sides of the constraint to apply to the same trace segment");
+}
+
 #[test]
 fn err_random_values_out_of_bounds_no_bindings() {
     let source = "
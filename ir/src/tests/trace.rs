@@ -18,6 +18,52 @@ fn trace_columns_index_access() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn trace_columns_multiple_aux_segments() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+        aux: [c, d]
+        aux2: [e]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf a.first = 1
+        enf e.first = 1
+    integrity_constraints:
+        enf c' = c * (a + $rand[0])
+        enf $aux2[0]' = e * (d + $rand[1])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.trace_segment_widths, vec![2, 2, 1]);
+    assert_eq!(air.aux_segments().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(air.aux_column_offset(2), 2);
+    assert_eq!(air.num_boundary_constraints(2), 1);
+    assert_eq!(air.integrity_constraints(1).len(), 1);
+    assert_eq!(air.integrity_constraints(2).len(), 1);
+}
+
+// The codegen backends reject this program, but it is lowered to the IR with the constraints of
+// each auxiliary segment kept apart
+#[test]
+fn multi_aux_trace_segments() {
+    let source = include_str!("../../../air-script/tests/multi_aux_trace/multi_aux_trace.air");
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.trace_segment_widths, vec![3, 2, 1]);
+    assert_eq!(air.aux_segments().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(air.random_value_offset(2), 2);
+    assert_eq!(air.num_boundary_constraints(0), 1);
+    assert_eq!(air.num_boundary_constraints(1), 2);
+    assert_eq!(air.num_boundary_constraints(2), 2);
+    assert_eq!(air.integrity_constraints(0).len(), 1);
+    assert_eq!(air.integrity_constraints(1).len(), 2);
+    assert_eq!(air.integrity_constraints(2).len(), 1);
+}
+
 #[test]
 fn trace_cols_groups() {
     let source = "
@@ -133,3 +179,37 @@ fn err_ic_trace_cols_group_used_as_scalar() {
 
     expect_diagnostic(source, "type mismatch");
 }
+
+#[test]
+fn trace_columns_typed() {
+    let source = "
+    def test
+    trace_columns:
+        main: [s[2]: bool, limb[2]: u16, a]
+        aux: [c]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    buses:
+        rc: multiset for u16
+    boundary_constraints:
+        enf a.first = 1
+    integrity_constraints:
+        enf a' = a + s[0] + s[1]
+        rc.receive(a)";
+
+    let air = compile(source).expect("compilation failed");
+    // Each bool column is constrained to be binary, and the constraints are tagged as implied
+    let implied = air
+        .integrity_constraints(0)
+        .iter()
+        .filter_map(|constraint| constraint.implied_by())
+        .map(|binding| binding.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(implied, vec!["s", "s"]);
+    assert_eq!(air.integrity_constraint_degrees(0).len(), 3);
+    // Each u16 column is sent on the range check bus, which adds a single column
+    assert_eq!(air.trace_segment_widths, vec![5, 2]);
+    assert_eq!(air.transition_constraints(1).count(), 1);
+}
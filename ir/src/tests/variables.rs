@@ -152,7 +152,7 @@ fn invalid_matrix_literal_with_leading_vector_binding() {
         let d = [a[0], [3, 4]]
         enf clk' = d[0][0]";
 
-    expect_diagnostic(source, "expected one of: '\"!\"', '\"(\"', 'decl_ident_ref', 'function_identifier', 'identifier', 'int'");
+    expect_diagnostic(source, "expected one of: '\"!\"', '\"(\"', '\"-\"', '\"if\"', 'decl_ident_ref', 'function_identifier', 'identifier', 'int'");
 }
 
 #[test]
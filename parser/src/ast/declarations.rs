@@ -12,6 +12,7 @@
 //! * `trace_columns`
 //! * `public_inputs`
 //! * `random_values`
+//! * `buses`
 //! * `boundary_constraints`
 //! * `integrity_constraints`
 //!
@@ -22,9 +23,9 @@
 //! * evaluators
 //! * pure functions
 //!
-//! There is no notion of public/private visiblity, so any declaration of the above types may be
-//! imported into another module, and "wildcard" imports will import all importable items.
-use std::{collections::HashSet, fmt};
+//! Importable items are private to the module they are declared in unless marked `pub`, and only
+//! public items may be imported into another module. "Wildcard" imports will import all public items.
+use std::{collections::HashMap, fmt};
 
 use miden_diagnostics::{SourceSpan, Spanned};
 
@@ -37,6 +38,10 @@ pub enum Declaration {
     Import(Span<Import>),
     /// A constant value declaration
     Constant(Constant),
+    /// A compile-time assertion
+    ///
+    /// Assertions may appear any number of times, in any module of the program
+    Assertion(Assertion),
     /// An evaluator function definition
     ///
     /// Evaluator functions can be defined in any module of the program
@@ -54,7 +59,12 @@ pub enum Declaration {
     ///
     /// There may only be one of these in the entire program, and it must
     /// appear in the root AirScript module, i.e. in a module declared with `def`
-    RandomValues(RandomValues),
+    RandomValues(Span<Vec<RandomValues>>),
+    /// A `buses` section declaration
+    ///
+    /// There may only be one of these in the entire program, and it must
+    /// appear in the root AirScript module, i.e. in a module declared with `def`
+    Buses(Span<Vec<Bus>>),
     /// A `trace_bindings` section declaration
     ///
     /// There may only be one of these in the entire program, and it must
@@ -72,6 +82,38 @@ pub enum Declaration {
     IntegrityConstraints(Span<Vec<Statement>>),
 }
 
+/// A compile-time assertion, e.g. `assert NUM_LIMBS * LIMB_BITS == 64 : "limb layout must cover 64 bits"`
+///
+/// The condition is evaluated during constant propagation, and compilation fails with the given
+/// message if it does not hold.
+#[derive(Debug, Clone, Spanned)]
+pub struct Assertion {
+    #[span]
+    pub span: SourceSpan,
+    pub condition: Comparison,
+    pub message: Symbol,
+}
+impl Assertion {
+    pub const fn new(span: SourceSpan, condition: Comparison, message: Symbol) -> Self {
+        Self {
+            span,
+            condition,
+            message,
+        }
+    }
+}
+impl Eq for Assertion {}
+impl PartialEq for Assertion {
+    fn eq(&self, other: &Self) -> bool {
+        self.condition == other.condition && self.message == other.message
+    }
+}
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "assert {} : \"{}\"", &self.condition, self.message)
+    }
+}
+
 /// Stores a constant's name and value. There are three types of constants:
 ///
 /// * Scalar: 123
@@ -83,11 +125,44 @@ pub struct Constant {
     pub span: SourceSpan,
     pub name: Identifier,
     pub value: ConstantExpr,
+    pub visibility: Visibility,
+    /// The expression defining the value of this constant, if it was not given as a literal
+    ///
+    /// This is `None` once the expression has been evaluated to `value` by constant propagation.
+    pub definition: Option<ScalarExpr>,
 }
 impl Constant {
-    /// Returns a new instance of a [Constant]
+    /// Returns a new instance of a private [Constant]
     pub const fn new(span: SourceSpan, name: Identifier, value: ConstantExpr) -> Self {
-        Self { span, name, value }
+        Self {
+            span,
+            name,
+            value,
+            visibility: Visibility::Private,
+            definition: None,
+        }
+    }
+
+    /// Returns a new instance of a private scalar [Constant] whose value is given by `definition`,
+    /// e.g. a conditional expression
+    pub const fn with_definition(
+        span: SourceSpan,
+        name: Identifier,
+        definition: ScalarExpr,
+    ) -> Self {
+        Self {
+            span,
+            name,
+            value: ConstantExpr::Scalar(0),
+            visibility: Visibility::Private,
+            definition: Some(definition),
+        }
+    }
+
+    /// Sets the visibility of this constant
+    pub const fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
     }
 
     /// Gets the type of the value associated with this constant
@@ -98,7 +173,10 @@ impl Constant {
 impl Eq for Constant {}
 impl PartialEq for Constant {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.value == other.value
+        self.name == other.name
+            && self.value == other.value
+            && self.visibility == other.visibility
+            && self.definition == other.definition
     }
 }
 
@@ -158,15 +236,27 @@ pub enum Import {
     /// Imports all items from `module`
     All { module: ModuleId },
     /// Imports `items` from `module`
+    ///
+    /// Each item is keyed by the name it is bound to in the importing module, and maps to
+    /// the name of the item in `module`. The two differ only when the item is renamed on
+    /// import, e.g. `use bitwise::{MASK as BITWISE_MASK}`.
     Partial {
         module: ModuleId,
-        items: HashSet<Identifier>,
+        items: HashMap<Identifier, Identifier>,
     },
+    /// Makes the items of `module` accessible by qualifying them with `alias`, e.g. `use hash as h`
+    /// allows referring to `round` from `hash` as `h::round`.
+    ///
+    /// Aliases are recorded in [Module::aliases] when the module is constructed, so this variant
+    /// is never found in [Module::imports].
+    Alias { module: ModuleId, alias: Identifier },
 }
 impl Import {
     pub fn module(&self) -> ModuleId {
         match self {
-            Self::All { module } | Self::Partial { module, .. } => *module,
+            Self::All { module } | Self::Partial { module, .. } | Self::Alias { module, .. } => {
+                *module
+            }
         }
     }
 }
@@ -184,15 +274,41 @@ impl PartialEq for Import {
                     module: r,
                     items: rs,
                 },
-            ) if l == r => ls.difference(rs).next().is_none(),
+            ) if l == r => ls == rs,
+            (
+                Self::Alias {
+                    module: l,
+                    alias: la,
+                },
+                Self::Alias {
+                    module: r,
+                    alias: ra,
+                },
+            ) => l == r && la == ra,
             _ => false,
         }
     }
 }
 
+/// The visibility of an item declared in a module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Visibility {
+    /// The item may only be referenced from within the module it is declared in
+    #[default]
+    Private,
+    /// The item may also be imported by other modules, i.e. it is declared with `pub`
+    Public,
+}
+impl Visibility {
+    #[inline]
+    pub fn is_public(&self) -> bool {
+        matches!(self, Self::Public)
+    }
+}
+
 /// Represents an item exported from a module
 ///
-/// Currently, only constants and functions are exported.
+/// Currently, only constants and functions can be exported, and only when they are public.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Export<'a> {
     Constant(&'a crate::ast::Constant),
@@ -206,6 +322,22 @@ impl Export<'_> {
         }
     }
 
+    /// Returns the span of the declaration of this item
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Self::Constant(item) => item.span,
+            Self::Evaluator(item) => item.span,
+        }
+    }
+
+    /// Returns the visibility of this item
+    pub fn visibility(&self) -> Visibility {
+        match self {
+            Self::Constant(item) => item.visibility,
+            Self::Evaluator(item) => item.visibility,
+        }
+    }
+
     /// Returns the type of the value associated with this export
     ///
     /// NOTE: Evaluator functions have no return value, so they have no type associated.
@@ -224,26 +356,60 @@ impl Export<'_> {
 /// for the periodic column should be the cycle of values that will be repeated. The
 /// length of the values vector is expected to be a power of 2 with a minimum length of 2,
 /// which is enforced during semantic analysis.
+///
+/// The values may also be given by an expression over constants, e.g. a comprehension like
+/// `[2^i for i in 0..64]`, or a reference to a constant like `ROUND_CONSTANTS[3]`, in which case
+/// they are evaluated during constant propagation.
 #[derive(Debug, Clone, Spanned)]
 pub struct PeriodicColumn {
     #[span]
     pub span: SourceSpan,
     pub name: Identifier,
     pub values: Vec<u64>,
+    /// The expression defining the values of this column, if they were not given as a literal
+    ///
+    /// This is `None` once the expression has been evaluated to `values` by constant propagation.
+    pub definition: Option<Expr>,
 }
 impl PeriodicColumn {
+    /// The minimum cycle length of a periodic column
+    pub const MIN_CYCLE_LENGTH: usize = 2;
+
     pub const fn new(span: SourceSpan, name: Identifier, values: Vec<u64>) -> Self {
-        Self { span, name, values }
+        Self {
+            span,
+            name,
+            values,
+            definition: None,
+        }
+    }
+
+    /// Creates a periodic column whose values are given by `definition`, e.g. a comprehension
+    pub const fn with_definition(span: SourceSpan, name: Identifier, definition: Expr) -> Self {
+        Self {
+            span,
+            name,
+            values: vec![],
+            definition: Some(definition),
+        }
     }
 
     pub fn period(&self) -> usize {
         self.values.len()
     }
+
+    /// Returns true if `period` is a valid cycle length for a periodic column, i.e. a power of two
+    /// which is at least [Self::MIN_CYCLE_LENGTH]
+    pub fn is_valid_period(period: usize) -> bool {
+        period >= Self::MIN_CYCLE_LENGTH && period.is_power_of_two()
+    }
 }
 impl Eq for PeriodicColumn {}
 impl PartialEq for PeriodicColumn {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.values == other.values
+        self.name == other.name
+            && self.values == other.values
+            && self.definition == other.definition
     }
 }
 
@@ -251,14 +417,15 @@ impl PartialEq for PeriodicColumn {
 ///
 /// This declaration is only permitted in the root module.
 ///
-/// Public inputs are represented by a named identifier which is used to identify a fixed
-/// size array of length `size`.
+/// Public inputs are represented by a named identifier which is used to identify an array
+/// of length `size`, or of a length which is only known at runtime, e.g. `outputs: [_]`.
 #[derive(Debug, Clone, Spanned)]
 pub struct PublicInput {
     #[span]
     pub span: SourceSpan,
     pub name: Identifier,
-    pub size: usize,
+    /// The number of elements in this public input, or `None` if it is variable-length
+    pub size: Option<usize>,
 }
 impl PublicInput {
     #[inline]
@@ -266,9 +433,31 @@ impl PublicInput {
         Self {
             span,
             name,
-            size: size.try_into().unwrap(),
+            size: Some(size.try_into().unwrap()),
+        }
+    }
+
+    /// Creates a public input whose length is only known at runtime, e.g. `outputs: [_]`
+    #[inline]
+    pub fn variable_length(span: SourceSpan, name: Identifier) -> Self {
+        Self {
+            span,
+            name,
+            size: None,
         }
     }
+
+    /// Returns true if the length of this public input is only known at runtime
+    #[inline]
+    pub fn is_variable_length(&self) -> bool {
+        self.size.is_none()
+    }
+
+    /// Returns the type of this public input, or `None` if it is variable-length, in which case
+    /// it may only be accessed by index.
+    pub fn ty(&self) -> Option<Type> {
+        self.size.map(Type::Vector)
+    }
 }
 impl Eq for PublicInput {}
 impl PartialEq for PublicInput {
@@ -307,6 +496,16 @@ impl PartialEq for PublicInput {
 /// one for `a`, and one for `b`, with sizes `1` and `12` respectively. The size of the overall
 /// [RandomValues] instance in that case would be `13`.
 ///
+/// Random values are drawn by the verifier once the trace segment preceding the one which consumes
+/// them has been committed to. By default, an array is consumed by the first auxiliary segment,
+/// but several arrays may be declared, each for a different auxiliary segment:
+///
+/// ```airscript
+/// random_values:
+///     rand: [2]
+///     gammas: [4] for aux2
+/// ```
+///
 #[derive(Clone, Spanned)]
 pub struct RandomValues {
     #[span]
@@ -315,6 +514,8 @@ pub struct RandomValues {
     pub name: Identifier,
     /// The size of the array
     pub size: usize,
+    /// The auxiliary trace segment which consumes these random values
+    pub segment: TraceSegmentId,
     /// Zero or more bindings for individual elements or groups of elements
     pub bindings: Vec<RandBinding>,
 }
@@ -325,6 +526,7 @@ impl RandomValues {
             span,
             name,
             size,
+            segment: 1,
             bindings: vec![],
         }
     }
@@ -351,14 +553,24 @@ impl RandomValues {
             span,
             name,
             size: offset,
+            segment: 1,
             bindings,
         }
     }
+
+    /// Ties this array to the auxiliary trace segment `segment`, instead of the first one
+    pub fn for_segment(mut self, segment: TraceSegmentId) -> Self {
+        self.segment = segment;
+        self
+    }
 }
 impl Eq for RandomValues {}
 impl PartialEq for RandomValues {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.size == other.size && self.bindings == other.bindings
+        self.name == other.name
+            && self.size == other.size
+            && self.segment == other.segment
+            && self.bindings == other.bindings
     }
 }
 impl fmt::Debug for RandomValues {
@@ -366,6 +578,7 @@ impl fmt::Debug for RandomValues {
         f.debug_struct("RandomValues")
             .field("name", &self.name)
             .field("size", &self.size)
+            .field("segment", &self.segment)
             .field("bindings", &self.bindings)
             .finish()
     }
@@ -378,9 +591,13 @@ impl fmt::Display for RandomValues {
             write!(f, "{}: ", self.name)?;
         }
         if self.bindings.is_empty() {
-            write!(f, "[{}]", self.size)
+            write!(f, "[{}]", self.size)?;
         } else {
-            write!(f, "{}", DisplayList(self.bindings.as_slice()))
+            write!(f, "{}", DisplayList(self.bindings.as_slice()))?;
+        }
+        match self.segment {
+            1 => Ok(()),
+            n => write!(f, " for aux{}", n),
         }
     }
 }
@@ -490,19 +707,93 @@ impl fmt::Display for RandBinding {
     }
 }
 
+/// Declaration of a bus in an AirScript program.
+///
+/// This declaration is only permitted in the root module.
+///
+/// A bus is a named channel on which tuples of values are sent and received, see [BusInteraction].
+/// Each bus is lowered to an auxiliary column enforcing that every tuple sent on it is also received.
+#[derive(Debug, Copy, Clone, Spanned)]
+pub struct Bus {
+    #[span]
+    pub span: SourceSpan,
+    pub name: Identifier,
+    pub kind: BusKind,
+    /// The bounded integer type range checked by this bus, if any, e.g. `u16` in `rc: multiset for u16`
+    ///
+    /// The values of every column declared with that type are sent on this bus.
+    pub range_check: Option<ColumnType>,
+}
+impl Bus {
+    pub const fn new(span: SourceSpan, name: Identifier, kind: BusKind) -> Self {
+        Self {
+            span,
+            name,
+            kind,
+            range_check: None,
+        }
+    }
+
+    /// Declares this bus as the range check for columns of type `ty`
+    pub const fn with_range_check(mut self, ty: ColumnType) -> Self {
+        self.range_check = Some(ty);
+        self
+    }
+}
+impl Eq for Bus {}
+impl PartialEq for Bus {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.kind == other.kind && self.range_check == other.range_check
+    }
+}
+impl fmt::Display for Bus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.kind)?;
+        if let Some(ty) = self.range_check {
+            write!(f, " for {}", ty)?;
+        }
+        Ok(())
+    }
+}
+
+/// The kinds of bus which may be declared
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BusKind {
+    /// A multiset check, implemented by a running product column
+    Multiset,
+}
+impl fmt::Display for BusKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Multiset => f.write_str("multiset"),
+        }
+    }
+}
+
 /// Evaluator functions take a vector of trace bindings as parameters where each trace binding
 /// represents one or a group of columns in the execution trace that are passed to the evaluator
 /// function, and enforce integrity constraints on those trace columns.
+///
+/// The trace bindings may be followed by value parameters, which bind field elements, or vectors
+/// of field elements, such as random values, constants, or public inputs.
+///
+/// An evaluator may also declare const parameters, e.g. `ev limbs<N>([x[N]])`, which are used to
+/// size its trace bindings. The values of these parameters are inferred from the arguments at each
+/// call site, and the function is instantiated separately for each call during inlining.
 #[derive(Debug, Clone, Spanned)]
 pub struct EvaluatorFunction {
     #[span]
     pub span: SourceSpan,
     pub name: Identifier,
+    /// The const parameters of this function, e.g. `N` in `ev limbs<N>([x[N]])`
+    pub const_params: Vec<Identifier>,
     pub params: Vec<TraceSegment>,
+    pub value_params: Vec<ValueParam>,
     pub body: Vec<Statement>,
+    pub visibility: Visibility,
 }
 impl EvaluatorFunction {
-    /// Creates a new function.
+    /// Creates a new private function.
     pub const fn new(
         span: SourceSpan,
         name: Identifier,
@@ -512,14 +803,82 @@ impl EvaluatorFunction {
         Self {
             span,
             name,
+            const_params: vec![],
             params,
+            value_params: vec![],
             body,
+            visibility: Visibility::Private,
         }
     }
+
+    /// Sets the const parameters of this function, which determine the size of its trace bindings
+    pub fn with_const_params(mut self, const_params: Vec<Identifier>) -> Self {
+        self.const_params = const_params;
+        self
+    }
+
+    /// Returns true if this function has const parameters, and so must be instantiated at each call site
+    pub fn is_generic(&self) -> bool {
+        !self.const_params.is_empty()
+    }
+
+    /// Sets the value parameters of this function, which follow its trace segment parameters
+    pub fn with_value_params(mut self, value_params: Vec<ValueParam>) -> Self {
+        self.value_params = value_params;
+        self
+    }
+
+    /// Sets the visibility of this function
+    pub const fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
 }
 impl Eq for EvaluatorFunction {}
 impl PartialEq for EvaluatorFunction {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.params == other.params && self.body == other.body
+        self.name == other.name
+            && self.const_params == other.const_params
+            && self.params == other.params
+            && self.value_params == other.value_params
+            && self.body == other.body
+            && self.visibility == other.visibility
+    }
+}
+
+/// A parameter of an evaluator function which binds a value rather than trace columns,
+/// e.g. `alpha` or `alpha[4]`.
+#[derive(Copy, Clone, Spanned)]
+pub struct ValueParam {
+    #[span]
+    pub span: SourceSpan,
+    pub name: Identifier,
+    pub ty: Type,
+}
+impl ValueParam {
+    pub const fn new(span: SourceSpan, name: Identifier, ty: Type) -> Self {
+        Self { span, name, ty }
+    }
+}
+impl Eq for ValueParam {}
+impl PartialEq for ValueParam {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.ty == other.ty
+    }
+}
+impl fmt::Debug for ValueParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ValueParam")
+            .field("name", &self.name)
+            .field("ty", &self.ty)
+            .finish()
+    }
+}
+impl fmt::Display for ValueParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ty {
+            Type::Vector(size) => write!(f, "{}[{}]", self.name, size),
+            _ => write!(f, "{}", self.name),
+        }
     }
 }
@@ -96,6 +96,23 @@ impl<'a> fmt::Display for DisplayStatement<'a> {
             Statement::EnforceAll(ref expr) => {
                 write!(f, "enf {}", expr)
             }
+            Statement::EnforceMatch(ref expr) => {
+                f.write_str("enf match:")?;
+                for arm in expr.arms.iter() {
+                    writeln!(f)?;
+                    self.write_indent(f)?;
+                    let selector = arm.selector.as_ref().unwrap();
+                    write!(f, "{}case {}: {}", Self::INDENT, selector, &arm.body)?;
+                }
+                if let Some(default) = expr.default.as_ref() {
+                    writeln!(f)?;
+                    self.write_indent(f)?;
+                    write!(f, "{}else: {}", Self::INDENT, &default.body)?;
+                }
+                Ok(())
+            }
+            Statement::BusInteraction(ref expr) => write!(f, "{}", expr),
+            Statement::Lookup(ref expr) => write!(f, "{}", expr),
             Statement::Expr(ref expr) => write!(f, "{}", expr),
         }
     }
@@ -11,6 +11,12 @@ pub enum InvalidExprError {
     BoundedSymbolAccess(SourceSpan),
     #[error("expected scalar expression")]
     InvalidScalarExpr(SourceSpan),
+    #[error("division by zero")]
+    DivisionByZero(SourceSpan),
+    #[error("expected this to be a constant")]
+    NonConstantDivision(SourceSpan),
+    #[error("this condition is not known at compile-time")]
+    NonConstantCondition(SourceSpan),
 }
 impl Eq for InvalidExprError {}
 impl PartialEq for InvalidExprError {
@@ -46,6 +52,30 @@ impl ToDiagnostic for InvalidExprError {
                 .with_labels(vec![
                     Label::primary(span.source_id(), span).with_message(message)
                 ]),
+            Self::DivisionByZero(span) => Diagnostic::error()
+                .with_message("invalid expression")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec!["Zero has no inverse in the field".to_string()]),
+            Self::NonConstantDivision(span) => Diagnostic::error()
+                .with_message("invalid expression")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![
+                    "Division and field inversion are only supported on constant values, and are evaluated at compile-time"
+                        .to_string(),
+                ]),
+            Self::NonConstantCondition(span) => Diagnostic::error()
+                .with_message("invalid expression")
+                .with_labels(vec![
+                    Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![
+                    "The condition of an `if` expression may only depend on constants, const parameters, and comprehension bindings over ranges, not on trace values"
+                        .to_string(),
+                ]),
         }
     }
 }
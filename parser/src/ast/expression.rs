@@ -38,9 +38,12 @@ impl Identifier {
         self.0.as_str()
     }
 
-    /// Returns true if all characters of this identifier are uppercase
+    /// Returns true if all alphabetic characters of this identifier are uppercase, e.g. `FOO_BAR`
     pub fn is_uppercase(&self) -> bool {
-        self.0.as_str().chars().all(char::is_uppercase)
+        self.0
+            .as_str()
+            .chars()
+            .all(|c| !c.is_alphabetic() || c.is_uppercase())
     }
 
     /// Returns true if this identifier was generated by the compiler
@@ -162,7 +165,17 @@ impl QualifiedIdentifier {
         if self.module.name() == "$builtin" {
             match self.item {
                 NamespacedIdentifier::Function(id) => {
-                    matches!(id.name(), symbols::Sum | symbols::Prod)
+                    matches!(
+                        id.name(),
+                        symbols::Sum
+                            | symbols::Prod
+                            | symbols::Inv
+                            | symbols::IsBinary
+                            | symbols::OneHot
+                            | symbols::BitsToField
+                            | symbols::Decompose
+                            | symbols::Fold
+                    )
                 }
                 _ => false,
             }
@@ -275,7 +288,7 @@ pub enum Expr {
     /// A constant expression
     Const(Span<ConstantExpr>),
     /// An expression which evaluates to a vector of integers in the given range
-    Range(Span<Range>),
+    Range(RangeExpr),
     /// A vector of expressions
     ///
     /// A vector may be used to represent matrices in some situations, but such matrices
@@ -288,6 +301,8 @@ pub enum Expr {
     SymbolAccess(SymbolAccess),
     /// A binary operator over scalar values
     Binary(BinaryExpr),
+    /// A conditional expression over scalar values, resolved at compile-time
+    If(IfExpr),
     /// A call to a pure function
     ///
     /// NOTE: This expression is only valid when the call is a pure function;
@@ -296,20 +311,29 @@ pub enum Expr {
     Call(Call),
     /// A generator expression which produces a vector or matrix of values
     ListComprehension(ListComprehension),
+    /// An anonymous function, e.g. `|acc, x| acc + x`
+    ///
+    /// NOTE: This expression is only valid as the combining function given to `fold`
+    Lambda(Lambda),
 }
 impl Expr {
     /// Returns true if this expression is constant
     ///
-    /// NOTE: This only returns true for the `Const` and `Range` variants
+    /// NOTE: This only returns true for the `Const` and `Range` variants, and in the
+    /// case of ranges, only when the bounds of the range are known.
     pub fn is_constant(&self) -> bool {
-        matches!(self, Self::Const(_) | Self::Range(_))
+        match self {
+            Self::Const(_) => true,
+            Self::Range(range) => range.is_constant(),
+            _ => false,
+        }
     }
 
     /// Returns the resolved type of this expression, if known
     pub fn ty(&self) -> Option<Type> {
         match self {
             Self::Const(constant) => Some(constant.ty()),
-            Self::Range(range) => Some(Type::Vector(range.item.end - range.item.start)),
+            Self::Range(range) => range.values().map(|values| Type::Vector(values.len())),
             Self::Vector(vector) => match vector.first().and_then(|e| e.ty()) {
                 Some(Type::Felt) => Some(Type::Vector(vector.len())),
                 Some(Type::Vector(n)) => Some(Type::Matrix(vector.len(), n)),
@@ -322,9 +346,10 @@ impl Expr {
                 Some(Type::Matrix(rows, cols))
             }
             Self::SymbolAccess(ref access) => access.ty,
-            Self::Binary(_) => Some(Type::Felt),
+            Self::Binary(_) | Self::If(_) => Some(Type::Felt),
             Self::Call(ref call) => call.ty,
             Self::ListComprehension(ref lc) => lc.ty,
+            Self::Lambda(_) => None,
         }
     }
 }
@@ -332,15 +357,17 @@ impl fmt::Debug for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Const(ref expr) => f.debug_tuple("Const").field(&expr.item).finish(),
-            Self::Range(ref expr) => f.debug_tuple("Range").field(&expr.item).finish(),
+            Self::Range(ref expr) => f.debug_tuple("Range").field(expr).finish(),
             Self::Vector(ref expr) => f.debug_tuple("Vector").field(&expr.item).finish(),
             Self::Matrix(ref expr) => f.debug_tuple("Matrix").field(&expr.item).finish(),
             Self::SymbolAccess(ref expr) => f.debug_tuple("SymbolAccess").field(expr).finish(),
             Self::Binary(ref expr) => f.debug_tuple("Binary").field(expr).finish(),
+            Self::If(ref expr) => f.debug_tuple("If").field(expr).finish(),
             Self::Call(ref expr) => f.debug_tuple("Call").field(expr).finish(),
             Self::ListComprehension(ref expr) => {
                 f.debug_tuple("ListComprehension").field(expr).finish()
             }
+            Self::Lambda(ref expr) => f.debug_tuple("Lambda").field(expr).finish(),
         }
     }
 }
@@ -348,7 +375,7 @@ impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Const(ref expr) => write!(f, "{}", &expr),
-            Self::Range(ref range) => write!(f, "{}..{}", range.start, range.end),
+            Self::Range(ref range) => write!(f, "{}", range),
             Self::Vector(ref expr) => write!(f, "{}", DisplayList(expr.as_slice())),
             Self::Matrix(ref expr) => {
                 f.write_str("[")?;
@@ -363,10 +390,142 @@ impl fmt::Display for Expr {
             Self::ListComprehension(ref expr) => write!(f, "{}", DisplayBracketed(expr)),
             Self::SymbolAccess(ref expr) => write!(f, "{}", expr),
             Self::Binary(ref expr) => write!(f, "{}", expr),
+            Self::If(ref expr) => write!(f, "{}", expr),
             Self::Call(ref expr) => write!(f, "{}", expr),
+            Self::Lambda(ref expr) => write!(f, "{}", expr),
+        }
+    }
+}
+/// Represents a range expression, e.g. `0..4`, equivalent to the interval `[start, end)`.
+///
+/// The bounds of a range are usually integer literals, but in the body of an evaluator
+/// function, a bound may also refer to a const parameter of that evaluator, e.g. `0..N`,
+/// in which case the range is only known once the evaluator has been instantiated.
+///
+/// A range may also specify a step, e.g. `0..16 by 2`, in which case it produces every
+/// `step`-th value of the interval, starting from `start`.
+#[derive(Clone, Spanned)]
+pub struct RangeExpr {
+    #[span]
+    pub span: SourceSpan,
+    pub start: RangeBound,
+    pub end: RangeBound,
+    pub step: usize,
+}
+impl RangeExpr {
+    pub const fn new(span: SourceSpan, start: RangeBound, end: RangeBound) -> Self {
+        Self {
+            span,
+            start,
+            end,
+            step: 1,
+        }
+    }
+
+    /// Sets the step of this range, e.g. `2` for `0..16 by 2`
+    pub const fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Returns true if both bounds of this range are known
+    pub fn is_constant(&self) -> bool {
+        self.to_range().is_some()
+    }
+
+    /// Returns the concrete [Range] represented by this expression, if both bounds are known
+    ///
+    /// NOTE: The returned range does not account for the step of this range, use [Self::values]
+    /// to obtain the values actually produced by this range.
+    pub fn to_range(&self) -> Option<Range> {
+        match (self.start, self.end) {
+            (RangeBound::Const(start), RangeBound::Const(end)) => Some(start..end),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the values produced by this range, if both bounds are known
+    pub fn values(&self) -> Option<core::iter::StepBy<Range>> {
+        self.to_range().map(|range| range.step_by(self.step))
+    }
+
+    /// Sets the end of this range, if it is [RangeBound::Inferred], such that the range produces
+    /// `len` values.
+    pub fn infer_end(&mut self, len: usize) {
+        if let (RangeBound::Const(start), RangeBound::Inferred) = (self.start, self.end) {
+            self.end = RangeBound::Const(start + len * self.step);
         }
     }
 }
+impl From<Span<Range>> for RangeExpr {
+    fn from(range: Span<Range>) -> Self {
+        let span = range.span();
+        let range = range.item;
+        Self::new(
+            span,
+            RangeBound::Const(range.start),
+            RangeBound::Const(range.end),
+        )
+    }
+}
+impl Eq for RangeExpr {}
+impl PartialEq for RangeExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.end == other.end && self.step == other.step
+    }
+}
+impl fmt::Debug for RangeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_range() {
+            Some(range) => write!(f, "{:?}", range)?,
+            None => write!(f, "{:?}..{:?}", self.start, self.end)?,
+        }
+        if self.step != 1 {
+            write!(f, " by {}", self.step)?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for RangeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)?;
+        if self.step != 1 {
+            write!(f, " by {}", self.step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents one of the bounds of a [RangeExpr]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum RangeBound {
+    /// A bound given as an integer literal
+    Const(usize),
+    /// A bound given by a const parameter of an evaluator function, e.g. `N`
+    Param(Identifier),
+    /// An upper bound which is inferred from the length of the other iterables
+    /// of a comprehension, e.g. the indices produced by `enumerate(xs)`
+    Inferred,
+}
+impl fmt::Debug for RangeBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Const(value) => write!(f, "{}", value),
+            Self::Param(name) => write!(f, "{:?}", name),
+            Self::Inferred => f.write_str("_"),
+        }
+    }
+}
+impl fmt::Display for RangeBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Const(value) => write!(f, "{}", value),
+            Self::Param(name) => write!(f, "{}", name),
+            Self::Inferred => f.write_str("_"),
+        }
+    }
+}
+
 impl From<SymbolAccess> for Expr {
     #[inline]
     fn from(expr: SymbolAccess) -> Self {
@@ -403,6 +562,7 @@ impl TryFrom<ScalarExpr> for Expr {
             ))),
             ScalarExpr::SymbolAccess(access) => Ok(Expr::SymbolAccess(access)),
             ScalarExpr::Binary(expr) => Ok(Expr::Binary(expr)),
+            ScalarExpr::If(expr) => Ok(Expr::If(expr)),
             ScalarExpr::Call(expr) => Ok(Expr::Call(expr)),
             ScalarExpr::BoundedSymbolAccess(_) => {
                 Err(InvalidExprError::BoundedSymbolAccess(expr.span()))
@@ -428,6 +588,11 @@ pub enum ScalarExpr {
     BoundedSymbolAccess(BoundedSymbolAccess),
     /// A binary operator over scalar values
     Binary(BinaryExpr),
+    /// A conditional expression over scalar values
+    ///
+    /// NOTE: The condition must be known at compile-time, and the expression is replaced by
+    /// the selected branch during constant propagation or inlining
+    If(IfExpr),
     /// A call to a pure function or evaluator
     ///
     /// NOTE: This is only a valid expression when one of the following hold:
@@ -461,6 +626,11 @@ impl ScalarExpr {
                 (Some(lty), Some(rty)) if lty == rty => Ok(Some(lty)),
                 _ => Err(expr.span()),
             },
+            Self::If(ref expr) => match (expr.then_expr.ty()?, expr.else_expr.ty()?) {
+                (None, _) | (_, None) => Ok(None),
+                (Some(lty), Some(rty)) if lty == rty => Ok(Some(lty)),
+                _ => Err(expr.span()),
+            },
             Self::Call(ref expr) => Ok(expr.ty),
         }
     }
@@ -479,6 +649,7 @@ impl TryFrom<Expr> for ScalarExpr {
             }
             Expr::SymbolAccess(sym) => Ok(Self::SymbolAccess(sym)),
             Expr::Binary(bin) => Ok(Self::Binary(bin)),
+            Expr::If(expr) => Ok(Self::If(expr)),
             Expr::Call(call) => Ok(Self::Call(call)),
             invalid => Err(InvalidExprError::InvalidScalarExpr(invalid.span())),
         }
@@ -493,6 +664,7 @@ impl fmt::Debug for ScalarExpr {
                 f.debug_tuple("BoundedSymbolAccess").field(expr).finish()
             }
             Self::Binary(ref expr) => f.debug_tuple("Binary").field(expr).finish(),
+            Self::If(ref expr) => f.debug_tuple("If").field(expr).finish(),
             Self::Call(ref expr) => f.debug_tuple("Call").field(expr).finish(),
         }
     }
@@ -504,6 +676,7 @@ impl fmt::Display for ScalarExpr {
             Self::SymbolAccess(ref expr) => write!(f, "{}", expr),
             Self::BoundedSymbolAccess(ref expr) => write!(f, "{}.{}", &expr.column, &expr.boundary),
             Self::Binary(ref expr) => write!(f, "{}", expr),
+            Self::If(ref expr) => write!(f, "{}", expr),
             Self::Call(ref call) => write!(f, "{}", call),
         }
     }
@@ -557,6 +730,10 @@ pub enum BinaryOp {
     Sub,
     /// Multiplication
     Mul,
+    /// Division
+    ///
+    /// NOTE: This is only valid between constant operands, and is evaluated at compile-time
+    Div,
     /// Exponentiation
     Exp,
     /// Equality
@@ -570,12 +747,146 @@ impl fmt::Display for BinaryOp {
             Self::Add => f.write_str("+"),
             Self::Sub => f.write_str("-"),
             Self::Mul => f.write_str("*"),
+            Self::Div => f.write_str("/"),
             Self::Exp => f.write_str("^"),
             Self::Eq => f.write_str("="),
         }
     }
 }
 
+/// Represents a conditional expression, i.e. `if i == 0 then 1 else 0`
+///
+/// The condition must be evaluable at compile-time, e.g. it may depend on constants, const
+/// parameters, and the bindings of comprehensions over ranges, but not on trace values.
+#[derive(Clone, Spanned)]
+pub struct IfExpr {
+    #[span]
+    pub span: SourceSpan,
+    pub condition: Comparison,
+    pub then_expr: Box<ScalarExpr>,
+    pub else_expr: Box<ScalarExpr>,
+}
+impl IfExpr {
+    pub fn new(
+        span: SourceSpan,
+        condition: Comparison,
+        then_expr: ScalarExpr,
+        else_expr: ScalarExpr,
+    ) -> Self {
+        Self {
+            span,
+            condition,
+            then_expr: Box::new(then_expr),
+            else_expr: Box::new(else_expr),
+        }
+    }
+}
+impl Eq for IfExpr {}
+impl PartialEq for IfExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.condition == other.condition
+            && self.then_expr == other.then_expr
+            && self.else_expr == other.else_expr
+    }
+}
+impl fmt::Debug for IfExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IfExpr")
+            .field("condition", &self.condition)
+            .field("then_expr", self.then_expr.as_ref())
+            .field("else_expr", self.else_expr.as_ref())
+            .finish()
+    }
+}
+impl fmt::Display for IfExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "if {} then {} else {}",
+            &self.condition, &self.then_expr, &self.else_expr
+        )
+    }
+}
+
+/// Represents a comparison of two scalar values, used as the condition of an [IfExpr]
+#[derive(Clone, Spanned)]
+pub struct Comparison {
+    #[span]
+    pub span: SourceSpan,
+    pub op: ComparisonOp,
+    pub lhs: Box<ScalarExpr>,
+    pub rhs: Box<ScalarExpr>,
+}
+impl Comparison {
+    pub fn new(span: SourceSpan, op: ComparisonOp, lhs: ScalarExpr, rhs: ScalarExpr) -> Self {
+        Self {
+            span,
+            op,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        }
+    }
+
+    /// Evaluates this comparison, if both operands are constant
+    ///
+    /// Operands are compared by their canonical integer representation.
+    pub fn evaluate(&self) -> Option<bool> {
+        match (self.lhs.as_ref(), self.rhs.as_ref()) {
+            (ScalarExpr::Const(l), ScalarExpr::Const(r)) => Some(match self.op {
+                ComparisonOp::Eq => l.item == r.item,
+                ComparisonOp::Neq => l.item != r.item,
+                ComparisonOp::Lt => l.item < r.item,
+                ComparisonOp::Lte => l.item <= r.item,
+                ComparisonOp::Gt => l.item > r.item,
+                ComparisonOp::Gte => l.item >= r.item,
+            }),
+            _ => None,
+        }
+    }
+}
+impl Eq for Comparison {}
+impl PartialEq for Comparison {
+    fn eq(&self, other: &Self) -> bool {
+        self.op == other.op && self.lhs == other.lhs && self.rhs == other.rhs
+    }
+}
+impl fmt::Debug for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Comparison")
+            .field("op", &self.op)
+            .field("lhs", self.lhs.as_ref())
+            .field("rhs", self.rhs.as_ref())
+            .finish()
+    }
+}
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", &self.lhs, &self.op, &self.rhs)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+impl fmt::Display for ComparisonOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Eq => f.write_str("=="),
+            Self::Neq => f.write_str("!="),
+            Self::Lt => f.write_str("<"),
+            Self::Lte => f.write_str("<="),
+            Self::Gt => f.write_str(">"),
+            Self::Gte => f.write_str(">="),
+        }
+    }
+}
+
 /// Describes the type of boundary in the boundary constraint.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Boundary {
@@ -634,6 +945,8 @@ pub enum InvalidAccessError {
     IndexIntoScalar,
     #[error("attempted to access an index which is out of bounds")]
     IndexOutOfBounds,
+    #[error("variable-length public inputs may only be accessed by index")]
+    VariableLengthAccess,
 }
 
 /// [SymbolAccess] represents access to a named item in the source code; one of the following:
@@ -670,10 +983,25 @@ impl SymbolAccess {
         name: Identifier,
         access_type: AccessType,
         offset: usize,
+    ) -> Self {
+        Self::from_name(
+            span,
+            ResolvableIdentifier::Unresolved(NamespacedIdentifier::Binding(name)),
+            access_type,
+            offset,
+        )
+    }
+
+    /// Like [SymbolAccess::new], but the name may already be qualified, e.g. `h::MASK`
+    pub const fn from_name(
+        span: SourceSpan,
+        name: ResolvableIdentifier,
+        access_type: AccessType,
+        offset: usize,
     ) -> Self {
         Self {
             span,
-            name: ResolvableIdentifier::Unresolved(NamespacedIdentifier::Binding(name)),
+            name,
             access_type,
             offset,
             ty: None,
@@ -1016,6 +1344,55 @@ impl fmt::Display for ListComprehension {
     }
 }
 
+/// Represents an anonymous function, e.g. `|acc, x| acc * alpha + x`
+///
+/// Lambdas are only used to provide the combining function of the `fold` builtin, which is
+/// unrolled during inlining, so they have no type of their own.
+#[derive(Clone, Spanned)]
+pub struct Lambda {
+    #[span]
+    pub span: SourceSpan,
+    /// The names to be bound to each argument in the body of this lambda
+    pub params: Vec<Identifier>,
+    /// The expression produced by this lambda
+    pub body: Box<ScalarExpr>,
+}
+impl Lambda {
+    pub fn new(span: SourceSpan, params: Vec<Identifier>, body: ScalarExpr) -> Self {
+        Self {
+            span,
+            params,
+            body: Box::new(body),
+        }
+    }
+}
+impl Eq for Lambda {}
+impl PartialEq for Lambda {
+    fn eq(&self, other: &Self) -> bool {
+        self.params == other.params && self.body == other.body
+    }
+}
+impl fmt::Debug for Lambda {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lambda")
+            .field("params", &self.params)
+            .field("body", self.body.as_ref())
+            .finish()
+    }
+}
+impl fmt::Display for Lambda {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("|")?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", param)?;
+        }
+        write!(f, "| {}", &self.body)
+    }
+}
+
 /// Represents a function call (either a pure function or an evaluator).
 ///
 /// Calls are permitted in a scalar expression context, but arguments to the
@@ -1057,6 +1434,12 @@ impl Call {
         match callee.name() {
             symbols::Sum => Self::sum(span, args),
             symbols::Prod => Self::prod(span, args),
+            symbols::Inv => Self::inv(span, args),
+            symbols::IsBinary => Self::is_binary(span, args),
+            symbols::OneHot => Self::one_hot(span, args),
+            symbols::BitsToField => Self::bits_to_field(span, args),
+            symbols::Decompose => Self::decompose(span, args),
+            symbols::Fold => Self::fold(span, args),
             _ => Self {
                 span,
                 callee: ResolvableIdentifier::Unresolved(NamespacedIdentifier::Function(callee)),
@@ -1066,25 +1449,86 @@ impl Call {
         }
     }
 
+    /// Constructs a call to a function qualified by a module alias, e.g. `h::round(...)`
+    pub fn qualified(span: SourceSpan, callee: QualifiedIdentifier, args: Vec<Expr>) -> Self {
+        Self {
+            span,
+            callee: ResolvableIdentifier::Resolved(callee),
+            args,
+            ty: None,
+        }
+    }
+
     /// Returns true if the callee is a builtin function, e.g. `sum`
     #[inline]
     pub fn is_builtin(&self) -> bool {
         self.callee.is_builtin()
     }
 
+    /// Returns true if the callee is a builtin function which behaves like an evaluator, e.g. `is_binary`
+    pub fn is_builtin_constraint(&self) -> bool {
+        use crate::symbols;
+
+        self.is_builtin()
+            && matches!(
+                self.callee.as_ref().name(),
+                symbols::IsBinary | symbols::OneHot | symbols::Decompose
+            )
+    }
+
     /// Constructs a function call for the `sum` reducer/fold
     #[inline]
     pub fn sum(span: SourceSpan, args: Vec<Expr>) -> Self {
-        Self::new_builtin(span, "sum", args, Type::Felt)
+        Self::new_builtin(span, "sum", args, Some(Type::Felt))
     }
 
     /// Constructs a function call for the `prod` reducer/fold
     #[inline]
     pub fn prod(span: SourceSpan, args: Vec<Expr>) -> Self {
-        Self::new_builtin(span, "prod", args, Type::Felt)
+        Self::new_builtin(span, "prod", args, Some(Type::Felt))
+    }
+
+    /// Constructs a function call for the `inv` builtin, i.e. the field inverse of a constant
+    #[inline]
+    pub fn inv(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "inv", args, Some(Type::Felt))
+    }
+
+    /// Constructs a call to the `is_binary` constraint builtin, i.e. `x^2 = x`
+    #[inline]
+    pub fn is_binary(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "is_binary", args, None)
+    }
+
+    /// Constructs a call to the `one_hot` constraint builtin, i.e. every element of a vector is
+    /// binary, and exactly one of them is set
+    #[inline]
+    pub fn one_hot(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "one_hot", args, None)
+    }
+
+    /// Constructs a function call for the `bits_to_field` builtin, i.e. the field element
+    /// represented by a vector of bits in little-endian order
+    #[inline]
+    pub fn bits_to_field(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "bits_to_field", args, Some(Type::Felt))
+    }
+
+    /// Constructs a call to the `decompose` constraint builtin, i.e. a value is equal to the
+    /// little-endian composition of a vector of limbs in a constant base
+    #[inline]
+    pub fn decompose(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "decompose", args, None)
+    }
+
+    /// Constructs a function call for the `fold` builtin, i.e. a list fold with an initial value
+    /// and a combining function given as a lambda, e.g. `fold(v, 0, |acc, x| acc * 2 + x)`
+    #[inline]
+    pub fn fold(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "fold", args, Some(Type::Felt))
     }
 
-    fn new_builtin(span: SourceSpan, name: &str, args: Vec<Expr>, ty: Type) -> Self {
+    fn new_builtin(span: SourceSpan, name: &str, args: Vec<Expr>, ty: Option<Type>) -> Self {
         let builtin_module = Identifier::new(SourceSpan::UNKNOWN, Symbol::intern("$builtin"));
         let name = Identifier::new(span, Symbol::intern(name));
         let id = QualifiedIdentifier::new(builtin_module, NamespacedIdentifier::Function(name));
@@ -1092,7 +1536,7 @@ impl Call {
             span,
             callee: ResolvableIdentifier::Resolved(id),
             args,
-            ty: Some(ty),
+            ty,
         }
     }
 }
@@ -29,7 +29,7 @@ use miden_diagnostics::{
 };
 
 use crate::{
-    parser::ParseError,
+    parser::{ParseError, ParserConfig},
     sema::{self, SemanticAnalysisError},
     Symbol,
 };
@@ -40,6 +40,7 @@ use crate::{
 /// All of the details described in the documentation for [Program] and [Library]
 /// apply to their respective variants here.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Source {
     /// The source code which was parsed produced a valid [Program],
     /// i.e. it contained a root module, and optionally, one or more
@@ -69,6 +70,8 @@ pub struct Program {
     pub name: Identifier,
     /// The set of used constants referenced in this program.
     pub constants: BTreeMap<QualifiedIdentifier, Constant>,
+    /// The compile-time assertions declared in all modules of this program.
+    pub assertions: Vec<Assertion>,
     /// The set of used evaluator functions referenced in this program.
     pub evaluators: BTreeMap<QualifiedIdentifier, EvaluatorFunction>,
     /// The set of used periodic columns referenced in this program.
@@ -78,13 +81,18 @@ pub struct Program {
     /// NOTE: Public inputs are only visible in the root module, so we do
     /// not use [QualifiedIdentifier] as a key into this collection.
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
-    /// The set of random values defined in the root module, if present
-    pub random_values: Option<RandomValues>,
+    /// The random values defined in the root module, keyed by the auxiliary segment consuming them
+    pub random_values: BTreeMap<TraceSegmentId, RandomValues>,
+    /// The set of buses defined in the root module
+    ///
+    /// NOTE: If any buses are declared, it is guaranteed that `random_values` has an entry for
+    /// the first auxiliary segment.
+    pub buses: BTreeMap<Identifier, Bus>,
     /// The set of trace columns defined in the root module
     ///
     /// NOTE: It is guaranteed that at least a `main` trace column set
-    /// will be present here. If `random_values` has a value, then it is
-    /// further guaranteed that an `aux` trace column set will be present.
+    /// will be present here. It is further guaranteed that a trace column set
+    /// will be present for every segment which `random_values` has an entry for.
     pub trace_columns: Vec<TraceSegment>,
     /// The boundary_constraints block defined in the root module
     ///
@@ -114,10 +122,12 @@ impl Program {
         Self {
             name,
             constants: Default::default(),
+            assertions: vec![],
             evaluators: Default::default(),
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
-            random_values: None,
+            random_values: Default::default(),
+            buses: Default::default(),
             trace_columns: vec![],
             boundary_constraints: vec![],
             integrity_constraints: vec![],
@@ -148,6 +158,7 @@ impl Program {
             let root_module = library.get_mut(&root).unwrap();
             mem::swap(&mut program.public_inputs, &mut root_module.public_inputs);
             mem::swap(&mut program.random_values, &mut root_module.random_values);
+            mem::swap(&mut program.buses, &mut root_module.buses);
             mem::swap(&mut program.trace_columns, &mut root_module.trace_columns);
         }
 
@@ -238,6 +249,25 @@ impl Program {
                 ));
             }
         }
+        // Assertions are evaluated in every module of the program, whether or not anything in
+        // that module is referenced from the root module, so we move them into the program along
+        // with the items they reference. The references of the assertions in each module are
+        // represented by a special node, similar to the root node above.
+        let mut modules = library.modules.values().collect::<Vec<_>>();
+        modules.sort_by_key(|module| module.name);
+        for module in modules {
+            if module.assertions.is_empty() {
+                continue;
+            }
+            program.assertions.extend(module.assertions.iter().cloned());
+            root_nodes.push_back(QualifiedIdentifier::new(
+                module.name,
+                NamespacedIdentifier::Binding(Identifier::new(
+                    SourceSpan::UNKNOWN,
+                    Symbol::intern("$$assertions"),
+                )),
+            ));
+        }
 
         let mut visited = HashSet::<QualifiedIdentifier>::default();
         while let Some(node) = root_nodes.pop_front() {
@@ -287,10 +317,12 @@ impl PartialEq for Program {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
             && self.constants == other.constants
+            && self.assertions == other.assertions
             && self.evaluators == other.evaluators
             && self.periodic_columns == other.periodic_columns
             && self.public_inputs == other.public_inputs
             && self.random_values == other.random_values
+            && self.buses == other.buses
             && self.trace_columns == other.trace_columns
             && self.boundary_constraints == other.boundary_constraints
             && self.integrity_constraints == other.integrity_constraints
@@ -308,13 +340,26 @@ impl fmt::Display for Program {
 
         writeln!(f, "public_inputs:")?;
         for public_input in self.public_inputs.values() {
-            writeln!(f, "    {}: [{}]", public_input.name, public_input.size)?;
+            match public_input.size {
+                Some(size) => writeln!(f, "    {}: [{}]", public_input.name, size)?,
+                None => writeln!(f, "    {}: [_]", public_input.name)?,
+            }
         }
         f.write_str("\n")?;
 
-        if let Some(rv) = self.random_values.as_ref() {
+        if !self.random_values.is_empty() {
             writeln!(f, "random_values:")?;
-            writeln!(f, "    {}", rv)?;
+            for rv in self.random_values.values() {
+                writeln!(f, "    {}", rv)?;
+            }
+            f.write_str("\n")?;
+        }
+
+        if !self.buses.is_empty() {
+            writeln!(f, "buses:")?;
+            for bus in self.buses.values() {
+                writeln!(f, "    {}", bus)?;
+            }
             f.write_str("\n")?;
         }
 
@@ -322,14 +367,14 @@ impl fmt::Display for Program {
             writeln!(f, "periodic_columns:")?;
             for (qid, column) in self.periodic_columns.iter() {
                 if qid.module == self.name {
-                    writeln!(
-                        f,
-                        "    {}: {}",
-                        &qid.item,
-                        DisplayList(column.values.as_slice())
-                    )?;
+                    write!(f, "    {}: ", &qid.item)?;
                 } else {
-                    writeln!(f, "    {}: {}", qid, DisplayList(column.values.as_slice()))?;
+                    write!(f, "    {}: ", qid)?;
+                }
+                // Columns whose values have not yet been evaluated are displayed by their definition
+                match column.definition.as_ref() {
+                    Some(definition) => writeln!(f, "{}", definition)?,
+                    None => writeln!(f, "{}", DisplayList(column.values.as_slice()))?,
                 }
             }
             f.write_str("\n")?;
@@ -338,10 +383,22 @@ impl fmt::Display for Program {
         if !self.constants.is_empty() {
             for (qid, constant) in self.constants.iter() {
                 if qid.module == self.name {
-                    writeln!(f, "const {} = {}", &qid.item, &constant.value)?;
+                    write!(f, "const {} = ", &qid.item)?;
                 } else {
-                    writeln!(f, "const {} = {}", qid, &constant.value)?;
+                    write!(f, "const {} = ", qid)?;
                 }
+                // Constants whose values have not yet been evaluated are displayed by their definition
+                match constant.definition.as_ref() {
+                    Some(definition) => writeln!(f, "{}", definition)?,
+                    None => writeln!(f, "{}", &constant.value)?,
+                }
+            }
+            f.write_str("\n")?;
+        }
+
+        if !self.assertions.is_empty() {
+            for assertion in self.assertions.iter() {
+                writeln!(f, "{}", assertion)?;
             }
             f.write_str("\n")?;
         }
@@ -361,15 +418,16 @@ impl fmt::Display for Program {
         for (qid, evaluator) in self.evaluators.iter() {
             f.write_str("ev ")?;
             if qid.module == self.name {
-                writeln!(
-                    f,
-                    "{}{}",
-                    &qid.item,
-                    DisplayTuple(evaluator.params.as_slice())
-                )?;
+                write!(f, "{}", &qid.item)?;
             } else {
-                writeln!(f, "{}{}", qid, DisplayTuple(evaluator.params.as_slice()))?;
+                write!(f, "{}", qid)?;
             }
+            if evaluator.is_generic() {
+                write!(f, "<{}>", DisplayCsv::new(evaluator.const_params.iter()))?;
+            }
+            let params = evaluator.params.iter().map(|param| param.to_string());
+            let value_params = evaluator.value_params.iter().map(|param| param.to_string());
+            writeln!(f, "({})", DisplayCsv::new(params.chain(value_params)))?;
 
             for statement in evaluator.body.iter() {
                 writeln!(f, "{}", statement.display(1))?;
@@ -394,6 +452,7 @@ impl Library {
     pub fn new(
         diagnostics: &DiagnosticsHandler,
         codemap: Arc<CodeMap>,
+        config: &ParserConfig,
         mut modules: Vec<Module>,
     ) -> Result<Self, SemanticAnalysisError> {
         use std::collections::hash_map::Entry;
@@ -404,9 +463,16 @@ impl Library {
             return Ok(lib);
         }
 
+        // The file each module was loaded from, so that we can detect two different files being
+        // imported under the same module name, e.g. `common` relative to two different directories
+        let mut files = HashMap::<ModuleId, PathBuf>::default();
+
         // Register all parsed modules first
         let mut found_duplicate = None;
         for module in modules.drain(..) {
+            if let Ok(FileName::Real(path)) = codemap.name(module.span().source_id()) {
+                files.insert(module.name, path);
+            }
             match lib.modules.entry(module.name) {
                 Entry::Occupied(entry) => {
                     let prev_span = entry.key().span();
@@ -477,11 +543,44 @@ impl Library {
                     .to_path_buf(),
             };
 
+            // An alias of a nested module, e.g. `use chiplets::hasher as h`, can only be told apart
+            // from a renamed item import once we know which modules exist, so we do that first
+            let mut importing = lib.modules.remove(&module).unwrap();
+            importing.alias_nested_modules(diagnostics, |name| {
+                lib.modules.contains_key(&name)
+                    || find_module(&codemap, &source_dir, &config.search_paths, name).is_some()
+            })?;
+            imports.clear();
+            imports.extend(importing.imports.values().map(|i| i.module()));
+            lib.modules.insert(module, importing);
+
             // For each module imported, try to load the module from the library, if it is unavailable
             // we must do extra work to load it into the library, as described above.
             for import in imports.drain(..) {
+                let found = find_module(&codemap, &source_dir, &config.search_paths, import);
+                if let (Some(path), Some(prev_path)) = (found.as_ref(), files.get(&import)) {
+                    if path != prev_path {
+                        let prev_span = lib.modules[&import].name.span();
+                        diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("conflicting module definitions")
+                            .with_primary_label(
+                                import.span(),
+                                format!("this import refers to {}", path.display()),
+                            )
+                            .with_secondary_label(
+                                prev_span,
+                                format!(
+                                    "but a module of the same name was already loaded from {}",
+                                    prev_path.display()
+                                ),
+                            )
+                            .emit();
+                        return Err(SemanticAnalysisError::ImportFailed(import.span()));
+                    }
+                }
                 if let Entry::Vacant(entry) = lib.modules.entry(import) {
-                    let filename = source_dir.join(format!("{}.air", import.as_str()));
+                    let filename = found.unwrap_or_else(|| source_dir.join(module_path(import)));
                     // Check if the module exists in the codemap first, so that we can add files directly
                     // to the codemap during testing for convenience
                     let result = match codemap.get_by_name(&FileName::Real(filename.clone())) {
@@ -491,17 +590,25 @@ impl Library {
                         }
                     };
                     match result {
-                        Ok(imported_module) => {
+                        Ok(mut imported_module) => {
                             // We must check if the file we parsed actually contains a module with
-                            // the same name as our import, if not, that's an error
-                            if imported_module.name != import {
+                            // the same name as our import, if not, that's an error. For nested module
+                            // paths, e.g. `chiplets::hasher`, this is the last component of the path.
+                            let expected_name = import
+                                .as_str()
+                                .rsplit_once("::")
+                                .map_or(import.as_str(), |(_, name)| name);
+                            if imported_module.name.as_str() != expected_name {
                                 diagnostics.diagnostic(Severity::Error)
                                     .with_message("invalid module declaration")
                                     .with_primary_label(imported_module.name.span(), "module names must be the same as the name of the file they are defined in")
                                     .emit();
                                 return Err(SemanticAnalysisError::ImportFailed(import.span()));
                             } else {
-                                // We parsed the module successfully, so add it to the library
+                                // We parsed the module successfully, so add it to the library,
+                                // identified by the full path it was imported with
+                                imported_module.name =
+                                    Identifier::new(imported_module.name.span(), import.name());
                                 if !imported_module.imports.is_empty() {
                                     let imports = imported_module
                                         .imports
@@ -510,6 +617,7 @@ impl Library {
                                         .collect::<Vec<_>>();
                                     worklist.push_back((imported_module.name, imports));
                                 }
+                                files.insert(import, filename);
                                 entry.insert(imported_module);
                             }
                         }
@@ -551,3 +659,30 @@ impl Library {
         self.modules.get_mut(module)
     }
 }
+
+/// Returns the path of the file defining the module `name`, looking first relative to `source_dir`,
+/// and then in each of `search_paths`, in order. Returns `None` if no such file exists.
+///
+/// Files which have been added to the [CodeMap] directly are found as though they exist on disk,
+/// which is convenient for testing.
+fn find_module(
+    codemap: &CodeMap,
+    source_dir: &Path,
+    search_paths: &[PathBuf],
+    name: ModuleId,
+) -> Option<PathBuf> {
+    let relative = module_path(name);
+    core::iter::once(source_dir)
+        .chain(search_paths.iter().map(PathBuf::as_path))
+        .map(|dir| dir.join(&relative))
+        .find(|path| codemap.get_by_name(&FileName::Real(path.clone())).is_some() || path.is_file())
+}
+
+/// Returns the path of the file defining the module `name`, relative to a search path. Nested module
+/// paths, e.g. `chiplets::hasher`, are mapped to subdirectories, i.e. `chiplets/hasher.air`.
+fn module_path(name: ModuleId) -> PathBuf {
+    name.as_str()
+        .split("::")
+        .collect::<PathBuf>()
+        .with_extension("air")
+}
@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, HashSet};
 
 use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
-use crate::{ast::*, sema::SemanticAnalysisError};
+use crate::{ast::*, sema::SemanticAnalysisError, Symbol};
 
 /// This is a type alias used to clarify that an identifier refers to a module
 pub type ModuleId = Identifier;
@@ -21,6 +21,7 @@ pub enum ModuleType {
     ///
     /// * public_inputs
     /// * random_values
+    /// * buses
     /// * trace_columns
     /// * boundary_constraints
     /// * integrity_constraints
@@ -52,11 +53,14 @@ pub struct Module {
     pub name: ModuleId,
     pub ty: ModuleType,
     pub imports: BTreeMap<ModuleId, Import>,
+    pub aliases: BTreeMap<Identifier, ModuleId>,
     pub constants: BTreeMap<Identifier, Constant>,
+    pub assertions: Vec<Assertion>,
     pub evaluators: BTreeMap<Identifier, EvaluatorFunction>,
     pub periodic_columns: BTreeMap<Identifier, PeriodicColumn>,
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
-    pub random_values: Option<RandomValues>,
+    pub random_values: BTreeMap<TraceSegmentId, RandomValues>,
+    pub buses: BTreeMap<Identifier, Bus>,
     pub trace_columns: Vec<TraceSegment>,
     pub boundary_constraints: Option<Span<Vec<Statement>>>,
     pub integrity_constraints: Option<Span<Vec<Statement>>>,
@@ -77,11 +81,14 @@ impl Module {
             name,
             ty,
             imports: Default::default(),
+            aliases: Default::default(),
             constants: Default::default(),
+            assertions: vec![],
             evaluators: Default::default(),
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
-            random_values: None,
+            random_values: Default::default(),
+            buses: Default::default(),
             trace_columns: vec![],
             boundary_constraints: None,
             integrity_constraints: None,
@@ -118,6 +125,9 @@ impl Module {
                 Declaration::Constant(constant) => {
                     module.declare_constant(diagnostics, &mut names, constant)?;
                 }
+                Declaration::Assertion(assertion) => {
+                    module.assertions.push(assertion);
+                }
                 Declaration::EvaluatorFunction(evaluator) => {
                     module.declare_evaluator(diagnostics, &mut names, evaluator)?;
                 }
@@ -135,8 +145,19 @@ impl Module {
                         module.declare_public_input(diagnostics, &mut names, input)?;
                     }
                 }
-                Declaration::RandomValues(rv) => {
-                    module.declare_random_values(diagnostics, &mut names, rv)?;
+                Declaration::RandomValues(mut random_values) => {
+                    for rv in random_values.item.drain(..) {
+                        module.declare_random_values(diagnostics, &mut names, rv)?;
+                    }
+                }
+                Declaration::Buses(mut buses) => {
+                    if module.is_library() {
+                        invalid_section_in_library(diagnostics, "buses", buses.span());
+                        return Err(SemanticAnalysisError::RootSectionInLibrary(buses.span()));
+                    }
+                    for bus in buses.item.drain(..) {
+                        module.declare_bus(diagnostics, &mut names, bus)?;
+                    }
                 }
                 Declaration::Trace(segments) => {
                     module.declare_trace_segments(diagnostics, &mut names, segments)?;
@@ -175,20 +196,77 @@ impl Module {
                 return Err(SemanticAnalysisError::MissingPublicInputs);
             }
 
-            if module.random_values.is_some()
-                && !module.trace_columns.iter().any(|ts| ts.name == "$aux")
+            // Random values must be consumed by a declared auxiliary segment
+            for rv in module.random_values.values() {
+                if !module.trace_columns.iter().any(|ts| ts.id == rv.segment) {
+                    let segment = match rv.segment {
+                        1 => "aux".to_string(),
+                        n => format!("aux{n}"),
+                    };
+                    diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message(format!(
+                            "declaring random_values requires an {segment} trace_columns declaration"
+                        ))
+                        .with_primary_label(rv.span(), "this declaration is invalid")
+                        .emit();
+                    return Err(SemanticAnalysisError::Invalid);
+                }
+            }
+
+            // Buses are lowered to running products over the random values of the first
+            // auxiliary segment
+            if let Some(bus) = module.buses.values().next() {
+                if !module.random_values.contains_key(&1) {
+                    diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("declaring buses requires a random_values declaration")
+                        .with_primary_label(bus.span(), "this declaration is invalid")
+                        .with_note("Buses are encoded using the random values of the first auxiliary segment")
+                        .emit();
+                    return Err(SemanticAnalysisError::Invalid);
+                }
+            }
+
+            // Bounded integer columns are range checked by the bus declared for their type
+            let mut range_checks = BTreeMap::<ColumnType, &Bus>::default();
+            for bus in module.buses.values() {
+                let Some(ty) = bus.range_check else {
+                    continue;
+                };
+                if let Some(prev) = range_checks.insert(ty, bus) {
+                    diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("conflicting range checks")
+                        .with_primary_label(
+                            bus.span(),
+                            format!("this bus range checks {ty} values"),
+                        )
+                        .with_secondary_label(prev.span(), "but so does this bus")
+                        .emit();
+                    return Err(SemanticAnalysisError::Invalid);
+                }
+            }
+            for binding in module
+                .trace_columns
+                .iter()
+                .flat_map(|segment| segment.bindings.iter())
             {
-                diagnostics
-                    .diagnostic(Severity::Error)
-                    .with_message(
-                        "declaring random_values requires an aux trace_columns declaration",
-                    )
-                    .with_primary_label(
-                        module.random_values.as_ref().unwrap().span(),
-                        "this declaration is invalid",
-                    )
-                    .emit();
-                return Err(SemanticAnalysisError::Invalid);
+                match binding.column_type {
+                    Some(ty) if ty.is_bounded_integer() && !range_checks.contains_key(&ty) => {
+                        diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("missing range check")
+                            .with_primary_label(
+                                binding.span(),
+                                format!("no range check is declared for {ty} columns"),
+                            )
+                            .with_note(format!("Declare a bus which range checks {ty} values in the buses section, e.g. 'rc: multiset for {ty}'."))
+                            .emit();
+                        return Err(SemanticAnalysisError::Invalid);
+                    }
+                    _ => (),
+                }
             }
         }
 
@@ -222,7 +300,18 @@ impl Module {
                                     .emit();
                             }
                             Import::Partial { items, .. } => {
-                                for item in items.iter() {
+                                if let Some(renamed) = items
+                                    .iter()
+                                    .find_map(|(local, item)| (local != item).then_some(*local))
+                                {
+                                    renamed_item_with_wildcard(
+                                        diagnostics,
+                                        renamed.span(),
+                                        name.span(),
+                                    );
+                                    return Err(SemanticAnalysisError::Invalid);
+                                }
+                                for item in items.keys() {
                                     diagnostics
                                         .diagnostic(Severity::Warning)
                                         .with_message("redundant item import")
@@ -235,6 +324,7 @@ impl Module {
                                 }
                                 entry.insert(import.item);
                             }
+                            Import::Alias { .. } => unreachable!(),
                         }
                     }
                     Entry::Vacant(entry) => {
@@ -251,9 +341,37 @@ impl Module {
                 if name == self.name {
                     return Err(SemanticAnalysisError::ImportSelf(name.span()));
                 }
+                // Constants and evaluators are distinguished by case, so a renamed item must
+                // keep the case of the original, or it would change namespaces
+                for (local, item) in items.iter() {
+                    if local.is_uppercase() != item.is_uppercase() {
+                        diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("invalid import alias")
+                            .with_primary_label(
+                                local.span(),
+                                "this name is not valid for the item being imported",
+                            )
+                            .with_secondary_label(item.span(), "the item being renamed")
+                            .with_note("Constants must be renamed using an uppercase identifier, e.g. FOO, and evaluators using a lowercase identifier, e.g. foo")
+                            .emit();
+                        return Err(SemanticAnalysisError::Invalid);
+                    }
+                }
                 match self.imports.entry(name) {
                     Entry::Occupied(mut entry) => match entry.get_mut() {
                         Import::All { module: prev } => {
+                            if let Some(renamed) = items
+                                .iter()
+                                .find_map(|(local, item)| (local != item).then_some(*local))
+                            {
+                                renamed_item_with_wildcard(
+                                    diagnostics,
+                                    renamed.span(),
+                                    prev.span(),
+                                );
+                                return Err(SemanticAnalysisError::Invalid);
+                            }
                             diagnostics
                                 .diagnostic(Severity::Warning)
                                 .with_message("redundant module import")
@@ -268,12 +386,28 @@ impl Module {
                             items: ref mut prev_items,
                             ..
                         } => {
-                            for item in items.drain() {
-                                if let Some(prev) = prev_items.get(&item) {
+                            for (local, item) in items.drain() {
+                                if let Some((prev, prev_item)) = prev_items.get_key_value(&local) {
+                                    // Importing the same item under the same name is redundant,
+                                    // but binding the name to a different item is a conflict
+                                    if prev_item != &item {
+                                        conflicting_declaration(
+                                            diagnostics,
+                                            "import",
+                                            prev.span(),
+                                            local.span(),
+                                        );
+                                        return Err(SemanticAnalysisError::NameConflict(
+                                            local.span(),
+                                        ));
+                                    }
                                     diagnostics
                                         .diagnostic(Severity::Warning)
                                         .with_message("redundant item import")
-                                        .with_primary_label(item.span(), "this import is redundant")
+                                        .with_primary_label(
+                                            local.span(),
+                                            "this import is redundant",
+                                        )
                                         .with_secondary_label(
                                             prev.span(),
                                             "because it was already imported here",
@@ -281,40 +415,15 @@ impl Module {
                                         .emit();
                                     continue;
                                 }
-                                prev_items.insert(item);
-                                let name = if item.is_uppercase() {
-                                    NamespacedIdentifier::Binding(item)
-                                } else {
-                                    NamespacedIdentifier::Function(item)
-                                };
-                                if let Some(prev) = names.replace(name) {
-                                    conflicting_declaration(
-                                        diagnostics,
-                                        "import",
-                                        prev.span(),
-                                        item.span(),
-                                    );
-                                    return Err(SemanticAnalysisError::NameConflict(item.span()));
-                                }
+                                prev_items.insert(local, item);
+                                declare_imported_name(diagnostics, names, local)?;
                             }
                         }
+                        Import::Alias { .. } => unreachable!(),
                     },
                     Entry::Vacant(entry) => {
-                        for item in items.iter().copied() {
-                            let name = if item.is_uppercase() {
-                                NamespacedIdentifier::Binding(item)
-                            } else {
-                                NamespacedIdentifier::Function(item)
-                            };
-                            if let Some(prev) = names.replace(name) {
-                                conflicting_declaration(
-                                    diagnostics,
-                                    "import",
-                                    prev.span(),
-                                    item.span(),
-                                );
-                                return Err(SemanticAnalysisError::NameConflict(item.span()));
-                            }
+                        for local in items.keys().copied() {
+                            declare_imported_name(diagnostics, names, local)?;
                         }
                         entry.insert(Import::Partial {
                             module: name,
@@ -323,6 +432,37 @@ impl Module {
                     }
                 }
 
+                Ok(())
+            }
+            Import::Alias {
+                module: name,
+                alias,
+            } => {
+                if name == self.name {
+                    return Err(SemanticAnalysisError::ImportSelf(name.span()));
+                }
+                match self.aliases.entry(alias) {
+                    Entry::Occupied(entry) => {
+                        conflicting_declaration(
+                            diagnostics,
+                            "module alias",
+                            entry.key().span(),
+                            alias.span(),
+                        );
+                        return Err(SemanticAnalysisError::NameConflict(alias.span()));
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(name);
+                    }
+                }
+                // Aliasing a module imports none of its items by name, but the module must still
+                // be loaded and resolved as a dependency of this one, so we record an empty import
+                // of it unless the module is already imported
+                self.imports.entry(name).or_insert_with(|| Import::Partial {
+                    module: name,
+                    items: Default::default(),
+                });
+
                 Ok(())
             }
         }
@@ -346,6 +486,8 @@ impl Module {
             return Err(SemanticAnalysisError::Invalid);
         }
 
+        self.check_visibility(diagnostics, constant.name, constant.visibility)?;
+
         if let Some(prev) = names.replace(NamespacedIdentifier::Binding(constant.name)) {
             conflicting_declaration(diagnostics, "constant", prev.span(), constant.name.span());
             return Err(SemanticAnalysisError::NameConflict(constant.name.span()));
@@ -385,6 +527,8 @@ impl Module {
         names: &mut HashSet<NamespacedIdentifier>,
         evaluator: EvaluatorFunction,
     ) -> Result<(), SemanticAnalysisError> {
+        self.check_visibility(diagnostics, evaluator.name, evaluator.visibility)?;
+
         if let Some(prev) = names.replace(NamespacedIdentifier::Function(evaluator.name)) {
             conflicting_declaration(diagnostics, "evaluator", prev.span(), evaluator.name.span());
             return Err(SemanticAnalysisError::NameConflict(evaluator.name.span()));
@@ -395,6 +539,29 @@ impl Module {
         Ok(())
     }
 
+    /// Items of the root module cannot be imported by other modules, so they cannot be `pub`
+    fn check_visibility(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        name: Identifier,
+        visibility: Visibility,
+    ) -> Result<(), SemanticAnalysisError> {
+        if self.is_root() && visibility.is_public() {
+            diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid visibility")
+                .with_primary_label(
+                    name.span(),
+                    "items of the root module cannot be declared pub",
+                )
+                .with_note("Only items of library modules may be imported by other modules")
+                .emit();
+            return Err(SemanticAnalysisError::Invalid);
+        }
+
+        Ok(())
+    }
+
     fn declare_periodic_column(
         &mut self,
         diagnostics: &DiagnosticsHandler,
@@ -411,19 +578,17 @@ impl Module {
             return Err(SemanticAnalysisError::NameConflict(column.name.span()));
         }
 
-        match column.period() {
-            n if n > 0 && n.is_power_of_two() => {
-                assert_eq!(self.periodic_columns.insert(column.name, column), None);
+        // The cycle length of a column defined by an expression is checked once it is evaluated
+        if column.definition.is_some() || PeriodicColumn::is_valid_period(column.period()) {
+            assert_eq!(self.periodic_columns.insert(column.name, column), None);
 
-                Ok(())
-            }
-            _ => {
-                diagnostics.diagnostic(Severity::Error)
-                    .with_message("invalid periodic column declaration")
-                    .with_primary_label(column.span(), "periodic columns must have a non-zero cycle length which is a power of two")
-                    .emit();
-                Err(SemanticAnalysisError::Invalid)
-            }
+            Ok(())
+        } else {
+            diagnostics.diagnostic(Severity::Error)
+                .with_message("invalid periodic column declaration")
+                .with_primary_label(column.span(), "periodic columns must have a cycle length which is a power of two, and at least 2")
+                .emit();
+            Err(SemanticAnalysisError::Invalid)
         }
     }
 
@@ -452,35 +617,60 @@ impl Module {
         names: &mut HashSet<NamespacedIdentifier>,
         rv: RandomValues,
     ) -> Result<(), SemanticAnalysisError> {
+        use std::collections::btree_map::Entry;
+
         let span = rv.span();
         if self.is_library() {
             invalid_section_in_library(diagnostics, "random_values", span);
             return Err(SemanticAnalysisError::RootSectionInLibrary(span));
         }
 
-        for binding in rv.bindings.iter() {
-            if let Some(prev) = names.replace(NamespacedIdentifier::Binding(binding.name)) {
+        for name in std::iter::once(&rv.name).chain(rv.bindings.iter().map(|b| &b.name)) {
+            if let Some(prev) = names.replace(NamespacedIdentifier::Binding(*name)) {
                 conflicting_declaration(
                     diagnostics,
                     "random values binding",
                     prev.span(),
-                    binding.name.span(),
+                    name.span(),
                 );
-                return Err(SemanticAnalysisError::NameConflict(binding.name.span()));
+                return Err(SemanticAnalysisError::NameConflict(name.span()));
             }
         }
 
-        if let Some(prev) = self.random_values.replace(rv) {
-            diagnostics
-                .diagnostic(Severity::Error)
-                .with_message("multiple random_values declarations")
-                .with_primary_label(span, "this declaration is invalid")
-                .with_secondary_label(prev.span(), "because this declaration already exists")
-                .with_note("Only a single random_values declaration is allowed at a time")
-                .emit();
-            self.random_values.replace(prev);
-            Err(SemanticAnalysisError::NameConflict(span))
+        match self.random_values.entry(rv.segment) {
+            Entry::Vacant(entry) => {
+                entry.insert(rv);
+                Ok(())
+            }
+            Entry::Occupied(entry) => {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("multiple random_values declarations")
+                    .with_primary_label(span, "this declaration is invalid")
+                    .with_secondary_label(
+                        entry.get().span(),
+                        "because this declaration already exists",
+                    )
+                    .with_note(
+                        "Only a single random_values declaration is allowed per trace segment",
+                    )
+                    .emit();
+                Err(SemanticAnalysisError::NameConflict(span))
+            }
+        }
+    }
+
+    fn declare_bus(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        names: &mut HashSet<NamespacedIdentifier>,
+        bus: Bus,
+    ) -> Result<(), SemanticAnalysisError> {
+        if let Some(prev) = names.replace(NamespacedIdentifier::Binding(bus.name)) {
+            conflicting_declaration(diagnostics, "bus", prev.span(), bus.name.span());
+            Err(SemanticAnalysisError::NameConflict(bus.name.span()))
         } else {
+            assert_eq!(self.buses.insert(bus.name, bus), None);
             Ok(())
         }
     }
@@ -596,15 +786,76 @@ impl Module {
         self.ty == ModuleType::Library
     }
 
-    /// Traverse all of the items exported from this module
+    /// Converts imports of a renamed item which names a nested module, e.g. `use chiplets::hasher as h`,
+    /// into an alias of that module.
+    ///
+    /// Such an import is syntactically identical to renaming the item `hasher` of the module `chiplets`,
+    /// so it can only be distinguished once we know which modules exist, as determined by `is_module`.
+    pub(crate) fn alias_nested_modules<F>(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        is_module: F,
+    ) -> Result<(), SemanticAnalysisError>
+    where
+        F: Fn(ModuleId) -> bool,
+    {
+        let mut aliased = vec![];
+        let aliases = &self.aliases;
+        self.imports.retain(|_, import| match import {
+            Import::Partial {
+                module: from,
+                items,
+            } if !items.is_empty() => {
+                items.retain(|local, item| {
+                    // Constants cannot name a module, and a module is never aliased by its own name
+                    if local == item || item.is_uppercase() {
+                        return true;
+                    }
+                    let nested =
+                        Identifier::new(item.span(), Symbol::intern(format!("{}::{}", from, item)));
+                    if !is_module(nested) {
+                        return true;
+                    }
+                    aliased.push((*local, nested));
+                    false
+                });
+                // If every item named a nested module, the parent module is not imported at all,
+                // unless it is also aliased
+                !items.is_empty() || aliases.values().any(|aliased| aliased == from)
+            }
+            _ => true,
+        });
+
+        for (alias, module) in aliased {
+            if let Some((prev, _)) = self.aliases.get_key_value(&alias) {
+                conflicting_declaration(diagnostics, "module alias", prev.span(), alias.span());
+                return Err(SemanticAnalysisError::NameConflict(alias.span()));
+            }
+            self.aliases.insert(alias, module);
+            self.imports
+                .entry(module)
+                .or_insert_with(|| Import::Partial {
+                    module,
+                    items: Default::default(),
+                });
+        }
+
+        Ok(())
+    }
+
+    /// Traverse all of the items exported from this module, i.e. its public items
     pub fn exports(&self) -> impl Iterator<Item = Export<'_>> + '_ {
         self.constants
             .values()
             .map(Export::Constant)
             .chain(self.evaluators.values().map(Export::Evaluator))
+            .filter(|export| export.visibility().is_public())
     }
 
-    /// Get the export with the given identifier, if it can be found
+    /// Get the exportable item with the given identifier, if it can be found
+    ///
+    /// NOTE: The item is returned regardless of its visibility, so that callers can
+    /// distinguish references to private items from references to undefined ones.
     pub fn get(&self, id: &Identifier) -> Option<Export<'_>> {
         if id.is_uppercase() {
             self.constants.get(id).map(Export::Constant)
@@ -619,11 +870,14 @@ impl PartialEq for Module {
         self.name == other.name
             && self.ty == other.ty
             && self.imports == other.imports
+            && self.aliases == other.aliases
             && self.constants == other.constants
+            && self.assertions == other.assertions
             && self.evaluators == other.evaluators
             && self.periodic_columns == other.periodic_columns
             && self.public_inputs == other.public_inputs
             && self.random_values == other.random_values
+            && self.buses == other.buses
             && self.trace_columns == other.trace_columns
             && self.boundary_constraints == other.boundary_constraints
             && self.integrity_constraints == other.integrity_constraints
@@ -638,6 +892,44 @@ fn invalid_section_in_library(diagnostics: &DiagnosticsHandler, ty: &str, span:
         .emit();
 }
 
+/// Registers `name` as an item explicitly imported into the current module, raising an
+/// error if it conflicts with a previous declaration or import
+fn declare_imported_name(
+    diagnostics: &DiagnosticsHandler,
+    names: &mut HashSet<NamespacedIdentifier>,
+    name: Identifier,
+) -> Result<(), SemanticAnalysisError> {
+    let namespaced_name = if name.is_uppercase() {
+        NamespacedIdentifier::Binding(name)
+    } else {
+        NamespacedIdentifier::Function(name)
+    };
+    if let Some(prev) = names.replace(namespaced_name) {
+        conflicting_declaration(diagnostics, "import", prev.span(), name.span());
+        return Err(SemanticAnalysisError::NameConflict(name.span()));
+    }
+    Ok(())
+}
+
+fn renamed_item_with_wildcard(
+    diagnostics: &DiagnosticsHandler,
+    renamed: SourceSpan,
+    wildcard: SourceSpan,
+) {
+    diagnostics
+        .diagnostic(Severity::Error)
+        .with_message("conflicting import")
+        .with_primary_label(renamed, "this item is renamed on import")
+        .with_secondary_label(
+            wildcard,
+            "but all items of this module are imported under their original names here",
+        )
+        .with_note(
+            "Either import all items from the module, or list the items to import, but not both",
+        )
+        .emit();
+}
+
 fn conflicting_declaration(
     diagnostics: &DiagnosticsHandler,
     ty: &str,
@@ -7,7 +7,7 @@
 //! Statements do not return any value, unlike expressions.
 use std::fmt;
 
-use miden_diagnostics::{SourceSpan, Spanned};
+use miden_diagnostics::{SourceSpan, Span, Spanned};
 
 use super::*;
 
@@ -60,6 +60,22 @@ pub enum Statement {
     /// Just like `Enforce`, except the constraint is contained in the body of a list comprehension,
     /// and must be enforced on every value produced by that comprehension.
     EnforceAll(ListComprehension),
+    /// Declares a set of constraints, each of which is enforced under its own selector, e.g. `enf match`.
+    ///
+    /// Each arm is equivalent to an `EnforceAll` with a selector, but the arms are kept together so
+    /// that the selectors of a match can be analyzed as a group. This variant is expanded into its
+    /// arms during inlining.
+    EnforceMatch(Match),
+    /// Sends or receives a tuple of values on a bus, e.g. `p.send(a, b) when s`.
+    ///
+    /// Interactions are not constraints on their own, instead all of the interactions with a bus
+    /// are combined into the constraints of the auxiliary column that the bus is lowered to.
+    BusInteraction(BusInteraction),
+    /// Looks up a tuple of values in a table, e.g. `enf lookup([t0, t1], [a, b], m)`.
+    ///
+    /// Like bus interactions, lookups are not constraints on their own, instead each lookup is
+    /// lowered to an auxiliary column and the constraints which enforce it.
+    Lookup(Lookup),
 }
 impl Statement {
     /// Checks this statement to see if it contains any constraints
@@ -69,12 +85,70 @@ impl Statement {
     /// one or more constraints in its body.
     pub fn has_constraints(&self) -> bool {
         match self {
-            Self::Enforce(_) | Self::EnforceIf(_, _) | Self::EnforceAll(_) => true,
+            Self::Enforce(_)
+            | Self::EnforceIf(_, _)
+            | Self::EnforceAll(_)
+            | Self::EnforceMatch(_)
+            | Self::BusInteraction(_)
+            | Self::Lookup(_) => true,
             Self::Let(Let { body, .. }) => body.iter().any(|s| s.has_constraints()),
             Self::Expr(_) => false,
         }
     }
 
+    /// Applies `selector` to every constraint in this statement, as if each had been written with `when selector`
+    ///
+    /// Constraints which already have a selector are enforced under the product of both selectors,
+    /// while constraints without one are transformed into a comprehension over a single element,
+    /// using `next_var` to generate the name of its binding, just as the parser does for `enf x when s`.
+    ///
+    /// NOTE: This is only intended for use by the parser, before any analysis has been performed.
+    pub(crate) fn apply_selector(&mut self, selector: &ScalarExpr, next_var: &mut usize) {
+        let apply = |lc: &mut ListComprehension| {
+            lc.selector = Some(match lc.selector.take() {
+                None => selector.clone(),
+                Some(inner) => ScalarExpr::Binary(BinaryExpr::new(
+                    inner.span(),
+                    BinaryOp::Mul,
+                    selector.clone(),
+                    inner,
+                )),
+            });
+        };
+        match self {
+            Self::Let(ref mut expr) => {
+                for statement in expr.body.iter_mut() {
+                    statement.apply_selector(selector, next_var);
+                }
+            }
+            Self::Enforce(ref mut expr) => {
+                let span = expr.span();
+                let generated_name = format!("%{}", *next_var);
+                *next_var += 1;
+                let generated_binding =
+                    Identifier::new(SourceSpan::UNKNOWN, crate::Symbol::intern(generated_name));
+                let context = vec![(
+                    generated_binding,
+                    Expr::Range(RangeExpr::from(Span::new(SourceSpan::UNKNOWN, 0..1))),
+                )];
+                let body =
+                    core::mem::replace(expr, ScalarExpr::Const(Span::new(SourceSpan::UNKNOWN, 0)));
+                *self = Self::EnforceAll(ListComprehension::new(
+                    span,
+                    body,
+                    context,
+                    Some(selector.clone()),
+                ));
+            }
+            Self::EnforceAll(ref mut lc) => apply(lc),
+            Self::EnforceMatch(ref mut expr) => expr.iter_mut().for_each(apply),
+            Self::BusInteraction(ref mut expr) => expr.apply_selector(selector.clone()),
+            Self::Lookup(ref mut expr) => expr.apply_selector(selector.clone()),
+            Self::EnforceIf(_, _) => unreachable!(),
+            Self::Expr(_) => (),
+        }
+    }
+
     pub fn display(&self, indent: usize) -> DisplayStatement<'_> {
         DisplayStatement {
             statement: self,
@@ -149,3 +223,254 @@ impl fmt::Debug for Let {
             .finish()
     }
 }
+
+/// An `enf match` statement, e.g.:
+///
+/// ```airscript
+/// enf match:
+///     case s: c' = a + b
+///     else: c' = c
+/// ```
+///
+/// Every arm is represented as a constraint comprehension over a single element, with the
+/// selector of the arm as its selector.
+#[derive(Clone, Spanned)]
+pub struct Match {
+    #[span]
+    pub span: SourceSpan,
+    /// The `case` arms of this match, in the order they were declared
+    pub arms: Vec<ListComprehension>,
+    /// The `else` arm of this match, if present.
+    ///
+    /// This arm is enforced when none of the `case` arms are selected, i.e. its selector is
+    /// `1 - (s_0 + .. + s_n)`, where `s_i` is the selector of the i-th arm.
+    pub default: Option<ListComprehension>,
+}
+impl Match {
+    pub fn new(
+        span: SourceSpan,
+        arms: Vec<ListComprehension>,
+        default: Option<ListComprehension>,
+    ) -> Self {
+        Self {
+            span,
+            arms,
+            default,
+        }
+    }
+
+    /// Returns an iterator over all of the arms of this match, including the `else` arm, if present
+    pub fn iter(&self) -> impl Iterator<Item = &ListComprehension> + '_ {
+        self.arms.iter().chain(self.default.iter())
+    }
+
+    /// Returns an iterator over mutable references to all of the arms of this match, including the `else` arm
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ListComprehension> + '_ {
+        self.arms.iter_mut().chain(self.default.iter_mut())
+    }
+}
+impl Eq for Match {}
+impl PartialEq for Match {
+    fn eq(&self, other: &Self) -> bool {
+        self.arms == other.arms && self.default == other.default
+    }
+}
+impl fmt::Debug for Match {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Match")
+            .field("arms", &self.arms)
+            .field("default", &self.default)
+            .finish()
+    }
+}
+
+/// An interaction with a bus, i.e. sending or receiving a tuple of values on it, e.g.:
+///
+/// ```airscript
+/// p.send(a, b) when s
+/// p.receive(c, d) when 1 - s
+/// ```
+///
+/// When a selector is given, the tuple is only sent or received on the rows where it is non-zero.
+#[derive(Clone, Spanned)]
+pub struct BusInteraction {
+    #[span]
+    pub span: SourceSpan,
+    /// The name of the bus being interacted with
+    pub bus: Identifier,
+    /// Whether the tuple is sent or received
+    pub op: BusOp,
+    /// The values of the tuple
+    pub args: Vec<ScalarExpr>,
+    /// The selector under which the interaction takes place, if any
+    pub selector: Option<ScalarExpr>,
+}
+impl BusInteraction {
+    pub fn new(
+        span: SourceSpan,
+        bus: Identifier,
+        op: BusOp,
+        args: Vec<ScalarExpr>,
+        selector: Option<ScalarExpr>,
+    ) -> Self {
+        Self {
+            span,
+            bus,
+            op,
+            args,
+            selector,
+        }
+    }
+
+    /// Applies `selector` to this interaction, in addition to its own selector, if any
+    pub fn apply_selector(&mut self, selector: ScalarExpr) {
+        self.selector = Some(match self.selector.take() {
+            None => selector,
+            Some(inner) => ScalarExpr::Binary(BinaryExpr::new(
+                inner.span(),
+                BinaryOp::Mul,
+                selector,
+                inner,
+            )),
+        });
+    }
+}
+impl Eq for BusInteraction {}
+impl PartialEq for BusInteraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.bus == other.bus
+            && self.op == other.op
+            && self.args == other.args
+            && self.selector == other.selector
+    }
+}
+impl fmt::Debug for BusInteraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BusInteraction")
+            .field("bus", &self.bus)
+            .field("op", &self.op)
+            .field("args", &self.args)
+            .field("selector", &self.selector)
+            .finish()
+    }
+}
+impl fmt::Display for BusInteraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}(", self.bus, self.op)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", arg)?;
+        }
+        f.write_str(")")?;
+        if let Some(selector) = self.selector.as_ref() {
+            write!(f, " when {}", selector)?;
+        }
+        Ok(())
+    }
+}
+
+/// The direction of a [BusInteraction]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BusOp {
+    /// The tuple is added to the bus
+    Send,
+    /// The tuple is removed from the bus
+    Receive,
+}
+impl fmt::Display for BusOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Send => f.write_str("send"),
+            Self::Receive => f.write_str("receive"),
+        }
+    }
+}
+
+/// A lookup of a tuple of values in a table, using the LogUp argument, e.g.:
+///
+/// ```airscript
+/// enf lookup([t0, t1], [a, b], m) when s
+/// ```
+///
+/// On every row, the tuple formed by the table columns is added to the table `m` times, and when
+/// the selector is non-zero (or absent), the query tuple is looked up in the table. Across the
+/// whole trace, every tuple looked up must be present in the table.
+#[derive(Clone, Spanned)]
+pub struct Lookup {
+    #[span]
+    pub span: SourceSpan,
+    /// The values of the tuple added to the table on each row
+    pub table: Vec<ScalarExpr>,
+    /// The values of the tuple being looked up
+    pub query: Vec<ScalarExpr>,
+    /// The number of times the table tuple is added to the table
+    pub multiplicity: ScalarExpr,
+    /// The selector under which the query takes place, if any
+    pub selector: Option<ScalarExpr>,
+}
+impl Lookup {
+    pub fn new(
+        span: SourceSpan,
+        table: Vec<ScalarExpr>,
+        query: Vec<ScalarExpr>,
+        multiplicity: ScalarExpr,
+        selector: Option<ScalarExpr>,
+    ) -> Self {
+        Self {
+            span,
+            table,
+            query,
+            multiplicity,
+            selector,
+        }
+    }
+
+    /// Applies `selector` to the query of this lookup, in addition to its own selector, if any
+    pub fn apply_selector(&mut self, selector: ScalarExpr) {
+        self.selector = Some(match self.selector.take() {
+            None => selector,
+            Some(inner) => ScalarExpr::Binary(BinaryExpr::new(
+                inner.span(),
+                BinaryOp::Mul,
+                selector,
+                inner,
+            )),
+        });
+    }
+}
+impl Eq for Lookup {}
+impl PartialEq for Lookup {
+    fn eq(&self, other: &Self) -> bool {
+        self.table == other.table
+            && self.query == other.query
+            && self.multiplicity == other.multiplicity
+            && self.selector == other.selector
+    }
+}
+impl fmt::Debug for Lookup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lookup")
+            .field("table", &self.table)
+            .field("query", &self.query)
+            .field("multiplicity", &self.multiplicity)
+            .field("selector", &self.selector)
+            .finish()
+    }
+}
+impl fmt::Display for Lookup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lookup({}, {}, {})",
+            DisplayList(self.table.as_slice()),
+            DisplayList(self.query.as_slice()),
+            &self.multiplicity
+        )?;
+        if let Some(selector) = self.selector.as_ref() {
+            write!(f, " when {}", selector)?;
+        }
+        Ok(())
+    }
+}
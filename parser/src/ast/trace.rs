@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 use miden_diagnostics::{SourceSpan, Spanned};
 
@@ -24,6 +24,12 @@ pub struct TraceSegment {
     pub size: usize,
     /// Bindings declared in this segment, without the segment-wide binding, e.g. `$main`
     pub bindings: Vec<TraceBinding>,
+    /// For each binding, the const parameter which determines its size, if any, e.g. `N` in
+    /// `ev limbs<N>([x[N]])`. This is only ever set for the parameters of an evaluator function.
+    ///
+    /// NOTE: Until the evaluator is instantiated, the size of such bindings is unknown, and the
+    /// size and offsets of the bindings in this segment should be disregarded.
+    pub size_params: Vec<Option<Identifier>>,
     /// A vector of `size` elements which tracks for every column whether a
     /// constraint has been applied to that column, and on what boundaries.
     pub boundary_constrained: Vec<Span<ColumnBoundaryFlags>>,
@@ -62,6 +68,7 @@ impl TraceSegment {
             id,
             name,
             size,
+            size_params: vec![None; bindings.len()],
             bindings,
             boundary_constrained: vec![
                 Span::new(SourceSpan::UNKNOWN, ColumnBoundaryFlags::EMPTY);
@@ -70,6 +77,50 @@ impl TraceSegment {
         }
     }
 
+    /// Sets the const parameters which determine the size of the bindings in this segment
+    pub fn with_size_params(mut self, size_params: Vec<Option<Identifier>>) -> Self {
+        assert_eq!(size_params.len(), self.bindings.len());
+        self.size_params = size_params;
+        self
+    }
+
+    /// Sets the declared column types of the bindings in this segment, e.g. `bool` in `s: bool`
+    pub fn with_column_types(mut self, column_types: Vec<Option<ColumnType>>) -> Self {
+        assert_eq!(column_types.len(), self.bindings.len());
+        for (binding, column_type) in self.bindings.iter_mut().zip(column_types) {
+            binding.column_type = column_type;
+        }
+        self
+    }
+
+    /// Returns true if the size of any binding in this segment is given by a const parameter
+    pub fn is_generic(&self) -> bool {
+        self.size_params.iter().any(|param| param.is_some())
+    }
+
+    /// Returns a new [TraceSegment] in which the size of each binding which is given by a const
+    /// parameter is replaced with the value of that parameter in `values`.
+    ///
+    /// Bindings sized by a const parameter are always vectors, even if only a single column wide.
+    pub fn instantiate(&self, values: &HashMap<Identifier, usize>) -> Self {
+        let raw_bindings = self
+            .bindings
+            .iter()
+            .zip(self.size_params.iter())
+            .map(|(binding, param)| {
+                let size = param.map(|param| values[&param]).unwrap_or(binding.size);
+                Span::new(binding.span, (binding.name.unwrap(), size))
+            })
+            .collect();
+        let mut segment = Self::new(self.span, self.id, self.name, raw_bindings);
+        for (binding, param) in segment.bindings.iter_mut().zip(self.size_params.iter()) {
+            if param.is_some() {
+                binding.ty = Type::Vector(binding.size);
+            }
+        }
+        segment
+    }
+
     /// Returns true if `column` is constrained on `boundary`
     pub fn is_boundary_constrained(&self, column: TraceColumnIndex, boundary: Boundary) -> bool {
         self.boundary_constrained[column].is_constrained(boundary)
@@ -96,9 +147,12 @@ impl TraceSegment {
         }
     }
 
+    /// Returns true if this segment has no columns.
+    ///
+    /// A generic segment is never considered empty, as its size is not known until it is instantiated.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.size == 0
+        self.size == 0 && !self.is_generic()
     }
 }
 impl fmt::Debug for TraceSegment {
@@ -108,6 +162,7 @@ impl fmt::Debug for TraceSegment {
             .field("name", &self.name)
             .field("size", &self.size)
             .field("bindings", &self.bindings)
+            .field("size_params", &self.size_params)
             .field(
                 "boundary_constrained",
                 &FormatConstrainedFlags(&self.boundary_constrained),
@@ -126,6 +181,23 @@ impl fmt::Display for TraceSegment {
         }
         if self.bindings.is_empty() {
             write!(f, "[{}]", self.size)
+        } else if self.is_generic() {
+            f.write_str("[")?;
+            for (i, (binding, param)) in self
+                .bindings
+                .iter()
+                .zip(self.size_params.iter())
+                .enumerate()
+            {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                match param {
+                    Some(param) => write!(f, "{}[{}]", binding.name.unwrap(), param)?,
+                    None => write!(f, "{}", binding)?,
+                }
+            }
+            f.write_str("]")
         } else {
             write!(f, "{}", DisplayList(self.bindings.as_slice()))
         }
@@ -134,10 +206,79 @@ impl fmt::Display for TraceSegment {
 impl Eq for TraceSegment {}
 impl PartialEq for TraceSegment {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.bindings == other.bindings && self.size == other.size
+        self.name == other.name
+            && self.bindings == other.bindings
+            && self.size == other.size
+            && self.size_params == other.size_params
     }
 }
 
+/// Infers the values of the const parameters which determine the size of bindings in `params`,
+/// given the number of columns provided for each segment by the arguments of a call, where known.
+///
+/// A parameter can be inferred from a segment once all of the other parameters used in that
+/// segment are known, as long as the remaining columns divide evenly between the bindings sized
+/// by that parameter. Every parameter must be at least 1.
+///
+/// Returns the name of the first parameter which could not be inferred on failure.
+pub fn infer_size_params(
+    params: &[TraceSegment],
+    sizes: &[Option<usize>],
+) -> Result<HashMap<Identifier, usize>, Identifier> {
+    let mut values = HashMap::<Identifier, usize>::default();
+    loop {
+        let mut changed = false;
+        for (segment, size) in params.iter().zip(sizes.iter().copied()) {
+            let Some(size) = size else {
+                continue;
+            };
+            let mut fixed = 0;
+            let mut unknown = None;
+            let mut count = 0;
+            for (binding, param) in segment.bindings.iter().zip(segment.size_params.iter()) {
+                match param {
+                    None => fixed += binding.size,
+                    Some(param) => match values.get(param) {
+                        Some(value) => fixed += value,
+                        None if unknown.is_none() || unknown == Some(*param) => {
+                            unknown = Some(*param);
+                            count += 1;
+                        }
+                        // There is more than one unknown parameter in this segment
+                        None => {
+                            unknown = None;
+                            break;
+                        }
+                    },
+                }
+            }
+            let Some(param) = unknown else {
+                continue;
+            };
+            if size <= fixed || (size - fixed) % count != 0 {
+                return Err(param);
+            }
+            values.insert(param, (size - fixed) / count);
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Every parameter referenced by the segments must have been inferred
+    for param in params
+        .iter()
+        .flat_map(|segment| segment.size_params.iter().flatten())
+    {
+        if !values.contains_key(param) {
+            return Err(*param);
+        }
+    }
+
+    Ok(values)
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Default)]
 pub struct ColumnBoundaryFlags(u8);
 impl ColumnBoundaryFlags {
@@ -218,6 +359,10 @@ pub struct TraceBinding {
     pub size: usize,
     /// The effective type of this binding
     pub ty: Type,
+    /// The declared type of the values in the columns bound by this binding, if any, e.g. `bool` in `s: bool`
+    ///
+    /// Declaring a column type implies constraints on the columns, see [ColumnType].
+    pub column_type: Option<ColumnType>,
 }
 impl TraceBinding {
     /// Creates a new trace binding.
@@ -236,6 +381,7 @@ impl TraceBinding {
             offset,
             size,
             ty,
+            column_type: None,
         }
     }
 
@@ -291,6 +437,7 @@ impl PartialEq for TraceBinding {
             && self.offset == other.offset
             && self.size == other.size
             && self.ty == other.ty
+            && self.column_type == other.column_type
     }
 }
 impl fmt::Debug for TraceBinding {
@@ -301,6 +448,7 @@ impl fmt::Debug for TraceBinding {
             .field("offset", &self.offset)
             .field("size", &self.size)
             .field("ty", &self.ty)
+            .field("column_type", &self.column_type)
             .finish()
     }
 }
@@ -311,14 +459,49 @@ impl fmt::Display for TraceBinding {
                 f,
                 "{}",
                 self.name.as_ref().map(|n| n.as_str()).unwrap_or("?")
-            )
+            )?;
         } else {
             write!(
                 f,
                 "{}[{}]",
                 self.name.as_ref().map(|n| n.as_str()).unwrap_or("?"),
                 self.size
-            )
+            )?;
+        }
+        if let Some(column_type) = self.column_type {
+            write!(f, ": {}", column_type)?;
+        }
+        Ok(())
+    }
+}
+
+/// The type of the values in a trace column, as declared by its binding, e.g. `s: bool`
+///
+/// Each type implies constraints on the columns it is declared for:
+///
+/// * `bool` columns are constrained to be binary, i.e. `s^2 = s`
+/// * Bounded integer columns, e.g. `u16`, are range checked by sending their values on the bus
+///   declared as the range check for that type, e.g. `rc: multiset for u16`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColumnType {
+    Bool,
+    U8,
+    U16,
+    U32,
+}
+impl ColumnType {
+    /// Returns true if this is a bounded integer type, i.e. one which requires a range check
+    pub fn is_bounded_integer(&self) -> bool {
+        !matches!(self, Self::Bool)
+    }
+}
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bool => f.write_str("bool"),
+            Self::U8 => f.write_str("u8"),
+            Self::U16 => f.write_str("u16"),
+            Self::U32 => f.write_str("u32"),
         }
     }
 }
@@ -29,7 +29,19 @@ impl Type {
     /// Returns true if this type is a valid iterable in a comprehension
     #[inline]
     pub fn is_iterable(&self) -> bool {
-        self.is_vector()
+        matches!(self, Self::Vector(_) | Self::Matrix(_, _))
+    }
+
+    /// Returns the number of elements produced when iterating over a value of this type,
+    /// i.e. the length of a vector, or the number of rows of a matrix.
+    ///
+    /// Returns `None` if this type is not iterable.
+    pub fn iterable_len(&self) -> Option<usize> {
+        match self {
+            Self::Felt => None,
+            Self::Vector(len) => Some(*len),
+            Self::Matrix(rows, _) => Some(*rows),
+        }
     }
 
     /// Returns true if this type is a vector
@@ -83,8 +95,9 @@ impl fmt::Display for Type {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FunctionType {
     /// An evaluator function, which has no results, and has
-    /// a complex type signature due to the nature of trace bindings
-    Evaluator(Vec<TraceSegment>),
+    /// a complex type signature due to the nature of trace bindings,
+    /// followed by any value parameters
+    Evaluator(Vec<TraceSegment>, Vec<ValueParam>),
     /// A standard function with one or more inputs, and a result
     #[allow(dead_code)]
     Function(Vec<Type>, Type),
@@ -92,7 +105,7 @@ pub enum FunctionType {
 impl FunctionType {
     pub fn result(&self) -> Option<Type> {
         match self {
-            Self::Evaluator(_) => None,
+            Self::Evaluator(..) => None,
             Self::Function(_, result) => Some(*result),
         }
     }
@@ -181,6 +181,15 @@ pub trait VisitMut<T> {
     fn visit_mut_enforce_all(&mut self, expr: &mut ast::ListComprehension) -> ControlFlow<T> {
         self.visit_mut_list_comprehension(expr)
     }
+    fn visit_mut_enforce_match(&mut self, expr: &mut ast::Match) -> ControlFlow<T> {
+        visit_mut_enforce_match(self, expr)
+    }
+    fn visit_mut_bus_interaction(&mut self, expr: &mut ast::BusInteraction) -> ControlFlow<T> {
+        visit_mut_bus_interaction(self, expr)
+    }
+    fn visit_mut_lookup(&mut self, expr: &mut ast::Lookup) -> ControlFlow<T> {
+        visit_mut_lookup(self, expr)
+    }
     fn visit_mut_integrity_constraints(
         &mut self,
         exprs: &mut Vec<ast::Statement>,
@@ -196,12 +205,24 @@ pub trait VisitMut<T> {
     fn visit_mut_binary_expr(&mut self, expr: &mut ast::BinaryExpr) -> ControlFlow<T> {
         visit_mut_binary_expr(self, expr)
     }
+    fn visit_mut_if_expr(&mut self, expr: &mut ast::IfExpr) -> ControlFlow<T> {
+        visit_mut_if_expr(self, expr)
+    }
+    fn visit_mut_comparison(&mut self, expr: &mut ast::Comparison) -> ControlFlow<T> {
+        visit_mut_comparison(self, expr)
+    }
+    fn visit_mut_range_expr(&mut self, expr: &mut ast::RangeExpr) -> ControlFlow<T> {
+        visit_mut_range_expr(self, expr)
+    }
     fn visit_mut_list_comprehension(
         &mut self,
         expr: &mut ast::ListComprehension,
     ) -> ControlFlow<T> {
         visit_mut_list_comprehension(self, expr)
     }
+    fn visit_mut_lambda(&mut self, expr: &mut ast::Lambda) -> ControlFlow<T> {
+        visit_mut_lambda(self, expr)
+    }
     fn visit_mut_call(&mut self, expr: &mut ast::Call) -> ControlFlow<T> {
         visit_mut_call(self, expr)
     }
@@ -308,6 +329,15 @@ where
     fn visit_mut_enforce_all(&mut self, expr: &mut ast::ListComprehension) -> ControlFlow<T> {
         (**self).visit_mut_enforce_all(expr)
     }
+    fn visit_mut_enforce_match(&mut self, expr: &mut ast::Match) -> ControlFlow<T> {
+        (**self).visit_mut_enforce_match(expr)
+    }
+    fn visit_mut_bus_interaction(&mut self, expr: &mut ast::BusInteraction) -> ControlFlow<T> {
+        (**self).visit_mut_bus_interaction(expr)
+    }
+    fn visit_mut_lookup(&mut self, expr: &mut ast::Lookup) -> ControlFlow<T> {
+        (**self).visit_mut_lookup(expr)
+    }
     fn visit_mut_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<T> {
         (**self).visit_mut_expr(expr)
     }
@@ -317,12 +347,24 @@ where
     fn visit_mut_binary_expr(&mut self, expr: &mut ast::BinaryExpr) -> ControlFlow<T> {
         (**self).visit_mut_binary_expr(expr)
     }
+    fn visit_mut_if_expr(&mut self, expr: &mut ast::IfExpr) -> ControlFlow<T> {
+        (**self).visit_mut_if_expr(expr)
+    }
+    fn visit_mut_comparison(&mut self, expr: &mut ast::Comparison) -> ControlFlow<T> {
+        (**self).visit_mut_comparison(expr)
+    }
+    fn visit_mut_range_expr(&mut self, expr: &mut ast::RangeExpr) -> ControlFlow<T> {
+        (**self).visit_mut_range_expr(expr)
+    }
     fn visit_mut_list_comprehension(
         &mut self,
         expr: &mut ast::ListComprehension,
     ) -> ControlFlow<T> {
         (**self).visit_mut_list_comprehension(expr)
     }
+    fn visit_mut_lambda(&mut self, expr: &mut ast::Lambda) -> ControlFlow<T> {
+        (**self).visit_mut_lambda(expr)
+    }
     fn visit_mut_call(&mut self, expr: &mut ast::Call) -> ControlFlow<T> {
         (**self).visit_mut_call(expr)
     }
@@ -365,7 +407,7 @@ where
     for input in module.public_inputs.values_mut() {
         visitor.visit_mut_public_input(input)?;
     }
-    if let Some(rv) = module.random_values.as_mut() {
+    for rv in module.random_values.values_mut() {
         visitor.visit_mut_random_values(rv)?;
     }
     for segment in module.trace_columns.iter_mut() {
@@ -529,10 +571,54 @@ where
             visitor.visit_mut_enforce_if(expr, selector)
         }
         ast::Statement::EnforceAll(ref mut expr) => visitor.visit_mut_enforce_all(expr),
+        ast::Statement::EnforceMatch(ref mut expr) => visitor.visit_mut_enforce_match(expr),
+        ast::Statement::BusInteraction(ref mut expr) => visitor.visit_mut_bus_interaction(expr),
+        ast::Statement::Lookup(ref mut expr) => visitor.visit_mut_lookup(expr),
         ast::Statement::Expr(ref mut expr) => visitor.visit_mut_expr(expr),
     }
 }
 
+pub fn visit_mut_bus_interaction<V, T>(
+    visitor: &mut V,
+    expr: &mut ast::BusInteraction,
+) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    visitor.visit_mut_identifier(&mut expr.bus)?;
+    for arg in expr.args.iter_mut() {
+        visitor.visit_mut_scalar_expr(arg)?;
+    }
+    if let Some(selector) = expr.selector.as_mut() {
+        visitor.visit_mut_scalar_expr(selector)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn visit_mut_lookup<V, T>(visitor: &mut V, expr: &mut ast::Lookup) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    for value in expr.table.iter_mut().chain(expr.query.iter_mut()) {
+        visitor.visit_mut_scalar_expr(value)?;
+    }
+    visitor.visit_mut_scalar_expr(&mut expr.multiplicity)?;
+    if let Some(selector) = expr.selector.as_mut() {
+        visitor.visit_mut_scalar_expr(selector)?;
+    }
+    ControlFlow::Continue(())
+}
+
+pub fn visit_mut_enforce_match<V, T>(visitor: &mut V, expr: &mut ast::Match) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    for arm in expr.iter_mut() {
+        visitor.visit_mut_enforce_all(arm)?;
+    }
+    ControlFlow::Continue(())
+}
+
 pub fn visit_mut_let<V, T>(visitor: &mut V, expr: &mut ast::Let) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
@@ -550,7 +636,8 @@ where
     V: ?Sized + VisitMut<T>,
 {
     match expr {
-        ast::Expr::Const(_) | ast::Expr::Range(_) => ControlFlow::Continue(()),
+        ast::Expr::Const(_) => ControlFlow::Continue(()),
+        ast::Expr::Range(ref mut expr) => visitor.visit_mut_range_expr(expr),
         ast::Expr::Vector(ref mut exprs) => {
             for expr in exprs.iter_mut() {
                 visitor.visit_mut_expr(expr)?;
@@ -567,8 +654,10 @@ where
         }
         ast::Expr::SymbolAccess(ref mut expr) => visitor.visit_mut_symbol_access(expr),
         ast::Expr::Binary(ref mut expr) => visitor.visit_mut_binary_expr(expr),
+        ast::Expr::If(ref mut expr) => visitor.visit_mut_if_expr(expr),
         ast::Expr::Call(ref mut expr) => visitor.visit_mut_call(expr),
         ast::Expr::ListComprehension(ref mut expr) => visitor.visit_mut_list_comprehension(expr),
+        ast::Expr::Lambda(ref mut expr) => visitor.visit_mut_lambda(expr),
     }
 }
 
@@ -583,6 +672,7 @@ where
             visitor.visit_mut_bounded_symbol_access(expr)
         }
         ast::ScalarExpr::Binary(ref mut expr) => visitor.visit_mut_binary_expr(expr),
+        ast::ScalarExpr::If(ref mut expr) => visitor.visit_mut_if_expr(expr),
         ast::ScalarExpr::Call(ref mut expr) => visitor.visit_mut_call(expr),
     }
 }
@@ -595,6 +685,30 @@ where
     visitor.visit_mut_scalar_expr(expr.rhs.as_mut())
 }
 
+pub fn visit_mut_if_expr<V, T>(visitor: &mut V, expr: &mut ast::IfExpr) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    visitor.visit_mut_comparison(&mut expr.condition)?;
+    visitor.visit_mut_scalar_expr(expr.then_expr.as_mut())?;
+    visitor.visit_mut_scalar_expr(expr.else_expr.as_mut())
+}
+
+pub fn visit_mut_comparison<V, T>(visitor: &mut V, expr: &mut ast::Comparison) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    visitor.visit_mut_scalar_expr(expr.lhs.as_mut())?;
+    visitor.visit_mut_scalar_expr(expr.rhs.as_mut())
+}
+
+pub fn visit_mut_range_expr<V, T>(_visitor: &mut V, _expr: &mut ast::RangeExpr) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    ControlFlow::Continue(())
+}
+
 pub fn visit_mut_list_comprehension<V, T>(
     visitor: &mut V,
     expr: &mut ast::ListComprehension,
@@ -614,6 +728,16 @@ where
     visitor.visit_mut_scalar_expr(expr.body.as_mut())
 }
 
+pub fn visit_mut_lambda<V, T>(visitor: &mut V, expr: &mut ast::Lambda) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    for param in expr.params.iter_mut() {
+        visitor.visit_mut_identifier(param)?;
+    }
+    visitor.visit_mut_scalar_expr(expr.body.as_mut())
+}
+
 pub fn visit_mut_call<V, T>(visitor: &mut V, expr: &mut ast::Call) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
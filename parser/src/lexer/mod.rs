@@ -2,6 +2,7 @@
 mod tests;
 
 use core::{fmt, mem, num::IntErrorKind};
+use std::collections::VecDeque;
 
 use miden_diagnostics::{Diagnostic, SourceIndex, SourceSpan, ToDiagnostic};
 use miden_parsing::{Scanner, Source};
@@ -21,6 +22,8 @@ pub enum LexicalError {
     },
     #[error("encountered unexpected character '{found}'")]
     UnexpectedCharacter { start: SourceIndex, found: char },
+    #[error("unterminated string literal")]
+    UnterminatedString { span: SourceSpan },
 }
 impl PartialEq for LexicalError {
     fn eq(&self, other: &Self) -> bool {
@@ -32,6 +35,7 @@ impl PartialEq for LexicalError {
                 Self::UnexpectedCharacter { found: lhs, .. },
                 Self::UnexpectedCharacter { found: rhs, .. },
             ) => lhs == rhs,
+            (Self::UnterminatedString { .. }, Self::UnterminatedString { .. }) => true,
             _ => false,
         }
     }
@@ -51,6 +55,10 @@ impl ToDiagnostic for LexicalError {
                     start.source_id(),
                     SourceSpan::new(start, start),
                 )]),
+            Self::UnterminatedString { span } => Diagnostic::error()
+                .with_message("unterminated string literal")
+                .with_labels(vec![Label::primary(span.source_id(), span)
+                    .with_message("expected a closing '\"' on the same line")]),
         }
     }
 }
@@ -86,6 +94,8 @@ pub enum Token {
     FunctionIdent(Symbol),
     /// Integers should only contain numeric characters.
     Num(u64),
+    /// A string literal, e.g. the message of an assertion
+    Str(Symbol),
 
     // DECLARATION KEYWORDS
     // --------------------------------------------------------------------------------------------
@@ -95,10 +105,16 @@ pub enum Token {
     Mod,
     /// Used to import items from an AIR module.
     Use,
+    /// Used to rename an imported module or item.
+    As,
+    /// Used to make a module item visible to other modules.
+    Pub,
     /// Used to declare intermediate variables in the AIR constraints module.
     Let,
     /// Used to declare constants in the AIR constraints module.
     Const,
+    /// Used to declare compile-time assertions in the AIR constraints module.
+    Assert,
     /// Used to declare trace columns section in the AIR constraints module.
     TraceColumns,
     /// Used to declare main trace columns.
@@ -111,6 +127,8 @@ pub enum Token {
     PeriodicColumns,
     /// Keyword to declare random values section in the AIR constraints module.
     RandomValues,
+    /// Keyword to declare the buses section in the AIR constraints module.
+    Buses,
     /// Keyword to declare the evaluator function section in the AIR constraints module.
     Ev,
 
@@ -132,6 +150,8 @@ pub enum Token {
     // --------------------------------------------------------------------------------------------
     For,
     In,
+    /// Keyword to specify the step of a range, e.g. `0..16 by 2`
+    By,
 
     // GENERAL KEYWORDS
     // --------------------------------------------------------------------------------------------
@@ -139,6 +159,9 @@ pub enum Token {
     Enf,
     Match,
     Case,
+    If,
+    Then,
+    Else,
     When,
 
     // PUNCTUATION
@@ -153,14 +176,31 @@ pub enum Token {
     RParen,
     LBracket,
     RBracket,
+    LBrace,
+    RBrace,
+    LAngle,
+    LAngleEqual,
+    RAngle,
+    RAngleEqual,
     Equal,
+    EqualEqual,
     Plus,
     Minus,
     Star,
+    Slash,
     Caret,
     Ampersand,
     Bar,
     Bang,
+    BangEqual,
+    Underscore,
+
+    // LAYOUT
+    // --------------------------------------------------------------------------------------------
+    /// Marks the beginning of an indented block, e.g. the body of `enf when s:`
+    Indent,
+    /// Marks the end of an indented block
+    Dedent,
 }
 impl Token {
     pub fn from_keyword_or_ident(s: &str) -> Self {
@@ -168,14 +208,18 @@ impl Token {
             "def" => Self::Def,
             "mod" => Self::Mod,
             "use" => Self::Use,
+            "as" => Self::As,
+            "pub" => Self::Pub,
             "let" => Self::Let,
             "const" => Self::Const,
+            "assert" => Self::Assert,
             "trace_columns" => Self::TraceColumns,
             "main" => Self::Main,
             "aux" => Self::Aux,
             "public_inputs" => Self::PublicInputs,
             "periodic_columns" => Self::PeriodicColumns,
             "random_values" => Self::RandomValues,
+            "buses" => Self::Buses,
             "ev" => Self::Ev,
             "boundary_constraints" => Self::BoundaryConstraints,
             "integrity_constraints" => Self::IntegrityConstraints,
@@ -183,9 +227,13 @@ impl Token {
             "last" => Self::Last,
             "for" => Self::For,
             "in" => Self::In,
+            "by" => Self::By,
             "enf" => Self::Enf,
             "match" => Self::Match,
             "case" => Self::Case,
+            "if" => Self::If,
+            "then" => Self::Then,
+            "else" => Self::Else,
             "when" => Self::When,
             other => Self::Ident(Symbol::intern(other)),
         }
@@ -220,6 +268,11 @@ impl PartialEq for Token {
                     return i == i2;
                 }
             }
+            Self::Str(s) => {
+                if let Self::Str(s2) = other {
+                    return s == s2;
+                }
+            }
             _ => return mem::discriminant(self) == mem::discriminant(other),
         }
         false
@@ -235,17 +288,22 @@ impl fmt::Display for Token {
             Self::DeclIdentRef(ref id) => write!(f, "{}", id),
             Self::FunctionIdent(ref id) => write!(f, "{}", id),
             Self::Num(ref i) => write!(f, "{}", i),
+            Self::Str(ref s) => write!(f, "\"{}\"", s),
             Self::Def => write!(f, "def"),
             Self::Mod => write!(f, "mod"),
             Self::Use => write!(f, "use"),
+            Self::As => write!(f, "as"),
+            Self::Pub => write!(f, "pub"),
             Self::Let => write!(f, "let"),
             Self::Const => write!(f, "const"),
+            Self::Assert => write!(f, "assert"),
             Self::TraceColumns => write!(f, "trace_columns"),
             Self::Main => write!(f, "main"),
             Self::Aux => write!(f, "aux"),
             Self::PublicInputs => write!(f, "public_inputs"),
             Self::PeriodicColumns => write!(f, "periodic_columns"),
             Self::RandomValues => write!(f, "random_values"),
+            Self::Buses => write!(f, "buses"),
             Self::Ev => write!(f, "ev"),
             Self::BoundaryConstraints => write!(f, "boundary_constraints"),
             Self::First => write!(f, "first"),
@@ -253,9 +311,13 @@ impl fmt::Display for Token {
             Self::IntegrityConstraints => write!(f, "integrity_constraints"),
             Self::For => write!(f, "for"),
             Self::In => write!(f, "in"),
+            Self::By => write!(f, "by"),
             Self::Enf => write!(f, "enf"),
             Self::Match => write!(f, "match"),
             Self::Case => write!(f, "case"),
+            Self::If => write!(f, "if"),
+            Self::Then => write!(f, "then"),
+            Self::Else => write!(f, "else"),
             Self::When => write!(f, "when"),
             Self::Quote => write!(f, "'"),
             Self::Colon => write!(f, ":"),
@@ -267,14 +329,26 @@ impl fmt::Display for Token {
             Self::RParen => write!(f, ")"),
             Self::LBracket => write!(f, "["),
             Self::RBracket => write!(f, "]"),
+            Self::LBrace => write!(f, "{{"),
+            Self::RBrace => write!(f, "}}"),
+            Self::LAngle => write!(f, "<"),
+            Self::LAngleEqual => write!(f, "<="),
+            Self::RAngle => write!(f, ">"),
+            Self::RAngleEqual => write!(f, ">="),
             Self::Equal => write!(f, "="),
+            Self::EqualEqual => write!(f, "=="),
             Self::Plus => write!(f, "+"),
             Self::Minus => write!(f, "-"),
             Self::Star => write!(f, "*"),
+            Self::Slash => write!(f, "/"),
             Self::Caret => write!(f, "^"),
             Self::Ampersand => write!(f, "&"),
             Self::Bar => write!(f, "|"),
             Self::Bang => write!(f, "!"),
+            Self::BangEqual => write!(f, "!="),
+            Self::Underscore => write!(f, "_"),
+            Self::Indent => write!(f, "INDENT"),
+            Self::Dedent => write!(f, "DEDENT"),
         }
     }
 }
@@ -313,6 +387,12 @@ macro_rules! pop2 {
 /// If an error is unrecoverable, the lexer will continue to produce tokens, but there is no
 /// guarantee that parsing them will produce meaningful results, it is primarily to assist in
 /// gathering as many errors as possible.
+///
+/// # Layout
+///
+/// Whitespace is insignificant, with one exception: the body of a block of the form `enf when s:`
+/// extends for as long as its lines are indented further than the line containing `enf`. The lexer
+/// emits an `Indent` token at the start of such a body, and a `Dedent` token at its end.
 pub struct Lexer<S> {
     /// The scanner produces a sequence of chars + location, and can be controlled
     /// The location type is SourceIndex
@@ -334,6 +414,39 @@ pub struct Lexer<S> {
     /// produced after that point is Token::Eof, or None, depending on how you are
     /// consuming the lexer
     eof: bool,
+
+    /// True if the current token is the first token on its line
+    token_line_start: bool,
+
+    /// The column at which the current token starts, only meaningful if it starts a line
+    token_column: usize,
+
+    /// Set when a newline is encountered, until the next token has been started
+    at_line_start: bool,
+
+    /// The indentation of the line containing the most recently produced token
+    line_indent: usize,
+
+    /// The most recently produced token, used to recognize the start of a block
+    prev: Token,
+
+    /// When lexing the header of a block, e.g. `enf when s:`, this is the indentation of the
+    /// line the header begins on, and the current nesting depth of brackets in the header
+    block_header: Option<(usize, usize)>,
+
+    /// When set, the next token begins the body of a block whose header began on a line with
+    /// the given indentation
+    block_start: Option<usize>,
+
+    /// The indentation of the body of each block we are currently in, innermost last
+    blocks: Vec<usize>,
+
+    /// Tokens which have been lexed, but not yet produced, e.g. layout tokens
+    pending: VecDeque<Lexed>,
+
+    /// True if the token being lexed immediately follows `ev`, i.e. it names an evaluator, which
+    /// may be followed by const parameters, e.g. `ev foo<N>(...)`, rather than a comparison
+    after_ev: bool,
 }
 impl<S> Lexer<S>
 where
@@ -351,6 +464,16 @@ where
             token_start: start + ByteOffset(0),
             token_end: start + ByteOffset(0),
             eof: false,
+            token_line_start: true,
+            token_column: 0,
+            at_line_start: true,
+            line_indent: 0,
+            prev: Token::Eof,
+            block_header: None,
+            block_start: None,
+            blocks: vec![],
+            pending: VecDeque::new(),
+            after_ev: false,
         };
         lexer.advance();
         lexer
@@ -364,6 +487,7 @@ where
         let token = std::mem::replace(&mut self.token, Token::Eof);
         let start = self.token_start;
         let end = self.token_end;
+        self.after_ev = token == Token::Ev;
         self.advance();
         match token {
             Token::Error(err) => Some(Err(err.into())),
@@ -379,6 +503,7 @@ where
     #[inline]
     fn advance_start(&mut self) {
         let mut position: SourceIndex;
+        let mut column = self.token_column;
         loop {
             let (pos, c) = self.scanner.read();
 
@@ -389,7 +514,15 @@ where
                 return;
             }
 
+            if c == '\n' {
+                self.at_line_start = true;
+                column = 0;
+                self.scanner.advance();
+                continue;
+            }
+
             if c.is_whitespace() {
+                column += 1;
                 self.scanner.advance();
                 continue;
             }
@@ -398,6 +531,8 @@ where
         }
 
         self.token_start = position;
+        self.token_line_start = mem::replace(&mut self.at_line_start, false);
+        self.token_column = column;
     }
 
     #[inline]
@@ -484,14 +619,33 @@ where
             ')' => pop!(self, Token::RParen),
             '[' => pop!(self, Token::LBracket),
             ']' => pop!(self, Token::RBracket),
-            '=' => pop!(self, Token::Equal),
+            '{' => pop!(self, Token::LBrace),
+            '}' => pop!(self, Token::RBrace),
+            '<' => match self.peek() {
+                '=' => pop2!(self, Token::LAngleEqual),
+                _ => pop!(self, Token::LAngle),
+            },
+            '>' => match self.peek() {
+                '=' => pop2!(self, Token::RAngleEqual),
+                _ => pop!(self, Token::RAngle),
+            },
+            '=' => match self.peek() {
+                '=' => pop2!(self, Token::EqualEqual),
+                _ => pop!(self, Token::Equal),
+            },
             '+' => pop!(self, Token::Plus),
             '-' => pop!(self, Token::Minus),
             '*' => pop!(self, Token::Star),
+            '/' => pop!(self, Token::Slash),
             '^' => pop!(self, Token::Caret),
             '&' => pop!(self, Token::Ampersand),
             '|' => pop!(self, Token::Bar),
-            '!' => pop!(self, Token::Bang),
+            '!' => match self.peek() {
+                '=' => pop2!(self, Token::BangEqual),
+                _ => pop!(self, Token::Bang),
+            },
+            '_' => pop!(self, Token::Underscore),
+            '"' => self.lex_string(),
             '$' => self.lex_special_identifier(),
             '0'..='9' => self.lex_number(),
             'a'..='z' => self.lex_keyword_or_ident(),
@@ -551,9 +705,8 @@ where
 
         self.skip_ident();
 
-        let next = self.read();
         match Token::from_keyword_or_ident(self.slice()) {
-            Token::Ident(id) if next == '(' => Token::FunctionIdent(id),
+            Token::Ident(id) if self.is_function_ident() => Token::FunctionIdent(id),
             token => token,
         }
     }
@@ -565,13 +718,24 @@ where
 
         self.skip_ident();
 
-        if self.read() == '(' {
+        if self.is_function_ident() {
             Token::FunctionIdent(Symbol::intern(self.slice()))
         } else {
             Token::Ident(Symbol::intern(self.slice()))
         }
     }
 
+    /// Returns true if the identifier just lexed names a function, i.e. it is followed by its
+    /// arguments, or by the const parameters of an evaluator being defined. Otherwise `<` is
+    /// a comparison, e.g. `i<3`.
+    fn is_function_ident(&mut self) -> bool {
+        match self.read() {
+            '(' => true,
+            '<' => self.after_ev,
+            _ => false,
+        }
+    }
+
     fn skip_ident(&mut self) {
         loop {
             match self.read() {
@@ -583,6 +747,29 @@ where
         }
     }
 
+    fn lex_string(&mut self) -> Token {
+        // Skip the opening quote
+        debug_assert_eq!(self.read(), '"');
+        self.skip();
+
+        let mut value = String::new();
+        loop {
+            match self.read() {
+                '"' => {
+                    self.skip();
+                    break;
+                }
+                // Strings may not span multiple lines
+                '\n' | '\0' => {
+                    return Token::Error(LexicalError::UnterminatedString { span: self.span() })
+                }
+                _ => value.push(self.pop()),
+            }
+        }
+
+        Token::Str(Symbol::intern(&value))
+    }
+
     #[inline]
     fn lex_number(&mut self) -> Token {
         let mut num = String::new();
@@ -611,10 +798,84 @@ where
     type Item = Lexed;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(lexed) = self.pending.pop_front() {
+            return Some(lexed);
+        }
+
+        let mut layout = (self.token_line_start, self.token_column);
         let mut res = self.lex();
         while let Some(Ok((_, Token::Comment, _))) = res {
+            layout = (self.token_line_start, self.token_column);
             res = self.lex();
         }
-        res
+
+        self.apply_layout(res, layout.0, layout.1);
+        self.pending.pop_front()
+    }
+}
+impl<S> Lexer<S>
+where
+    S: Source,
+{
+    /// Queues up `lexed`, preceded by any layout tokens which must be produced before it, given
+    /// whether the token starts a line, and if so, at what column.
+    fn apply_layout(&mut self, lexed: Option<Lexed>, line_start: bool, column: usize) {
+        let (start, token, end) = match lexed {
+            Some(Ok(lexed)) => lexed,
+            Some(Err(err)) => {
+                self.pending.push_back(Err(err));
+                return;
+            }
+            // Close any blocks which are still open at the end of the input
+            None => {
+                let end = self.token_end;
+                for _ in self.blocks.drain(..) {
+                    self.pending.push_back(Ok((end, Token::Dedent, end)));
+                }
+                return;
+            }
+        };
+
+        if line_start {
+            self.line_indent = column;
+        }
+
+        // The first token following the header of a block determines the indentation of its body
+        let mut dedent = line_start;
+        if let Some(header_indent) = self.block_start.take() {
+            self.pending.push_back(Ok((start, Token::Indent, start)));
+            if line_start && column <= header_indent {
+                // The body is empty, let the parser report it
+                self.pending.push_back(Ok((start, Token::Dedent, start)));
+            } else {
+                self.blocks.push(column);
+                dedent = false;
+            }
+        }
+
+        // Close all of the blocks which this token is not indented far enough to be a part of
+        if dedent {
+            while matches!(self.blocks.last(), Some(indent) if column < *indent) {
+                self.blocks.pop();
+                self.pending.push_back(Ok((start, Token::Dedent, start)));
+            }
+        }
+
+        // Track the header of a block, so we know where its body begins
+        match (&self.prev, &token, self.block_header.as_mut()) {
+            (Token::Enf, Token::When, _) => self.block_header = Some((self.line_indent, 0)),
+            (_, Token::LParen | Token::LBracket | Token::LBrace, Some((_, depth))) => *depth += 1,
+            (_, Token::RParen | Token::RBracket | Token::RBrace, Some((_, depth))) => {
+                *depth = depth.saturating_sub(1)
+            }
+            (_, Token::Colon, Some((indent, 0))) => {
+                self.block_start = Some(*indent);
+                self.block_header = None;
+            }
+            _ => (),
+        }
+
+        self.prev = token.clone();
+        self.pending.push_back(Ok((start, token, end)));
     }
 }
@@ -113,3 +113,22 @@ fn ops_without_matching_closing_parens() {
     ];
     expect_valid_tokenization(source, tokens);
 }
+
+#[test]
+fn div_op() {
+    let source = "enf clk' = clk * (1 / 2)";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident(Symbol::intern("clk")),
+        Token::Quote,
+        Token::Equal,
+        Token::Ident(Symbol::intern("clk")),
+        Token::Star,
+        Token::LParen,
+        Token::Num(1),
+        Token::Slash,
+        Token::Num(2),
+        Token::RParen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
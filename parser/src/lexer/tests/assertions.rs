@@ -0,0 +1,34 @@
+use miden_diagnostics::SourceSpan;
+
+use super::{expect_error_at_location, expect_valid_tokenization, LexicalError, Symbol, Token};
+
+#[test]
+fn assertion() {
+    let source = r#"assert A * B == 64 : "limbs must cover 64 bits""#;
+
+    let tokens = vec![
+        Token::Assert,
+        Token::Ident(Symbol::intern("A")),
+        Token::Star,
+        Token::Ident(Symbol::intern("B")),
+        Token::EqualEqual,
+        Token::Num(64),
+        Token::Colon,
+        Token::Str(Symbol::intern("limbs must cover 64 bits")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+// SCAN ERRORS
+// ================================================================================================
+
+#[test]
+fn error_unterminated_string() {
+    let source = "assert A == 1 : \"missing quote
+    const B = 2";
+
+    let expected = LexicalError::UnterminatedString {
+        span: SourceSpan::UNKNOWN,
+    };
+    expect_error_at_location(source, expected, 0, 16);
+}
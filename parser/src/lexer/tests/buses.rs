@@ -0,0 +1,36 @@
+use super::{expect_valid_tokenization, Symbol, Token};
+
+#[test]
+fn buses_section() {
+    let source = "
+buses:
+    p: multiset";
+
+    let tokens = vec![
+        Token::Buses,
+        Token::Colon,
+        Token::Ident(Symbol::intern("p")),
+        Token::Colon,
+        Token::Ident(Symbol::intern("multiset")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn bus_interaction() {
+    let source = "p.send(a, b) when s";
+
+    let tokens = vec![
+        Token::Ident(Symbol::intern("p")),
+        Token::Dot,
+        Token::FunctionIdent(Symbol::intern("send")),
+        Token::LParen,
+        Token::Ident(Symbol::intern("a")),
+        Token::Comma,
+        Token::Ident(Symbol::intern("b")),
+        Token::RParen,
+        Token::When,
+        Token::Ident(Symbol::intern("s")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -121,7 +121,7 @@ fn ev_fn_with_main_and_aux_cols() {
         Token::RBracket,
         Token::RParen,
         Token::Let,
-        Token::Ident(Symbol::intern("as")),
+        Token::As,
         Token::Equal,
         Token::LBracket,
         Token::Ident(Symbol::intern("x")),
@@ -150,7 +150,7 @@ fn ev_fn_with_main_and_aux_cols() {
         Token::Num(0),
         Token::RBracket,
         Token::Equal,
-        Token::Ident(Symbol::intern("as")),
+        Token::As,
         Token::LBracket,
         Token::Num(0),
         Token::RBracket,
@@ -148,3 +148,25 @@ fn multiple_iterables_list_folding() {
     ];
     expect_valid_tokenization(source, tokens);
 }
+
+#[test]
+fn stepped_range_comprehension() {
+    let source = "let y = [x for x in 0..16 by 2]";
+    let tokens = vec![
+        Token::Let,
+        Token::Ident(Symbol::intern("y")),
+        Token::Equal,
+        Token::LBracket,
+        Token::Ident(Symbol::intern("x")),
+        Token::For,
+        Token::Ident(Symbol::intern("x")),
+        Token::In,
+        Token::Num(0),
+        Token::DotDot,
+        Token::Num(16),
+        Token::By,
+        Token::Num(2),
+        Token::RBracket,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
@@ -3,7 +3,9 @@ use crate::parser::ParseError;
 use crate::Symbol;
 
 mod arithmetic_ops;
+mod assertions;
 mod boundary_constraints;
+mod buses;
 mod constants;
 mod evaluator_functions;
 mod identifiers;
@@ -12,6 +14,7 @@ mod modules;
 mod periodic_columns;
 mod pub_inputs;
 mod random_values;
+mod selectors;
 mod variables;
 
 // TEST HELPERS
@@ -51,6 +54,7 @@ fn expect_error_at_location(source: &str, expected: LexicalError, line: u32, col
 
     let loc = match &err {
         LexicalError::InvalidInt { span, .. } => codemap.location(span).unwrap(),
+        LexicalError::UnterminatedString { span } => codemap.location(span).unwrap(),
         LexicalError::UnexpectedCharacter { start, .. } => {
             let span = miden_diagnostics::SourceSpan::new(*start, *start);
             codemap.location(&span).unwrap()
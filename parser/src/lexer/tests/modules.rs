@@ -76,17 +76,57 @@ fn root_module_with_imports_tokenization() {
     expect_valid_tokenization(source, tokens);
 }
 
+#[test]
+fn aliased_imports_tokenization() {
+    let source = r#"
+    use bitwise::{ev_bitwise, MASK as BITWISE_MASK}
+    use hash as h
+
+    integrity_constraints:
+        enf h::round([a])
+    "#;
+    let tokens = vec![
+        Token::Use,
+        Token::Ident(Symbol::intern("bitwise")),
+        Token::ColonColon,
+        Token::LBrace,
+        Token::Ident(Symbol::intern("ev_bitwise")),
+        Token::Comma,
+        Token::Ident(Symbol::intern("MASK")),
+        Token::As,
+        Token::Ident(Symbol::intern("BITWISE_MASK")),
+        Token::RBrace,
+        Token::Use,
+        Token::Ident(Symbol::intern("hash")),
+        Token::As,
+        Token::Ident(Symbol::intern("h")),
+        Token::IntegrityConstraints,
+        Token::Colon,
+        Token::Enf,
+        Token::Ident(Symbol::intern("h")),
+        Token::ColonColon,
+        Token::FunctionIdent(Symbol::intern("round")),
+        Token::LParen,
+        Token::LBracket,
+        Token::Ident(Symbol::intern("a")),
+        Token::RBracket,
+        Token::RParen,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
 #[test]
 fn library_module_tokenization() {
     let source = r#"
     mod my_constraints
 
-    ev first_is_zero(state[a]):
+    pub ev first_is_zero(state[a]):
         enf a = 0
     "#;
     let tokens = vec![
         Token::Mod,
         Token::Ident(Symbol::intern("my_constraints")),
+        Token::Pub,
         Token::Ev,
         Token::FunctionIdent(Symbol::intern("first_is_zero")),
         Token::LParen,
@@ -0,0 +1,78 @@
+use super::{expect_valid_tokenization, Symbol, Token};
+
+// WHEN BLOCK VALID TOKENIZATION
+// ================================================================================================
+
+#[test]
+fn when_block_layout() {
+    let source = "
+enf when s:
+    enf a = 0
+    enf when t[0]:
+        enf b = 0
+enf c = 0";
+    let tokens = vec![
+        Token::Enf,
+        Token::When,
+        Token::Ident(Symbol::intern("s")),
+        Token::Colon,
+        Token::Indent,
+        Token::Enf,
+        Token::Ident(Symbol::intern("a")),
+        Token::Equal,
+        Token::Num(0),
+        Token::Enf,
+        Token::When,
+        Token::Ident(Symbol::intern("t")),
+        Token::LBracket,
+        Token::Num(0),
+        Token::RBracket,
+        Token::Colon,
+        Token::Indent,
+        Token::Enf,
+        Token::Ident(Symbol::intern("b")),
+        Token::Equal,
+        Token::Num(0),
+        Token::Dedent,
+        Token::Dedent,
+        Token::Enf,
+        Token::Ident(Symbol::intern("c")),
+        Token::Equal,
+        Token::Num(0),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn when_block_closed_at_end_of_input() {
+    let source = "
+enf when s:
+    enf a = 0";
+    let tokens = vec![
+        Token::Enf,
+        Token::When,
+        Token::Ident(Symbol::intern("s")),
+        Token::Colon,
+        Token::Indent,
+        Token::Enf,
+        Token::Ident(Symbol::intern("a")),
+        Token::Equal,
+        Token::Num(0),
+        Token::Dedent,
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn when_selector_does_not_open_block() {
+    let source = "enf a = 0 when s";
+    let tokens = vec![
+        Token::Enf,
+        Token::Ident(Symbol::intern("a")),
+        Token::Equal,
+        Token::Num(0),
+        Token::When,
+        Token::Ident(Symbol::intern("s")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
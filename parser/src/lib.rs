@@ -8,7 +8,7 @@ mod sema;
 pub mod symbols;
 pub mod transforms;
 
-pub use self::parser::{ParseError, Parser};
+pub use self::parser::{ParseError, Parser, ParserConfig, SEARCH_PATH_VAR};
 pub use self::sema::{LexicalScope, SemanticAnalysisError};
 pub use self::symbols::Symbol;
 
@@ -23,7 +23,7 @@ pub fn parse(
     codemap: Arc<CodeMap>,
     source: &str,
 ) -> Result<ast::Program, ParseError> {
-    let parser = Parser::new((), codemap);
+    let parser = Parser::new(ParserConfig::default(), codemap);
     match parser.parse_string::<ast::Program, _, _>(diagnostics, source) {
         Ok(ast) => Ok(ast),
         Err(ParseError::Lexer(err)) => {
@@ -40,7 +40,18 @@ pub fn parse_file<P: AsRef<Path>>(
     codemap: Arc<CodeMap>,
     source: P,
 ) -> Result<ast::Program, ParseError> {
-    let parser = Parser::new((), codemap);
+    parse_file_with_config(diagnostics, codemap, source, ParserConfig::default())
+}
+
+/// Parses the provided source and returns the AST, using the given [ParserConfig], e.g. to
+/// search additional directories for imported modules.
+pub fn parse_file_with_config<P: AsRef<Path>>(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: P,
+    config: ParserConfig,
+) -> Result<ast::Program, ParseError> {
+    let parser = Parser::new(config, codemap);
     match parser.parse_file::<ast::Program, _, _>(diagnostics, source) {
         Ok(ast) => Ok(ast),
         Err(ParseError::Lexer(err)) => {
@@ -79,7 +90,7 @@ pub(crate) fn parse_module_from_file<P: AsRef<Path>>(
     codemap: Arc<CodeMap>,
     path: P,
 ) -> Result<ast::Module, ParseError> {
-    let parser = Parser::new((), codemap);
+    let parser = Parser::new(ParserConfig::default(), codemap);
     match parser.parse_file::<ast::Module, _, _>(diagnostics, path) {
         ok @ Ok(_) => ok,
         Err(ParseError::Lexer(err)) => {
@@ -98,7 +109,7 @@ pub(crate) fn parse_module(
     codemap: Arc<CodeMap>,
     source: Arc<miden_diagnostics::SourceFile>,
 ) -> Result<ast::Module, ParseError> {
-    let parser = Parser::new((), codemap);
+    let parser = Parser::new(ParserConfig::default(), codemap);
     match parser.parse::<ast::Module, _>(diagnostics, source) {
         ok @ Ok(_) => ok,
         Err(ParseError::Lexer(err)) => {
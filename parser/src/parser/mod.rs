@@ -14,7 +14,10 @@ lalrpop_mod!(
     "/parser/grammar.rs"
 );
 
-use std::sync::Arc;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use miden_diagnostics::{
     CodeMap, Diagnostic, DiagnosticsHandler, Label, SourceIndex, SourceSpan, ToDiagnostic,
@@ -27,7 +30,38 @@ use crate::{
     sema,
 };
 
-pub type Parser = miden_parsing::Parser<()>;
+pub type Parser = miden_parsing::Parser<ParserConfig>;
+
+/// The name of the environment variable from which additional search paths for imported modules
+/// are read, see [ParserConfig::with_env_search_paths]
+pub const SEARCH_PATH_VAR: &str = "AIRSCRIPT_PATH";
+
+/// Configuration for the AirScript parser
+#[derive(Debug, Default, Clone)]
+pub struct ParserConfig {
+    /// Directories in which to search for imported modules, in order of precedence.
+    ///
+    /// A module is always looked up relative to the module importing it first, and only then in
+    /// each of these directories. A module path such as `chiplets::hasher` is resolved to the file
+    /// `chiplets/hasher.air` relative to each of these locations.
+    pub search_paths: Vec<PathBuf>,
+}
+impl ParserConfig {
+    /// Appends the given directory to the list of search paths
+    pub fn with_search_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.search_paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Appends the directories listed in the `AIRSCRIPT_PATH` environment variable, if set, to the
+    /// list of search paths. The directories are separated as in the platform's `PATH` variable.
+    pub fn with_env_search_paths(mut self) -> Self {
+        if let Some(paths) = std::env::var_os(SEARCH_PATH_VAR) {
+            self.search_paths.extend(std::env::split_paths(&paths));
+        }
+        self
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -178,7 +212,7 @@ impl ToDiagnostic for ParseError {
 impl miden_parsing::Parse for ast::Source {
     type Parser = grammar::SourceParser;
     type Error = ParseError;
-    type Config = ();
+    type Config = ParserConfig;
     type Token = Lexed;
 
     fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
@@ -195,7 +229,15 @@ impl miden_parsing::Parse for ast::Source {
     {
         let scanner = Scanner::new(source);
         let lexer = Lexer::new(scanner);
-        Self::parse_tokens(diagnostics, parser.codemap.clone(), lexer)
+        let mut next_var = 0;
+        let result = Self::Parser::new().parse(
+            diagnostics,
+            &parser.codemap,
+            &parser.config,
+            &mut next_var,
+            lexer,
+        );
+        finish(diagnostics, result)
     }
 
     fn parse_tokens<S: IntoIterator<Item = Lexed>>(
@@ -203,25 +245,18 @@ impl miden_parsing::Parse for ast::Source {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
+        let config = ParserConfig::default();
         let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
-                }
-                Ok(ast)
-            }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
-        }
+        let result =
+            Self::Parser::new().parse(diagnostics, &codemap, &config, &mut next_var, tokens);
+        finish(diagnostics, result)
     }
 }
 
 impl miden_parsing::Parse for ast::Program {
     type Parser = grammar::ProgramParser;
     type Error = ParseError;
-    type Config = ();
+    type Config = ParserConfig;
     type Token = Lexed;
 
     fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
@@ -238,7 +273,15 @@ impl miden_parsing::Parse for ast::Program {
     {
         let scanner = Scanner::new(source);
         let lexer = Lexer::new(scanner);
-        Self::parse_tokens(diagnostics, parser.codemap.clone(), lexer)
+        let mut next_var = 0;
+        let result = Self::Parser::new().parse(
+            diagnostics,
+            &parser.codemap,
+            &parser.config,
+            &mut next_var,
+            lexer,
+        );
+        finish(diagnostics, result)
     }
 
     fn parse_tokens<S: IntoIterator<Item = Lexed>>(
@@ -246,25 +289,18 @@ impl miden_parsing::Parse for ast::Program {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
+        let config = ParserConfig::default();
         let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
-                }
-                Ok(ast)
-            }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
-        }
+        let result =
+            Self::Parser::new().parse(diagnostics, &codemap, &config, &mut next_var, tokens);
+        finish(diagnostics, result)
     }
 }
 
 impl miden_parsing::Parse for ast::Module {
     type Parser = grammar::AnyModuleParser;
     type Error = ParseError;
-    type Config = ();
+    type Config = ParserConfig;
     type Token = Lexed;
 
     fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
@@ -281,7 +317,15 @@ impl miden_parsing::Parse for ast::Module {
     {
         let scanner = Scanner::new(source);
         let lexer = Lexer::new(scanner);
-        Self::parse_tokens(diagnostics, parser.codemap.clone(), lexer)
+        let mut next_var = 0;
+        let result = Self::Parser::new().parse(
+            diagnostics,
+            &parser.codemap,
+            &parser.config,
+            &mut next_var,
+            lexer,
+        );
+        finish(diagnostics, result)
     }
 
     fn parse_tokens<S: IntoIterator<Item = Lexed>>(
@@ -289,18 +333,28 @@ impl miden_parsing::Parse for ast::Module {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
+        let config = ParserConfig::default();
         let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
-                }
-                Ok(ast)
+        let result =
+            Self::Parser::new().parse(diagnostics, &codemap, &config, &mut next_var, tokens);
+        finish(diagnostics, result)
+    }
+}
+
+/// Converts the result of one of the generated parsers into the result of parsing
+fn finish<T>(
+    diagnostics: &DiagnosticsHandler,
+    result: Result<T, lalrpop_util::ParseError<SourceIndex, Token, ParseError>>,
+) -> Result<T, ParseError> {
+    match result {
+        Ok(ast) => {
+            if diagnostics.has_errors() {
+                return Err(ParseError::Failed);
             }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
+            Ok(ast)
         }
+        Err(lalrpop_util::ParseError::User { error }) => Err(error),
+        Err(err) => Err(err.into()),
     }
 }
 
@@ -356,3 +356,112 @@ fn multi_arithmetic_ops_different_precedence_w_parens() {
     );
     ParseTest::new().expect_module_ast(source, expected);
 }
+
+#[test]
+fn unary_negation() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf -clk' = -1 * clk";
+
+    // Negation is desugared to subtraction from zero, and binds more tightly than multiplication
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                sub!(int!(0), access!(clk, 1)),
+                mul!(sub!(int!(0), int!(1)), access!(clk))
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn unary_negation_in_binary_expr() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf clk' - -clk = 0";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                sub!(access!(clk, 1), sub!(int!(0), access!(clk))),
+                int!(0)
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn unary_negation_of_exponentiation() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf clk' = -clk^2 * -clk";
+
+    // Exponentiation binds more tightly than negation, which binds more tightly than multiplication
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                access!(clk, 1),
+                mul!(
+                    sub!(int!(0), exp!(access!(clk), int!(2))),
+                    sub!(int!(0), access!(clk))
+                )
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn division_and_inversion() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf clk' = clk * 1 / 2 + inv(3)";
+
+    // Division has the same precedence as multiplication
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                access!(clk, 1),
+                add!(
+                    div!(mul!(access!(clk), int!(1)), int!(2)),
+                    call!(inv(expr!(int!(3))))
+                )
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
@@ -0,0 +1,177 @@
+use air_pass::Pass;
+use miden_diagnostics::SourceSpan;
+
+use crate::{ast::*, transforms::ConstantPropagation, Symbol};
+
+use super::ParseTest;
+
+// ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn assertions() {
+    let source = r#"
+    mod test
+
+    const NUM_LIMBS = 4
+    const LIMB_BITS = 16
+
+    assert NUM_LIMBS * LIMB_BITS == 64 : "limb layout must cover 64 bits"
+    assert LIMB_BITS <= 32 : "limbs must fit in a field element""#;
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .constants
+        .insert(ident!(NUM_LIMBS), constant!(NUM_LIMBS = 4));
+    expected
+        .constants
+        .insert(ident!(LIMB_BITS), constant!(LIMB_BITS = 16));
+    expected.assertions.push(Assertion::new(
+        SourceSpan::UNKNOWN,
+        Comparison::new(
+            SourceSpan::UNKNOWN,
+            ComparisonOp::Eq,
+            mul!(access!(NUM_LIMBS), access!(LIMB_BITS)),
+            int!(64),
+        ),
+        Symbol::intern("limb layout must cover 64 bits"),
+    ));
+    expected.assertions.push(Assertion::new(
+        SourceSpan::UNKNOWN,
+        Comparison::new(
+            SourceSpan::UNKNOWN,
+            ComparisonOp::Lte,
+            access!(LIMB_BITS),
+            int!(32),
+        ),
+        Symbol::intern("limbs must fit in a field element"),
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn assertions_in_library() {
+    let root = r#"
+    def root
+
+    use lib::NUM_LIMBS
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = NUM_LIMBS
+
+    integrity_constraints:
+        enf a' = a"#;
+    let lib = r#"
+    mod lib
+
+    pub const NUM_LIMBS = 4
+    const LIMB_BITS = 16
+
+    assert NUM_LIMBS * LIMB_BITS == 64 : "limb layout must cover 64 bits""#;
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("lib.air");
+    test.add_virtual_file(path, lib.to_string());
+
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    // The constants referenced by the assertion are part of the program, even though they are
+    // not referenced from the root module
+    assert_eq!(program.assertions.len(), 1);
+    assert!(program.constants.contains_key(&ident!(lib, LIMB_BITS)));
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_ok());
+}
+
+#[test]
+fn err_assertion_failed() {
+    let root = r#"
+    def root
+
+    use lib::NUM_LIMBS
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = NUM_LIMBS
+
+    integrity_constraints:
+        enf a' = a"#;
+    let lib = r#"
+    mod lib
+
+    pub const NUM_LIMBS = 4
+    const LIMB_BITS = 8
+
+    assert NUM_LIMBS * LIMB_BITS == 64 : "limb layout must cover 64 bits""#;
+
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("lib.air");
+    test.add_virtual_file(path, lib.to_string());
+
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_err());
+    let diagnostics = test.captured_diagnostics();
+    assert!(diagnostics.contains("assertion failed"));
+    assert!(diagnostics.contains("limb layout must cover 64 bits"));
+}
+
+#[test]
+fn err_assertion_not_constant() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    assert a == 1 : "a must be one"
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a"#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(source) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_err());
+    assert!(test
+        .captured_diagnostics()
+        .contains("this condition is not known at compile-time"));
+}
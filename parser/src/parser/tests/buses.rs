@@ -0,0 +1,220 @@
+use miden_diagnostics::{SourceSpan, Span};
+
+use crate::ast::*;
+
+use super::ParseTest;
+
+// BUSES
+// ================================================================================================
+
+#[test]
+fn buses() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s, a, b]
+        aux: [c]
+
+    random_values:
+        rand: [3]
+
+    public_inputs:
+        inputs: [2]
+
+    buses:
+        p: multiset
+        q: multiset
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        p.send(a, b) when s
+        q.receive(a)";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(s, 1), (a, 1), (b, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(c, 1)]));
+    expected.random_values.insert(1, random_values!("$rand", 3));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.buses.insert(
+        ident!(p),
+        Bus::new(SourceSpan::UNKNOWN, ident!(p), BusKind::Multiset),
+    );
+    expected.buses.insert(
+        ident!(q),
+        Bus::new(SourceSpan::UNKNOWN, ident!(q), BusKind::Multiset),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            Statement::BusInteraction(BusInteraction::new(
+                SourceSpan::UNKNOWN,
+                ident!(p),
+                BusOp::Send,
+                vec![access!(a), access!(b)],
+                Some(access!(s)),
+            )),
+            Statement::BusInteraction(BusInteraction::new(
+                SourceSpan::UNKNOWN,
+                ident!(q),
+                BusOp::Receive,
+                vec![access!(a)],
+                None,
+            )),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_unknown_bus_type() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+        aux: [c]
+
+    random_values:
+        rand: [2]
+
+    public_inputs:
+        inputs: [2]
+
+    buses:
+        p: logup
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        p.send(a)";
+
+    ParseTest::new().expect_module_diagnostic(source, "unknown bus type, expected 'multiset'");
+}
+
+#[test]
+fn err_invalid_bus_operation() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+        aux: [c]
+
+    random_values:
+        rand: [2]
+
+    public_inputs:
+        inputs: [2]
+
+    buses:
+        p: multiset
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        p.push(a)";
+
+    ParseTest::new().expect_module_diagnostic(source, "expected either 'send' or 'receive'");
+}
+
+#[test]
+fn err_buses_without_random_values() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+        aux: [c]
+
+    public_inputs:
+        inputs: [2]
+
+    buses:
+        p: multiset
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        p.send(a)";
+
+    ParseTest::new().expect_module_diagnostic(
+        source,
+        "declaring buses requires a random_values declaration",
+    );
+}
+
+#[test]
+fn err_undefined_bus() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+        aux: [c]
+
+    random_values:
+        rand: [2]
+
+    public_inputs:
+        inputs: [2]
+
+    buses:
+        p: multiset
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        q.send(a)";
+
+    ParseTest::new()
+        .expect_program_diagnostic(source, "this bus is not declared in the buses section");
+}
+
+#[test]
+fn err_bus_interaction_in_boundary_constraints() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+        aux: [c]
+
+    random_values:
+        rand: [2]
+
+    public_inputs:
+        inputs: [2]
+
+    buses:
+        p: multiset
+
+    boundary_constraints:
+        p.send(a)
+
+    integrity_constraints:
+        enf a = 0";
+
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "bus interactions are only permitted in integrity constraints",
+    );
+}
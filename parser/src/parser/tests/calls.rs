@@ -88,3 +88,30 @@ fn call_fold_list_comprehension() {
 
     ParseTest::new().expect_module_ast(source, expected);
 }
+
+#[test]
+fn call_fold_lambda() {
+    let source = "
+    mod test
+
+    ev test([a, c[4]]):
+        let x = fold(c, 1, |acc, col| acc * 2 + col)
+        enf a = x";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    let body = vec![
+        let_!(x = expr!(call!(fold(expr!(access!(c)), expr!(int!(1)), lambda!(|acc, col| add!(mul!(access!(acc), int!(2)), access!(col)))))) =>
+                  enforce!(eq!(access!(a), access!(x)))),
+    ];
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(a, 1), (c, 4)])],
+            body,
+        ),
+    );
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
@@ -0,0 +1,337 @@
+use air_pass::Pass;
+use miden_diagnostics::{SourceSpan, Span};
+
+use crate::{ast::*, transforms::ConstantPropagation};
+
+use super::ParseTest;
+
+// CONDITIONALS
+// ================================================================================================
+
+#[test]
+fn conditional_in_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [if i == 0 then col else col^2 for (col, i) in (c, 0..4)]
+        enf a = x[0] + x[1]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((col, expr!(access!(c))), (i, range!(0..4))) => if_!(access!(i), Eq, int!(0) => access!(col), exp!(access!(col), int!(2)))).into() =>
+              enforce!(eq!(access!(a), add!(access!(x[0]), access!(x[1]))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn conditional_comparison_ops() {
+    let source = "
+    def test
+
+    const N = 4
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a = if N != 4 then 1 else if N < 4 then 2 else if N <= 3 then 3 else if N > 5 then 4 else if N >= 4 then 5 else 6";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.constants.insert(ident!(N), constant!(N = 4));
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    let body = if_!(access!(N), Gte, int!(4) => int!(5), int!(6));
+    let body = if_!(access!(N), Gt, int!(5) => int!(4), body);
+    let body = if_!(access!(N), Lte, int!(3) => int!(3), body);
+    let body = if_!(access!(N), Lt, int!(4) => int!(2), body);
+    let body = if_!(access!(N), Neq, int!(4) => int!(1), body);
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(a), body))],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+// `<` immediately following an identifier is a comparison, unless the identifier names an
+// evaluator being defined, e.g. `ev foo<N>(...)`
+#[test]
+fn conditional_unspaced_comparison_ops() {
+    let source = "
+    def test
+
+    const N = 4
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [if i<2 then col else 0 for (col, i) in (c, 0..4)]
+        enf a = x[0] + x[1]
+        enf b = if N<4 then 1 else if N<=3 then 2 else 3";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.constants.insert(ident!(N), constant!(N = 4));
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    let body = if_!(access!(N), Lte, int!(3) => int!(2), int!(3));
+    let body = if_!(access!(N), Lt, int!(4) => int!(1), body);
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((col, expr!(access!(c))), (i, range!(0..4))) => if_!(access!(i), Lt, int!(2) => access!(col), int!(0))).into() =>
+              enforce!(eq!(access!(a), add!(access!(x[0]), access!(x[1])))),
+              enforce!(eq!(access!(b), body))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn conditional_constant_propagation() {
+    let source = "
+    def test
+
+    const N = 4
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [2]
+
+    periodic_columns:
+        k: [if i == 0 then 1 else 0 for i in 0..4]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = if N > 2 then a else b
+        enf a' = x * k";
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(source) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let k = program.periodic_columns.values().next().unwrap();
+    assert_eq!(k.values, vec![1, 0, 0, 0]);
+    match &program.integrity_constraints[0] {
+        Statement::Let(expr) => assert_matches!(
+            &expr.value,
+            Expr::SymbolAccess(access) if access.name == ResolvableIdentifier::Local(ident!(a))
+        ),
+        invalid => panic!("expected let, got {:#?}", invalid),
+    }
+}
+
+// Constants may be defined by a conditional expression over other constants, including those
+// which are themselves defined by one
+#[test]
+fn conditional_constant_definition() {
+    let source = "
+    def test
+
+    const A = if K > 8 then 1 else N * 2
+    const K = if N == 4 then 7 else 9
+    const N = 4
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a * K + A";
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(source) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    assert_eq!(program.constants[&ident!(test, K)], constant!(K = 7));
+    assert_eq!(program.constants[&ident!(test, A)], constant!(A = 8));
+    assert_eq!(
+        program.integrity_constraints[0],
+        enforce!(eq!(
+            access!(a, 1, Type::Felt),
+            add!(mul!(access!(a, Type::Felt), int!(7)), int!(8))
+        ))
+    );
+}
+
+#[test]
+fn err_conditional_constant_definition_cycle() {
+    let source = "
+    def test
+
+    const K = if K == 4 then 7 else 9
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a * K";
+
+    let test = ParseTest::new();
+    let program = test.parse_program(source).unwrap();
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_err());
+    assert!(test
+        .captured_diagnostics()
+        .contains("the value of a constant must be known at compile time"));
+}
+
+#[test]
+fn err_conditional_constant_definition_non_constant() {
+    let source = "
+    def test
+
+    const K = if a == 4 then 7 else 9
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a * K";
+
+    let test = ParseTest::new();
+    let program = test.parse_program(source).unwrap();
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_err());
+    assert!(test
+        .captured_diagnostics()
+        .contains("the value of a constant must be known at compile time"));
+}
+
+#[test]
+fn err_conditional_missing_else() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a = if 1 == 1 then 1
+        enf a' = a";
+
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
+#[test]
+fn err_conditional_branch_type_mismatch() {
+    let source = "
+    def test
+
+    const A = [1, 2]
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [if i == 0 then A else 1 for i in 0..2]
+        enf a = x[0]";
+
+    ParseTest::new().expect_program_diagnostic(source, "type mismatch");
+}
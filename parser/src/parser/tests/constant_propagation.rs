@@ -37,9 +37,9 @@ fn test_constant_propagation() {
     let lib = r#"
     mod lib
 
-    const EXP = 2
+    pub const EXP = 2
 
-    ev test_constraint([b0, b1]):
+    pub ev test_constraint([b0, b1]):
         let x = EXP
         let y = 2^x
         enf b0 + x = b1 + y
@@ -76,9 +76,10 @@ fn test_constant_propagation() {
     expected
         .constants
         .insert(ident!(root, B), constant!(B = [[1, 1], [2, 2]]));
-    expected
-        .constants
-        .insert(ident!(lib, EXP), constant!(EXP = 2));
+    expected.constants.insert(
+        ident!(lib, EXP),
+        constant!(EXP = 2).with_visibility(Visibility::Public),
+    );
     // When constant propagation is done, the boundary constraints should look like:
     //     enf a.first = 1
     expected.boundary_constraints.push(enforce!(eq!(
@@ -111,8 +112,126 @@ fn test_constant_propagation() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(b0, 1), (b1, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
+    );
+
+    assert_eq!(program, expected);
+}
+
+#[test]
+fn test_constant_propagation_negation() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        inputs: [0]
+
+    const A = 2
+
+    integrity_constraints:
+        enf a = clk * -A + -(A - 1)
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (a, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
     );
+    expected.constants.insert(ident!(root, A), constant!(A = 2));
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(0)
+    )));
+    // Negated constants are folded to their field representation, i.e. `p - n`:
+    //     enf a = clk * (p - 2) + (p - 1)
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(a, Type::Felt),
+        add!(
+            mul!(access!(clk, Type::Felt), int!(18446744069414584319)),
+            int!(18446744069414584320)
+        )
+    )));
+
+    assert_eq!(program, expected);
+}
+
+#[test]
+fn test_constant_propagation_division() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        inputs: [0]
+
+    const A = 6
+
+    integrity_constraints:
+        let half = inv(2)
+        enf a = clk * half + A / 3
+
+    boundary_constraints:
+        enf a.first = 1 / 2
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let mut expected = Program::new(ident!(root));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (a, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 0),
+    );
+    expected.constants.insert(ident!(root, A), constant!(A = 6));
+    // Division is evaluated in the field, i.e. `1 / 2` is the inverse of 2, `(p + 1) / 2`
+    expected.boundary_constraints.push(enforce!(eq!(
+        bounded_access!(a, Boundary::First, Type::Felt),
+        int!(9223372034707292161)
+    )));
+    //     enf a = clk * 9223372034707292161 + 2
+    expected.integrity_constraints.push(enforce!(eq!(
+        access!(a, Type::Felt),
+        add!(
+            mul!(access!(clk, Type::Felt), int!(9223372034707292161)),
+            int!(2)
+        )
+    )));
 
     assert_eq!(program, expected);
 }
@@ -225,6 +225,66 @@ fn ev_fn_call_with_more_than_two_args() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn ev_fn_with_value_params() {
+    let source = "
+    mod test
+
+    ev multiset_step([p], [a, b], alpha, betas[2]):
+        enf p' = p * (alpha + a + betas[0] * b)";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(multiset_step),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(multiset_step),
+            vec![
+                trace_segment!(0, "%0", [(p, 1)]),
+                trace_segment!(1, "%1", [(a, 1), (b, 1)]),
+            ],
+            vec![enforce!(eq!(
+                access!(p, 1),
+                mul!(
+                    access!(p),
+                    add!(
+                        add!(access!(alpha), access!(a)),
+                        mul!(access!(betas[0]), access!(b))
+                    )
+                )
+            ))],
+        )
+        .with_value_params(vec![
+            ValueParam::new(SourceSpan::UNKNOWN, ident!(alpha), Type::Felt),
+            ValueParam::new(SourceSpan::UNKNOWN, ident!(betas), Type::Vector(2)),
+        ]),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn ev_fn_with_const_params() {
+    let source = "
+    mod test
+
+    ev limbs<N>([x[N], y]):
+        enf y = x[0]";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(limbs),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(limbs),
+            vec![trace_segment!(0, "%0", [(x, 0), (y, 1)])
+                .with_size_params(vec![Some(ident!(N)), None])],
+            vec![enforce!(eq!(access!(y), access!(x[0])))],
+        )
+        .with_const_params(vec![ident!(N)]),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 // INVALID USE OF EVALUATOR FUNCTIONS
 // ================================================================================================
 
@@ -276,4 +336,226 @@ fn ev_fn_with_invalid_params() {
     ev advance_clock([clk],):
         enf clk' = clk + 1";
     ParseTest::new().expect_unrecognized_token(source);
+
+    // Value parameters must follow the trace segments
+    let source = "
+    mod test
+    ev advance_clock(alpha, [clk]):
+        enf clk' = clk + alpha";
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
+#[test]
+fn err_ev_fn_duplicate_value_param() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    ev advance_clock([clk], k, k):
+        enf clk' = clk + k
+
+    integrity_constraints:
+        enf advance_clock([clk], 1, 1)
+
+    boundary_constraints:
+        enf clk.first = 0";
+    ParseTest::new().expect_program_diagnostic(source, "this parameter name is already in use");
+}
+
+#[test]
+fn err_ev_fn_call_wrong_number_of_args() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    ev advance_clock([clk], alpha):
+        enf clk' = clk + alpha
+
+    integrity_constraints:
+        enf advance_clock([clk])
+
+    boundary_constraints:
+        enf clk.first = 0";
+    ParseTest::new().expect_program_diagnostic(source, "the callee expects 2 arguments, but got 1");
+}
+
+#[test]
+fn err_ev_fn_call_value_arg_type_mismatch() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    ev advance_clock([clk], alpha):
+        enf clk' = clk + alpha
+
+    integrity_constraints:
+        enf advance_clock([clk], $rand)
+
+    boundary_constraints:
+        enf clk.first = 0";
+    ParseTest::new()
+        .expect_program_diagnostic(source, "this expression has type vector of length 2");
+}
+
+#[test]
+fn err_ev_fn_call_value_arg_local_variable() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    ev advance_clock([clk], k):
+        enf clk' = clk + k
+
+    integrity_constraints:
+        let x = clk * clk
+        enf advance_clock([clk], x)
+
+    boundary_constraints:
+        enf clk.first = 0";
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "evaluator arguments cannot reference local variables",
+    );
+}
+
+#[test]
+fn err_ev_fn_const_param_not_inferrable() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    ev advance<N>([x]):
+        enf x' = x + N
+
+    integrity_constraints:
+        enf advance([a])
+
+    boundary_constraints:
+        enf a.first = 0";
+    ParseTest::new()
+        .expect_program_diagnostic(source, "the value of this parameter cannot be inferred");
+}
+
+#[test]
+fn err_ev_fn_range_bound_not_const_param() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    ev advance<N>([x[N]]):
+        enf xi' = xi + i for (xi, i) in (x, 0..M)
+
+    integrity_constraints:
+        enf advance([a, b])
+
+    boundary_constraints:
+        enf a.first = 0";
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "this is not a const parameter of the enclosing evaluator",
+    );
+}
+
+#[test]
+fn err_ev_fn_call_const_param_not_inferrable() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    ev advance<N>([x[N], y[N]]):
+        enf xi' = yi for (xi, yi) in (x, y)
+
+    integrity_constraints:
+        enf advance([a, b, c])
+
+    boundary_constraints:
+        enf a.first = 0";
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "unable to infer a value for the const parameter 'N' from these arguments",
+    );
+}
+
+#[test]
+fn err_ev_fn_call_const_param_width_mismatch() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    ev advance<N>([x[N]], [y[N]]):
+        enf y[0]' = y[0] + x[0]
+
+    integrity_constraints:
+        enf advance([a, b], [p])
+
+    boundary_constraints:
+        enf a.first = 0";
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "callee expects 2 trace columns here, but this argument only provides 1",
+    );
 }
@@ -50,9 +50,9 @@ fn test_inlining_with_evaluator_split_input_binding() {
     let lib = r#"
     mod lib
 
-    const EXP = 2
+    pub const EXP = 2
 
-    ev test_constraint([b0, b1]):
+    pub ev test_constraint([b0, b1]):
         let x = EXP
         let y = 2^x
         enf b0 + x = b1 + y
@@ -90,9 +90,10 @@ fn test_inlining_with_evaluator_split_input_binding() {
     expected
         .constants
         .insert(ident!(root, B), constant!(B = [[1, 1], [2, 2]]));
-    expected
-        .constants
-        .insert(ident!(lib, EXP), constant!(EXP = 2));
+    expected.constants.insert(
+        ident!(lib, EXP),
+        constant!(EXP = 2).with_visibility(Visibility::Public),
+    );
     // When constant propagation and inlining is done, the boundary constraints should look like:
     //     enf a.first = 1
     expected.boundary_constraints.push(enforce!(eq!(
@@ -123,7 +124,8 @@ fn test_inlining_with_evaluator_split_input_binding() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(b0, 1), (b1, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -164,7 +166,7 @@ fn test_inlining_with_vector_literal_binding_regrouped() {
     let lib = r#"
     mod lib
 
-    ev test_constraint([pair[2], b1]):
+    pub ev test_constraint([pair[2], b1]):
         enf pair[0] + pair[1] = b1
     "#;
 
@@ -218,7 +220,8 @@ fn test_inlining_with_vector_literal_binding_regrouped() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(pair, 2), (b1, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -250,7 +253,7 @@ fn test_inlining_with_vector_literal_binding_unordered() {
     let lib = r#"
     mod lib
 
-    ev test_constraint([b0, pair[2]]):
+    pub ev test_constraint([b0, pair[2]]):
         enf pair[1] + b0 = pair[0]
     "#;
 
@@ -304,7 +307,8 @@ fn test_inlining_with_vector_literal_binding_unordered() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(b0, 1), (pair, 2)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -336,7 +340,7 @@ fn test_inlining_with_vector_literal_binding_different_arity_many_to_few() {
     let lib = r#"
     mod lib
 
-    ev test_constraint([pair[3], foo]):
+    pub ev test_constraint([pair[3], foo]):
         enf pair[0] + pair[1] = foo + pair[2]
     "#;
 
@@ -390,7 +394,8 @@ fn test_inlining_with_vector_literal_binding_different_arity_many_to_few() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(pair, 3), (foo, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -422,7 +427,7 @@ fn test_inlining_with_vector_literal_binding_different_arity_few_to_many() {
     let lib = r#"
     mod lib
 
-    ev test_constraint([x, y, z]):
+    pub ev test_constraint([x, y, z]):
         enf x + y = z
     "#;
 
@@ -476,7 +481,8 @@ fn test_inlining_with_vector_literal_binding_different_arity_few_to_many() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(x, 1), (y, 1), (z, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -509,13 +515,13 @@ fn test_inlining_across_modules_with_nested_evaluators_variant1() {
 
     use lib2::*
 
-    ev test_constraint([tuple[3], z]):
+    pub ev test_constraint([tuple[3], z]):
         enf helper_constraint([z, tuple[1..3]])
     "#;
     let lib2 = r#"
     mod lib2
 
-    ev helper_constraint([x[2], y]):
+    pub ev helper_constraint([x[2], y]):
         enf x[0] + x[1] = y
     "#;
 
@@ -571,7 +577,8 @@ fn test_inlining_across_modules_with_nested_evaluators_variant1() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(tuple, 3), (z, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
     // The helper_constraint function before inlining should look like:
     //     enf x[0] + x[1] = y
@@ -586,7 +593,8 @@ fn test_inlining_across_modules_with_nested_evaluators_variant1() {
             ident!(helper_constraint),
             vec![trace_segment!(0, "%0", [(x, 2), (y, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -618,13 +626,13 @@ fn test_inlining_across_modules_with_nested_evaluators_variant2() {
 
     use lib2::*
 
-    ev test_constraint([tuple[3], z]):
+    pub ev test_constraint([tuple[3], z]):
         enf helper_constraint([z, tuple[1], tuple[2..3]])
     "#;
     let lib2 = r#"
     mod lib2
 
-    ev helper_constraint([x[2], y]):
+    pub ev helper_constraint([x[2], y]):
         enf x[0] + x[1] = y
     "#;
 
@@ -681,7 +689,8 @@ fn test_inlining_across_modules_with_nested_evaluators_variant2() {
             ident!(test_constraint),
             vec![trace_segment!(0, "%0", [(tuple, 3), (z, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
     // The helper_constraint function before inlining should look like:
     //     enf x[0] + x[1] = y
@@ -696,7 +705,8 @@ fn test_inlining_across_modules_with_nested_evaluators_variant2() {
             ident!(helper_constraint),
             vec![trace_segment!(0, "%0", [(x, 2), (y, 1)])],
             body,
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
 
     assert_eq!(program, expected);
@@ -1113,3 +1123,267 @@ fn test_inlining_constraints_with_folded_comprehensions_in_evaluator() {
 
     assert_eq!(program, expected);
 }
+
+/// This test verifies that arguments for the value parameters of an evaluator are substituted
+/// in its body when inlined, including when passed on to another evaluator
+#[test]
+fn test_inlining_with_value_params() {
+    let root = r#"
+    def root
+
+    const K = 3
+
+    trace_columns:
+        main: [a, b]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [alpha, beta[2]]
+
+    ev multiset_step([a, b], [p], alpha, betas[2], k):
+        enf p' = p * (alpha + a + betas[0] * b)
+        enf scaled([a], betas[1] * k)
+
+    ev scaled([x], c):
+        enf x' = x * c
+
+    integrity_constraints:
+        enf multiset_step([a, b], [p], alpha, beta, K)
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline =
+        ConstantPropagation::new(&test.diagnostics).chain(Inlining::new(&test.diagnostics));
+    let program = pipeline.run(program).unwrap();
+
+    // Random values are referenced directly in the inlined constraints
+    let random_value = |name: &str, access_type| {
+        ScalarExpr::SymbolAccess(SymbolAccess {
+            span: SourceSpan::UNKNOWN,
+            name: ResolvableIdentifier::Global(Identifier::new(
+                SourceSpan::UNKNOWN,
+                crate::Symbol::intern(name),
+            )),
+            access_type,
+            offset: 0,
+            ty: Some(Type::Felt),
+        })
+    };
+    // When constant propagation and inlining is done, integrity_constraints should look like:
+    //     enf p' = p * (alpha + a + beta[0] * b)
+    //     enf a' = a * (beta[1] * 3)
+    let expected = vec![
+        enforce!(eq!(
+            access!(p, 1, Type::Felt),
+            mul!(
+                access!(p, Type::Felt),
+                add!(
+                    add!(
+                        random_value("alpha", AccessType::Default),
+                        access!(a, Type::Felt)
+                    ),
+                    mul!(
+                        random_value("beta", AccessType::Index(0)),
+                        access!(b, Type::Felt)
+                    )
+                )
+            )
+        )),
+        enforce!(eq!(
+            access!(a, 1, Type::Felt),
+            mul!(
+                access!(a, Type::Felt),
+                mul!(random_value("beta", AccessType::Index(1)), int!(3))
+            )
+        )),
+    ];
+
+    assert_eq!(program.integrity_constraints, expected);
+}
+
+#[test]
+fn test_inlining_generic_evaluator() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    ev shift<N>([x[N]]):
+        enf xi' = xi + i * N for (xi, i) in (x, 0..N)
+
+    ev shift_all<M>([y[M]]):
+        enf shift([y])
+
+    integrity_constraints:
+        enf shift([a, b])
+        enf shift_all([c])
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline =
+        ConstantPropagation::new(&test.diagnostics).chain(Inlining::new(&test.diagnostics));
+    let program = pipeline.run(program).unwrap();
+
+    // The evaluator is instantiated with N = 2 for the first call, and N = 1 for the second,
+    // so when constant propagation and inlining is done, integrity_constraints should look like:
+    //     enf a' = a + 0
+    //     enf b' = b + 2
+    //     enf c' = c + 0
+    let expected = vec![
+        enforce!(eq!(
+            access!(a, 1, Type::Felt),
+            add!(access!(a, Type::Felt), int!(0))
+        )),
+        enforce!(eq!(
+            access!(b, 1, Type::Felt),
+            add!(access!(b, Type::Felt), int!(2))
+        )),
+        enforce!(eq!(
+            access!(c, 1, Type::Felt),
+            add!(access!(c, Type::Felt), int!(0))
+        )),
+    ];
+
+    assert_eq!(program.integrity_constraints, expected);
+}
+
+/// This test verifies that comprehensions over the rows of a matrix, nested comprehensions, stepped
+/// ranges, and `enumerate` are all unrolled correctly
+#[test]
+fn test_inlining_comprehensions_over_matrix_rows() {
+    let root = r#"
+    def root
+
+    const RC = [[1, 2], [3, 4]]
+
+    trace_columns:
+        main: [a[2], b]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf x' = x + row[1] for (x, row) in (a, RC)
+        enf y = i for (i, y) in enumerate([x * k for (x, k) in (a, 0..4 by 2)])
+
+    boundary_constraints:
+        enf b.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline =
+        ConstantPropagation::new(&test.diagnostics).chain(Inlining::new(&test.diagnostics));
+    let program = pipeline.run(program).unwrap();
+
+    // When constant propagation and inlining is done, integrity_constraints should look like:
+    //     enf a[0]' = a[0] + 2
+    //     enf a[1]' = a[1] + 4
+    //     enf a[0] * 0 = 0
+    //     enf a[1] * 2 = 1
+    let expected = vec![
+        enforce!(eq!(
+            access!(a[0], 1, Type::Felt),
+            add!(access!(a[0], Type::Felt), int!(2))
+        )),
+        enforce!(eq!(
+            access!(a[1], 1, Type::Felt),
+            add!(access!(a[1], Type::Felt), int!(4))
+        )),
+        enforce!(eq!(mul!(access!(a[0], Type::Felt), int!(0)), int!(0))),
+        enforce!(eq!(mul!(access!(a[1], Type::Felt), int!(2)), int!(1))),
+    ];
+
+    assert_eq!(program.integrity_constraints, expected);
+}
+
+/// This test verifies that the value parameters of an evaluator are not substituted where they
+/// are shadowed by a local binding, e.g. the binding of a comprehension
+#[test]
+fn test_inlining_with_shadowed_value_params() {
+    let root = r#"
+    def root
+
+    trace_columns:
+        main: [a, c[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    ev step([x, y[2]], s):
+        let t = sum([s * x for s in y])
+        enf x' = t
+        enf y[0]' = s
+
+    integrity_constraints:
+        enf step([a, c], 7)
+
+    boundary_constraints:
+        enf a.first = 0
+    "#;
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pipeline =
+        ConstantPropagation::new(&test.diagnostics).chain(Inlining::new(&test.diagnostics));
+    let program = pipeline.run(program).unwrap();
+
+    // When constant propagation and inlining is done, integrity_constraints should look like:
+    //     let lc%0 = c[0] * a
+    //     let lc%1 = c[1] * a
+    //     let t = lc%0 + lc%1
+    //     enf a' = t
+    //     enf c[0]' = 7
+    let expected = vec![
+        let_!("%lc0" = expr!(mul!(access!(c[0], Type::Felt), access!(a, Type::Felt)))
+            => let_!("%lc1" = expr!(mul!(access!(c[1], Type::Felt), access!(a, Type::Felt)))
+            => let_!(t = expr!(add!(access!("%lc0", Type::Felt), access!("%lc1", Type::Felt)))
+            => enforce!(eq!(access!(a, 1, Type::Felt), access!(t, Type::Felt))),
+            enforce!(eq!(access!(c[0], 1, Type::Felt), int!(7)))))),
+    ];
+
+    assert_eq!(program.integrity_constraints, expected);
+}
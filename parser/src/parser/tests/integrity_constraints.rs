@@ -183,7 +183,7 @@ fn integrity_constraint_with_random_value() {
     expected
         .trace_columns
         .push(trace_segment!(1, "$aux", [(aux0, 2)]));
-    expected.random_values = Some(random_values!("$rand", 2));
+    expected.random_values.insert(1, random_values!("$rand", 2));
     expected.public_inputs.insert(
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
@@ -638,18 +638,78 @@ fn ic_match_constraint() {
     ));
     expected.integrity_constraints = Some(Span::new(
         SourceSpan::UNKNOWN,
-        vec![
-            enforce_all!(
-                lc!((("%1", range!(0..1))) => call!(is_binary(vector!(access!(c[0])))), when and!(access!(s[0]), access!(s[1])))
-            ),
-            enforce_all!(
-                lc!((("%2", range!(0..1))) => eq!(access!(c[1]), access!(c[2])), when access!(s[0]))
-            ),
-        ],
+        vec![enforce_match!([
+            lc!((("%1", range!(0..1))) => call!(is_binary(vector!(access!(c[0])))), when and!(access!(s[0]), access!(s[1]))),
+            lc!((("%2", range!(0..1))) => eq!(access!(c[1]), access!(c[2])), when access!(s[0]))
+        ])],
     ));
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn ic_match_constraint_with_default() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2], c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf c[0].first = 0
+
+    integrity_constraints:
+        enf match:
+            case s[0]: c[1] = c[2]
+            case s[1]: c[1] = 0
+            else: c[1] = c[3]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(s, 2), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(c[0], Boundary::First),
+            int!(0)
+        ))],
+    ));
+    // The default arm is enforced under the selector `1 - (s[0] + s[1])`
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce_match!(
+            [
+                lc!((("%0", range!(0..1))) => eq!(access!(c[1]), access!(c[2])), when access!(s[0])),
+                lc!((("%1", range!(0..1))) => eq!(access!(c[1]), int!(0)), when access!(s[1]))
+            ],
+            else lc!((("%2", range!(0..1))) => eq!(access!(c[1]), access!(c[3])), when sub!(int!(1), add!(access!(s[0]), access!(s[1]))))
+        )],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_ic_match_default_not_last() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s, c]
+
+    integrity_constraints:
+        enf match:
+            else: c' = c
+            case s: c' = 0";
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
 // INVALID INTEGRITY CONSTRAINT COMPREHENSION
 // ================================================================================================
 
@@ -426,6 +426,181 @@ fn ic_multiple_iterables_lc() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn ic_stepped_range_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [i * c for (i, c) in (0..8 by 2, c)]
+        enf a = x[0] + x[1] + x[2] + x[3]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    let range = RangeExpr::from(Span::new(SourceSpan::UNKNOWN, 0..8)).with_step(2);
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((i, Expr::Range(range)), (c, expr!(access!(c)))) => mul!(access!(i), access!(c))).into() =>
+                  enforce!(eq!(access!(a), add!(add!(add!(access!(x[0]), access!(x[1])), access!(x[2])), access!(x[3]))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn ic_enumerate_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [i * c for (i, c) in enumerate(c)]
+        enf a = x[0] + x[1] + x[2] + x[3]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    // The end of the range of indices is inferred from the other iterable during semantic analysis
+    let indices = RangeExpr::new(
+        SourceSpan::UNKNOWN,
+        RangeBound::Const(0),
+        RangeBound::Inferred,
+    );
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((i, Expr::Range(indices)), (c, expr!(access!(c)))) => mul!(access!(i), access!(c))).into() =>
+                  enforce!(eq!(access!(a), add!(add!(add!(access!(x[0]), access!(x[1])), access!(x[2])), access!(x[3]))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn ic_nested_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [y * 2 for y in [c + 1 for c in c]]
+        enf a = x[0] + x[1]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 2)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    let nested = lc!(((c, expr!(access!(c)))) => add!(access!(c), int!(1)));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((y, Expr::ListComprehension(nested))) => mul!(access!(y), int!(2))).into() =>
+                  enforce!(eq!(access!(a), add!(access!(x[0]), access!(x[1]))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn ic_matrix_rows_lc() {
+    let source = "
+    def test
+
+    const RC = [[1, 2], [3, 4]]
+
+    trace_columns:
+        main: [a, b, c[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf x = row[0] * row[1] for (x, row) in (c, RC)";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .constants
+        .insert(ident!(RC), constant!(RC = [[1, 2], [3, 4]]));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 2)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce_all!(
+            lc!(((x, expr!(access!(c))), (row, expr!(access!(RC)))) => eq!(access!(x), mul!(access!(row[0]), access!(row[1]))))
+        )],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 // INVALID LIST COMPREHENSION
 // ================================================================================================
 
@@ -492,3 +667,79 @@ fn err_ic_lc_two_members_one_iterable() {
     ParseTest::new()
         .expect_module_diagnostic(source, "bindings and iterables lengths are mismatched");
 }
+
+#[test]
+fn err_lc_range_step_zero() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    integrity_constraints:
+        let x = [i * c for (i, c) in (0..8 by 0, c)]
+        enf a = x[0]";
+
+    ParseTest::new().expect_module_diagnostic(source, "the step of a range must be non-zero");
+}
+
+#[test]
+fn err_lc_iterable_call() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    integrity_constraints:
+        let x = [i * c for (i, c) in zip(c)]
+        enf a = x[0]";
+
+    ParseTest::new().expect_module_diagnostic(source, "only 'enumerate' may be called here");
+}
+
+#[test]
+fn err_lc_scalar_iterable() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [b * 2 for b in a]
+        enf a = x[0]";
+
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "expected a vector or matrix, but this expression has type field element",
+    );
+}
+
+#[test]
+fn err_lc_mismatched_iterable_lengths() {
+    let source = "
+    def test
+
+    const RC = [[1, 2], [3, 4], [5, 6]]
+
+    trace_columns:
+        main: [a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf x = row[0] for (x, row) in (c, RC)";
+
+    ParseTest::new().expect_program_diagnostic(source, "type mismatch");
+}
@@ -0,0 +1,157 @@
+use miden_diagnostics::{SourceSpan, Span};
+
+use crate::ast::*;
+
+use super::ParseTest;
+
+// LOOKUPS
+// ================================================================================================
+
+#[test]
+fn lookup() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s, a, b, t0, t1, m]
+        aux: [c]
+
+    random_values:
+        rand: [3]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf lookup([t0, t1], [a, b], m) when s";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected.trace_columns.push(trace_segment!(
+        0,
+        "$main",
+        [(s, 1), (a, 1), (b, 1), (t0, 1), (t1, 1), (m, 1)]
+    ));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(c, 1)]));
+    expected.random_values.insert(1, random_values!("$rand", 3));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![Statement::Lookup(Lookup::new(
+            SourceSpan::UNKNOWN,
+            vec![access!(t0), access!(t1)],
+            vec![access!(a), access!(b)],
+            access!(m),
+            Some(access!(s)),
+        ))],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_lookup_tuple_size_mismatch() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, t, m]
+        aux: [c]
+
+    random_values:
+        rand: [3]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf lookup([t], [a, b], m)";
+
+    ParseTest::new().expect_module_diagnostic(source, "but the table has 1");
+}
+
+#[test]
+fn err_lookup_invalid_arguments() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, t, m]
+        aux: [c]
+
+    random_values:
+        rand: [3]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf lookup([t], [a])";
+
+    ParseTest::new().expect_module_diagnostic(
+        source,
+        "expected 3 arguments: the table tuple, the query tuple, and the multiplicity",
+    );
+}
+
+#[test]
+fn err_lookup_in_comprehension() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a[2], t, m]
+        aux: [c]
+
+    random_values:
+        rand: [3]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a[0].first = 0
+
+    integrity_constraints:
+        enf lookup([t], [x], m) for x in a";
+
+    ParseTest::new().expect_module_diagnostic(source, "lookups cannot be used in a comprehension");
+}
+
+#[test]
+fn err_lookup_without_random_values() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, t, m]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf lookup([t], [a], m)";
+
+    ParseTest::new()
+        .expect_program_diagnostic(source, "lookups require a random_values declaration");
+}
@@ -457,6 +457,20 @@ macro_rules! enforce_all {
     };
 }
 
+macro_rules! enforce_match {
+    ([$($arm:expr),+]) => {
+        Statement::EnforceMatch(Match::new(miden_diagnostics::SourceSpan::UNKNOWN, vec![$($arm),+], None))
+    };
+
+    ([$($arm:expr),+], else $default:expr) => {
+        Statement::EnforceMatch(Match::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            vec![$($arm),+],
+            Some($default),
+        ))
+    };
+}
+
 macro_rules! lc {
     (($(($binding:ident, $iterable:expr)),+) => $body:expr) => {{
         let context = vec![
@@ -495,9 +509,19 @@ macro_rules! lc {
     }};
 }
 
+macro_rules! lambda {
+    (|$($param:ident),*| $body:expr) => {
+        Expr::Lambda(Lambda::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            vec![$(ident!($param)),*],
+            $body,
+        ))
+    };
+}
+
 macro_rules! range {
     ($range:expr) => {
-        Expr::Range(Span::new(SourceSpan::UNKNOWN, $range))
+        Expr::Range(RangeExpr::from(Span::new(SourceSpan::UNKNOWN, $range)))
     };
 }
 
@@ -519,6 +543,22 @@ macro_rules! not {
     };
 }
 
+macro_rules! if_ {
+    ($lhs:expr, $op:ident, $rhs:expr => $then:expr, $else:expr) => {
+        ScalarExpr::If(IfExpr::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            Comparison::new(
+                miden_diagnostics::SourceSpan::UNKNOWN,
+                ComparisonOp::$op,
+                $lhs,
+                $rhs,
+            ),
+            $then,
+            $else,
+        ))
+    };
+}
+
 macro_rules! eq {
     ($lhs:expr, $rhs:expr) => {
         ScalarExpr::Binary(BinaryExpr::new(
@@ -563,6 +603,17 @@ macro_rules! mul {
     };
 }
 
+macro_rules! div {
+    ($lhs:expr, $rhs:expr) => {
+        ScalarExpr::Binary(BinaryExpr::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            BinaryOp::Div,
+            $lhs,
+            $rhs,
+        ))
+    };
+}
+
 macro_rules! exp {
     ($lhs:expr, $rhs:expr) => {
         ScalarExpr::Binary(BinaryExpr::new(
@@ -583,9 +634,16 @@ macro_rules! import_all {
 }
 
 macro_rules! import {
-    ($module:ident, $item:ident) => {{
-        let mut items: std::collections::HashSet<Identifier> = std::collections::HashSet::default();
-        items.insert(ident!($item));
+    ($module:ident, $item:ident) => {
+        import!($module, [$item as $item])
+    };
+
+    ($module:ident, [$($item:ident as $alias:ident),+]) => {{
+        let mut items: std::collections::HashMap<Identifier, Identifier> =
+            std::collections::HashMap::default();
+        $(
+            items.insert(ident!($alias), ident!($item));
+        )+
         Import::Partial {
             module: ident!($module),
             items,
@@ -594,8 +652,11 @@ macro_rules! import {
 }
 
 mod arithmetic_ops;
+mod assertions;
 mod boundary_constraints;
+mod buses;
 mod calls;
+mod conditionals;
 mod constant_propagation;
 mod constants;
 mod evaluators;
@@ -603,6 +664,7 @@ mod identifiers;
 mod inlining;
 mod integrity_constraints;
 mod list_comprehension;
+mod lookups;
 mod modules;
 mod periodic_columns;
 mod pub_inputs;
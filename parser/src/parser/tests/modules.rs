@@ -1,6 +1,6 @@
 use miden_diagnostics::{SourceSpan, Span};
 
-use crate::ast::*;
+use crate::{ast::*, ParserConfig};
 
 use super::ParseTest;
 
@@ -16,6 +16,77 @@ fn use_declaration() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn item_visibility() {
+    let source = "
+    mod test
+
+    pub const A = 1
+    const B = 2
+
+    pub ev foo([clk]):
+        enf clk' = clk + A
+
+    ev bar([clk]):
+        enf clk' = clk + B
+    ";
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.constants.insert(
+        ident!(A),
+        constant!(A = 1).with_visibility(Visibility::Public),
+    );
+    expected.constants.insert(ident!(B), constant!(B = 2));
+    expected.evaluators.insert(
+        ident!(foo),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(foo),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                access!(clk, 1),
+                add!(access!(clk), access!(A))
+            ))],
+        )
+        .with_visibility(Visibility::Public),
+    );
+    expected.evaluators.insert(
+        ident!(bar),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(bar),
+            vec![trace_segment!(0, "%1", [(clk, 1)])],
+            vec![enforce!(eq!(
+                access!(clk, 1),
+                add!(access!(clk), access!(B))
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_pub_item_in_root_module() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    pub const A = 1
+
+    integrity_constraints:
+        enf clk' = clk + A
+
+    boundary_constraints:
+        enf clk.first = 0
+    ";
+    ParseTest::new()
+        .expect_module_diagnostic(source, "items of the root module cannot be declared pub");
+}
+
 #[test]
 fn import_declaration() {
     let source = "
@@ -28,6 +99,535 @@ fn import_declaration() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn import_declaration_with_multiple_items() {
+    let source = "
+    mod test
+
+    use foo::{bar, BAZ as QUX}
+    ";
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .imports
+        .insert(ident!(foo), import!(foo, [bar as bar, BAZ as QUX]));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn module_alias_declaration() {
+    let source = "
+    mod test
+
+    use foo as f
+    ";
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    // Aliasing a module does not import any of its items by name
+    expected.imports.insert(
+        ident!(foo),
+        Import::Partial {
+            module: ident!(foo),
+            items: Default::default(),
+        },
+    );
+    expected.aliases.insert(ident!(f), ident!(foo));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_import_alias_changes_namespace() {
+    let source = "
+    mod test
+
+    use foo::{BAR as bar}
+    ";
+    ParseTest::new().expect_module_diagnostic(source, "invalid import alias");
+}
+
+#[test]
+fn err_import_alias_conflict() {
+    let source = "
+    mod test
+
+    use foo::{BAR as BAZ}
+    use bar::BAZ
+    ";
+    ParseTest::new().expect_module_diagnostic(source, "invalid import declaration");
+}
+
+#[test]
+fn err_renamed_import_with_wildcard() {
+    let source = "
+    mod test
+
+    use foo::*
+    use foo::{BAR as BAZ}
+    ";
+    ParseTest::new().expect_module_diagnostic(source, "conflicting import");
+}
+
+const CHIPLET_A: &str = r#"
+mod chiplet_a
+
+pub const MASK = 1
+
+pub ev helper([x]):
+    enf x' = x + MASK
+"#;
+
+const CHIPLET_B: &str = r#"
+mod chiplet_b
+
+pub const MASK = 2
+
+pub ev helper([x]):
+    enf x' = x * MASK
+"#;
+
+const CHIPLET_C: &str = r#"
+mod chiplet_c
+
+const SECRET = 3
+
+pub ev helper([x]):
+    enf x' = x + SECRET
+"#;
+
+fn chiplets_test() -> ParseTest {
+    let test = ParseTest::new();
+    let path = std::env::current_dir().unwrap().join("chiplet_a.air");
+    test.add_virtual_file(path, CHIPLET_A.to_string());
+    let path = std::env::current_dir().unwrap().join("chiplet_b.air");
+    test.add_virtual_file(path, CHIPLET_B.to_string());
+    let path = std::env::current_dir().unwrap().join("chiplet_c.air");
+    test.add_virtual_file(path, CHIPLET_C.to_string());
+    test
+}
+
+// Modules which export items with the same names can be used together, by renaming
+// the clashing items on import, or by referencing them through a module alias
+#[test]
+fn import_aliasing_resolves_clashing_names() {
+    let root = r#"
+    def root
+
+    use chiplet_a::{helper, MASK as A_MASK}
+    use chiplet_b as b
+
+    trace_columns:
+        main: [clk, x]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf helper([clk])
+        enf b::helper([x])
+        enf x = A_MASK + b::MASK
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    let test = chiplets_test();
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(program) => program,
+    };
+
+    assert!(program.constants.contains_key(&ident!(chiplet_a, MASK)));
+    assert!(program.constants.contains_key(&ident!(chiplet_b, MASK)));
+    assert!(program
+        .evaluators
+        .contains_key(&function_ident!(chiplet_a, helper)));
+    assert!(program
+        .evaluators
+        .contains_key(&function_ident!(chiplet_b, helper)));
+    assert_eq!(
+        program.integrity_constraints[2],
+        enforce!(eq!(
+            access!(x, Type::Felt),
+            add!(
+                access!(chiplet_a, MASK, Type::Felt),
+                access!(chiplet_b, MASK, Type::Felt)
+            )
+        ))
+    );
+}
+
+#[test]
+fn err_import_clashing_names() {
+    let root = r#"
+    def root
+
+    use chiplet_a::*
+    use chiplet_b::*
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf helper([clk])
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "conflicting import");
+}
+
+#[test]
+fn err_undefined_item_in_aliased_module() {
+    let root = r#"
+    def root
+
+    use chiplet_b as b
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf clk' = clk + b::OTHER
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "reference to undefined item");
+}
+
+// Qualifying an item by the name of an imported module is checked like qualifying it by an alias
+#[test]
+fn err_undefined_item_in_imported_module() {
+    let root = r#"
+    def root
+
+    use chiplet_b::helper
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf helper([clk])
+        enf clk' = clk + chiplet_b::OTHER
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "reference to undefined item");
+}
+
+#[test]
+fn err_import_private_item() {
+    let root = r#"
+    def root
+
+    use chiplet_c::{helper, SECRET}
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf helper([clk])
+        enf clk = SECRET
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "the item 'SECRET' is private to its module");
+}
+
+#[test]
+fn err_private_item_in_aliased_module() {
+    let root = r#"
+    def root
+
+    use chiplet_c as c
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf clk' = clk + c::SECRET
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "reference to private item");
+}
+
+#[test]
+fn err_private_item_in_imported_module() {
+    let root = r#"
+    def root
+
+    use chiplet_c::helper
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf helper([clk])
+        enf clk' = clk + chiplet_c::SECRET
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "reference to private item");
+}
+
+// Wildcard imports only bring the public items of a module into scope
+#[test]
+fn err_wildcard_import_skips_private_items() {
+    let root = r#"
+    def root
+
+    use chiplet_c::*
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf helper([clk])
+        enf clk = SECRET
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    chiplets_test().expect_program_diagnostic(root, "this variable is not defined");
+}
+
+const HASHER: &str = r#"
+mod hasher
+
+pub const ROUNDS = 8
+
+pub ev round([x]):
+    enf x' = x * ROUNDS
+"#;
+
+const SEARCH_PATH_ROOT: &str = r#"
+def root
+
+use chiplets::hasher::*
+
+trace_columns:
+    main: [clk]
+
+public_inputs:
+    inputs: [2]
+
+integrity_constraints:
+    enf round([clk])
+
+boundary_constraints:
+    enf clk.first = 0
+"#;
+
+// Modules which are not found next to the importing module are looked up in the configured
+// search paths, with nested module paths mapped to subdirectories
+#[test]
+fn import_from_search_path() {
+    let lib_dir = std::env::current_dir().unwrap().join("airscript-lib");
+    let test = ParseTest::with_config(
+        ParserConfig::default()
+            .with_search_path(std::env::current_dir().unwrap().join("does-not-exist"))
+            .with_search_path(&lib_dir),
+    );
+    test.add_virtual_file(lib_dir.join("chiplets/hasher.air"), HASHER.to_string());
+
+    let program = match test.parse_program(SEARCH_PATH_ROOT) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(program) => program,
+    };
+
+    // Items of nested modules are qualified by the full module path
+    let hasher = Identifier::new(
+        SourceSpan::UNKNOWN,
+        crate::Symbol::intern("chiplets::hasher"),
+    );
+    assert!(program.constants.contains_key(&QualifiedIdentifier::new(
+        hasher,
+        NamespacedIdentifier::Binding(ident!(ROUNDS))
+    )));
+    assert!(program.evaluators.contains_key(&QualifiedIdentifier::new(
+        hasher,
+        NamespacedIdentifier::Function(ident!(round))
+    )));
+}
+
+// A nested module is aliased with the same syntax as a renamed item import, so the import is
+// treated as an alias if the nested module exists
+#[test]
+fn nested_module_alias() {
+    let root = r#"
+    def root
+
+    use chiplets::hasher as h
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    integrity_constraints:
+        enf h::round([clk])
+        enf clk = h::ROUNDS
+
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    let lib_dir = std::env::current_dir().unwrap().join("airscript-lib");
+    let test = ParseTest::with_config(ParserConfig::default().with_search_path(&lib_dir));
+    test.add_virtual_file(lib_dir.join("chiplets/hasher.air"), HASHER.to_string());
+
+    let program = match test.parse_program(root) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(program) => program,
+    };
+
+    let hasher = Identifier::new(
+        SourceSpan::UNKNOWN,
+        crate::Symbol::intern("chiplets::hasher"),
+    );
+    assert!(program.constants.contains_key(&QualifiedIdentifier::new(
+        hasher,
+        NamespacedIdentifier::Binding(ident!(ROUNDS))
+    )));
+    assert!(program.evaluators.contains_key(&QualifiedIdentifier::new(
+        hasher,
+        NamespacedIdentifier::Function(ident!(round))
+    )));
+}
+
+#[test]
+fn err_import_not_in_search_path() {
+    let lib_dir = std::env::current_dir().unwrap().join("airscript-lib");
+    let test = ParseTest::new();
+    test.add_virtual_file(lib_dir.join("chiplets/hasher.air"), HASHER.to_string());
+    test.expect_program_diagnostic(SEARCH_PATH_ROOT, "error occurred while resolving an import");
+}
+
+const NESTED_HASHER: &str = r#"
+mod hasher
+
+use common::K
+
+pub ev round([x]):
+    enf x' = x * K
+"#;
+
+const NESTED_ROOT: &str = r#"
+def root
+
+use chiplets::hasher::round
+
+trace_columns:
+    main: [clk]
+
+public_inputs:
+    inputs: [2]
+
+integrity_constraints:
+    enf round([clk])
+
+boundary_constraints:
+    enf clk.first = 0
+"#;
+
+// Imports are resolved relative to the importing module, so `common` imported by
+// `chiplets::hasher` refers to `chiplets/common.air`
+#[test]
+fn nested_module_with_same_name_import() {
+    let lib_dir = std::env::current_dir().unwrap().join("airscript-lib");
+    let test = ParseTest::with_config(ParserConfig::default().with_search_path(&lib_dir));
+    test.add_virtual_file(
+        lib_dir.join("chiplets/hasher.air"),
+        NESTED_HASHER.to_string(),
+    );
+    test.add_virtual_file(
+        lib_dir.join("chiplets/common.air"),
+        "mod common\n\npub const K = 2".to_string(),
+    );
+    test.add_virtual_file(
+        std::env::current_dir().unwrap().join("common.air"),
+        "mod common\n\npub const K = 1".to_string(),
+    );
+
+    let program = match test.parse_program(NESTED_ROOT) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(program) => program,
+    };
+
+    let k = QualifiedIdentifier::new(ident!(common), NamespacedIdentifier::Binding(ident!(K)));
+    assert_eq!(program.constants[&k].value, ConstantExpr::Scalar(2));
+}
+
+// Two different files imported under the same module name would otherwise be merged into one
+// module, so this is rejected
+#[test]
+fn err_nested_modules_with_same_name() {
+    let root = NESTED_ROOT.replace(
+        "use chiplets::hasher::round",
+        "use chiplets::hasher::round\nuse common::K",
+    );
+
+    let lib_dir = std::env::current_dir().unwrap().join("airscript-lib");
+    let test = ParseTest::with_config(ParserConfig::default().with_search_path(&lib_dir));
+    test.add_virtual_file(
+        lib_dir.join("chiplets/hasher.air"),
+        NESTED_HASHER.to_string(),
+    );
+    test.add_virtual_file(
+        lib_dir.join("chiplets/common.air"),
+        "mod common\n\npub const K = 2".to_string(),
+    );
+    test.add_virtual_file(
+        std::env::current_dir().unwrap().join("common.air"),
+        "mod common\n\npub const K = 1".to_string(),
+    );
+    test.expect_program_diagnostic(&root, "conflicting module definitions");
+}
+
 // This test performs a realistic test involving compilation of a program consisting of
 // items in 3 different modules, which tests the following:
 //
@@ -70,7 +670,8 @@ fn modules_integration_test() {
                 access!(clk, 1, Type::Felt),
                 add!(access!(clk, Type::Felt), access!(bar, k0, Type::Felt))
             ), when access!(bar, k0, Type::Felt)))],
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
     // ev foo_constraint([clk]):
     //    enf clk' = clk + 1 when k0
@@ -81,7 +682,8 @@ fn modules_integration_test() {
             ident!(foo_constraint),
             vec![trace_segment!(0, "%0", [(clk, 1)])],
             vec![enforce_all!(lc!((("%1", range!(0..1))) => eq!(access!(clk, 1, Type::Felt), add!(access!(clk, Type::Felt), int!(1))), when access!(foo, k0, Type::Felt)))],
-        ),
+        )
+        .with_visibility(Visibility::Public),
     );
     expected.public_inputs.insert(
         ident!(inputs),
@@ -1,6 +1,7 @@
+use air_pass::Pass;
 use miden_diagnostics::SourceSpan;
 
-use crate::ast::*;
+use crate::{ast::*, transforms::ConstantPropagation};
 
 use super::ParseTest;
 
@@ -50,6 +51,139 @@ fn err_periodic_columns_length() {
 
     ParseTest::new().expect_module_diagnostic(
         source,
-        "periodic columns must have a non-zero cycle length which is a power of two",
+        "periodic columns must have a cycle length which is a power of two, and at least 2",
     );
 }
+
+#[test]
+fn periodic_columns_definition() {
+    let source = "
+    mod test
+
+    periodic_columns:
+        k0: ROUND_CONSTANTS[3]";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.periodic_columns.insert(
+        ident!(k0),
+        PeriodicColumn::with_definition(
+            SourceSpan::UNKNOWN,
+            ident!(k0),
+            expr!(access!(ROUND_CONSTANTS[3])),
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn periodic_columns_computed() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    const ROUND_CONSTANTS = [[1, 2, 3, 4], [5, 6, 7, 8]]
+
+    periodic_columns:
+        k0: [2^i for i in 0..8]
+        k1: ROUND_CONSTANTS[1]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a * k0 + k1";
+
+    let test = ParseTest::new();
+    let program = match test.parse_program(source) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    let program = pass.run(program).unwrap();
+
+    let columns = program
+        .periodic_columns
+        .values()
+        .map(|column| {
+            (
+                column.name,
+                column.values.clone(),
+                column.definition.is_none(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        vec![
+            (ident!(k0), vec![1, 2, 4, 8, 16, 32, 64, 128], true),
+            (ident!(k1), vec![5, 6, 7, 8], true),
+        ]
+    );
+}
+
+#[test]
+fn err_periodic_columns_computed_length() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    periodic_columns:
+        k0: [i for i in 0..6]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a * k0";
+
+    let test = ParseTest::new();
+    let program = test.parse_program(source).unwrap();
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_err());
+    assert!(test.captured_diagnostics().contains(
+        "this expression has 6 values, but periodic columns must have a cycle length which is a power of two, and at least 2"
+    ));
+}
+
+#[test]
+fn err_periodic_columns_not_constant() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b[4]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    periodic_columns:
+        k0: [x for x in b]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a * k0";
+
+    let test = ParseTest::new();
+    let program = test.parse_program(source).unwrap();
+    let mut pass = ConstantPropagation::new(&test.diagnostics);
+    assert!(pass.run(program).is_err());
+    assert!(test
+        .captured_diagnostics()
+        .contains("the values of a periodic column must be constant"));
+}
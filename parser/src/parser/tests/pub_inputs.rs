@@ -51,6 +51,73 @@ fn public_inputs() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn public_inputs_variable_length() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+        outputs: [_]
+
+    boundary_constraints:
+        enf clk.last = outputs[3]
+
+    integrity_constraints:
+        enf clk = 0";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(stack_inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(stack_inputs), 16),
+    );
+    expected.public_inputs.insert(
+        ident!(outputs),
+        PublicInput::variable_length(SourceSpan::UNKNOWN, ident!(outputs)),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::Last),
+            access!(outputs[3])
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_variable_length_public_input_not_indexed() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        outputs: [_]
+
+    boundary_constraints:
+        enf clk.last = sum(outputs)
+
+    integrity_constraints:
+        enf clk = 0";
+
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "variable-length public inputs may only be accessed by index",
+    );
+}
+
 #[test]
 fn error_no_public_input() {
     let source = "
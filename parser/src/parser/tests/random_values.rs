@@ -35,7 +35,9 @@ fn random_values_fixed_list() {
     expected
         .trace_columns
         .push(trace_segment!(1, "$aux", [(a, 1)]));
-    expected.random_values = Some(random_values!("$rand", 15));
+    expected
+        .random_values
+        .insert(1, random_values!("$rand", 15));
     expected.public_inputs.insert(
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
@@ -83,7 +85,9 @@ fn random_values_ident_vector() {
     expected
         .trace_columns
         .push(trace_segment!(1, "$aux", [(aux0, 1)]));
-    expected.random_values = Some(random_values!("$rand", [(a, 1), (b, 12), (c, 1)]));
+    expected
+        .random_values
+        .insert(1, random_values!("$rand", [(a, 1), (b, 12), (c, 1)]));
     expected.public_inputs.insert(
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
@@ -131,7 +135,9 @@ fn random_values_custom_name() {
     expected
         .trace_columns
         .push(trace_segment!(1, "$aux", [(aux0, 1)]));
-    expected.random_values = Some(random_values!("$alphas", 14));
+    expected
+        .random_values
+        .insert(1, random_values!("$alphas", 14));
     expected.public_inputs.insert(
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
@@ -168,6 +174,63 @@ fn err_random_values_empty_list() {
     ParseTest::new().expect_module_diagnostic(source, "random values cannot be empty");
 }
 
+#[test]
+fn random_values_per_segment() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux: [a]
+        aux2: [b]
+
+    random_values:
+        rand: [2]
+        gammas: [g, h[3]] for aux2
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(a, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(2, "$aux2", [(b, 1)]));
+    expected.random_values.insert(1, random_values!("$rand", 2));
+    expected.random_values.insert(
+        2,
+        random_values!("$gammas", [(g, 1), (h, 3)]).for_segment(2),
+    );
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_random_values_multiple_declaration() {
     let source = "
@@ -175,16 +238,44 @@ fn err_random_values_multiple_declaration() {
 
     trace_columns:
         main: [clk]
+        aux: [a]
 
     random_values:
         rand: [12]
-        alphas: [a, b[2]]
+        alphas: [x, y[2]]
 
     integrity_constraints:
         enf clk = 0";
 
-    ParseTest::new()
-        .expect_module_diagnostic(source, "only one declaration may appear in random_values");
+    ParseTest::new().expect_module_diagnostic(source, "multiple random_values declarations");
+}
+
+#[test]
+fn err_random_values_missing_segment() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux: [a]
+
+    random_values:
+        rand: [2]
+        gammas: [4] for aux2
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0";
+
+    ParseTest::new().expect_module_diagnostic(
+        source,
+        "declaring random_values requires an aux2 trace_columns declaration",
+    );
 }
 
 #[test]
@@ -215,7 +306,9 @@ fn random_values_index_access() {
     expected
         .trace_columns
         .push(trace_segment!(1, "$aux", [(aux0, 1)]));
-    expected.random_values = Some(random_values!("$rand", 12));
+    expected
+        .random_values
+        .insert(1, random_values!("$rand", 12));
     expected.public_inputs.insert(
         ident!(inputs),
         PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
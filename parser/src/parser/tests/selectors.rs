@@ -1,6 +1,7 @@
+use air_pass::Pass;
 use miden_diagnostics::{SourceSpan, Span};
 
-use crate::ast::*;
+use crate::{ast::*, transforms::SelectorAnalysis};
 
 use super::ParseTest;
 
@@ -91,3 +92,297 @@ fn chained_selectors() {
 
     ParseTest::new().expect_module_ast(source, expected);
 }
+
+#[test]
+fn when_block_selector() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, a, n1, n2]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf when n1:
+            enf clk' = clk
+            enf a' = a when n2
+        enf a = 0
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected.trace_columns.push(trace_segment!(
+        0,
+        "$main",
+        [(clk, 1), (a, 1), (n1, 1), (n2, 1)]
+    ));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    // The selector of the block is applied to each constraint in it, in addition to any selector
+    // the constraint already had
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            enforce_all!(
+                lc!((("%1", range!(0..1))) => eq!(access!(clk, 1), access!(clk)), when access!(n1))
+            ),
+            enforce_all!(
+                lc!((("%0", range!(0..1))) => eq!(access!(a, 1), access!(a)), when mul!(access!(n1), access!(n2)))
+            ),
+            enforce!(eq!(access!(a), int!(0))),
+        ],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn nested_when_block_selectors() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, n1, n2]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf when n1:
+            enf when n2:
+                enf clk' = clk
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (n1, 1), (n2, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce_all!(
+            lc!((("%0", range!(0..1))) => eq!(access!(clk, 1), access!(clk)), when mul!(access!(n1), access!(n2)))
+        )],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_empty_when_block() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, n1]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf when n1:
+        enf clk' = clk
+    "#;
+    ParseTest::new().expect_unrecognized_token(source);
+}
+
+// SELECTOR ANALYSIS
+// ================================================================================================
+
+/// Parses the given program and runs selector analysis on it, returning the emitted diagnostics
+fn analyze_selectors(source: &str) -> String {
+    let test = ParseTest::new();
+    let program = match test.parse_program(source) {
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+        Ok(ast) => ast,
+    };
+    SelectorAnalysis::new(&test.diagnostics)
+        .run(program)
+        .unwrap();
+    test.captured_diagnostics()
+}
+
+#[test]
+fn match_selectors_binary_and_exclusive() {
+    let source = "
+    def test
+
+    ev is_binary([x]):
+        enf x * (x - 1) = 0
+
+    trace_columns:
+        main: [s[2], a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf s[0]^2 = s[0]
+        enf is_binary([s[1]])
+        enf match:
+            case s[0] & s[1]: a' = a + b
+            case s[0] & !s[1]: a' = a * b
+            case !s[0] & s[1]: a' = b
+            else: a' = c";
+    let diagnostics = analyze_selectors(source);
+    assert!(
+        diagnostics.is_empty(),
+        "expected no diagnostics, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn match_selectors_binary_by_comprehension() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2], a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf x^2 = x for x in s
+        enf match:
+            case s[0] | s[1]: a' = a + b
+            case !s[0] & !s[1]: a' = a";
+    let diagnostics = analyze_selectors(source);
+    assert!(
+        diagnostics.is_empty(),
+        "expected no diagnostics, got: {}",
+        diagnostics
+    );
+}
+
+#[test]
+fn warn_match_selector_not_binary_constrained() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2], a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf s[0]^2 = s[0]
+        enf match:
+            case s[0]: a' = a + b
+            case !s[0] & s[1]: a' = a";
+    let diagnostics = analyze_selectors(source);
+    assert!(diagnostics.contains("this value is not constrained to be binary"));
+}
+
+#[test]
+fn warn_match_selectors_not_exclusive() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2], a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf s[0]^2 = s[0]
+        enf s[1]^2 = s[1]
+        enf match:
+            case s[0]: a' = a + b
+            case s[1]: a' = a";
+    let diagnostics = analyze_selectors(source);
+    assert!(diagnostics.contains("match selectors are not mutually exclusive"));
+    assert!(diagnostics.contains("this selector is selected when s[0] = 1, s[1] = 1"));
+}
+
+#[test]
+fn warn_match_selector_not_binary() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2], a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf s[0]^2 = s[0]
+        enf s[1]^2 = s[1]
+        enf match:
+            case s[0] + s[1]: a' = a + b";
+    let diagnostics = analyze_selectors(source);
+    assert!(diagnostics.contains("this selector evaluates to 2 when s[0] = 1, s[1] = 1"));
+}
+
+#[test]
+fn match_selectors_binary_by_column_type() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2]: bool, a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf match:
+            case s[0] | s[1]: a' = a + b
+            case !s[0] & !s[1]: a' = a";
+    let diagnostics = analyze_selectors(source);
+    assert!(
+        diagnostics.is_empty(),
+        "expected no diagnostics, got: {}",
+        diagnostics
+    );
+}
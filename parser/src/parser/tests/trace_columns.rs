@@ -140,6 +140,53 @@ fn trace_columns_groups() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn trace_columns_multiple_aux_segments() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp, ctx]
+        aux: [rc_bus, ch_bus]
+        aux2: [range[2]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (fmp, 1), (ctx, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(rc_bus, 1), (ch_bus, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(2, "$aux2", [(range, 2)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_empty_trace_columns() {
     let source = r#"
@@ -171,3 +218,190 @@ fn err_main_trace_cols_missing() {
     ParseTest::new()
         .expect_module_diagnostic(source, "declaration of main trace columns is required");
 }
+
+#[test]
+fn err_aux_trace_segments_out_of_order() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux2: [p]
+    public_inputs:
+        stack_inputs: [16]
+    integrity_constraints:
+        enf clk' = clk + 1
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "expected 'aux1' here");
+}
+
+#[test]
+fn err_aux_trace_segment_declared_twice() {
+    // `aux` and `aux1` both name the first auxiliary segment
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux: [p]
+        aux1: [q]
+    public_inputs:
+        stack_inputs: [16]
+    integrity_constraints:
+        enf clk' = clk + 1
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "expected 'aux2' here");
+}
+
+#[test]
+fn err_invalid_trace_segment_name() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+        extra: [p]
+    public_inputs:
+        stack_inputs: [16]
+    integrity_constraints:
+        enf clk' = clk + 1
+    boundary_constraints:
+        enf clk.first = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "invalid trace segment");
+}
+
+#[test]
+fn trace_columns_typed() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [s: bool, limb[2]: u16, x]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    buses:
+        rc: multiset for u16
+
+    boundary_constraints:
+        enf x.first = 0
+
+    integrity_constraints:
+        enf x = 0
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected.trace_columns.push(
+        trace_segment!(0, "$main", [(s, 1), (limb, 2), (x, 1)]).with_column_types(vec![
+            Some(ColumnType::Bool),
+            Some(ColumnType::U16),
+            None,
+        ]),
+    );
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(p, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.random_values.insert(1, random_values!("$rand", 2));
+    expected.buses.insert(
+        ident!(rc),
+        Bus::new(SourceSpan::UNKNOWN, ident!(rc), BusKind::Multiset)
+            .with_range_check(ColumnType::U16),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(x, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(x), int!(0)))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_invalid_column_type() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [s: u64]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf s.first = 0
+
+    integrity_constraints:
+        enf s = 0
+    "#;
+
+    ParseTest::new()
+        .expect_module_diagnostic(source, "expected one of 'bool', 'u8', 'u16', or 'u32'");
+}
+
+#[test]
+fn err_missing_range_check() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [limb[4]: u16]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf limb[0].first = 0
+
+    integrity_constraints:
+        enf limb[0] = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "no range check is declared for u16 columns");
+}
+
+#[test]
+fn err_conflicting_range_checks() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [limb[4]: u16]
+        aux: [p]
+
+    public_inputs:
+        inputs: [2]
+
+    random_values:
+        rand: [2]
+
+    buses:
+        rc: multiset for u16
+        rc2: multiset for u16
+
+    boundary_constraints:
+        enf limb[0].first = 0
+
+    integrity_constraints:
+        enf limb[0] = 0
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "but so does this bus");
+}
@@ -5,7 +5,7 @@ use pretty_assertions::assert_eq;
 
 use crate::{
     ast::{Module, Program},
-    parser::{ParseError, Parser},
+    parser::{ParseError, Parser, ParserConfig},
 };
 
 struct SplitEmitter {
@@ -66,6 +66,11 @@ impl ParseTest {
 
     /// Creates a new test, from the source string.
     pub fn new() -> Self {
+        Self::with_config(ParserConfig::default())
+    }
+
+    /// Creates a new test, using the given parser configuration
+    pub fn with_config(parser_config: ParserConfig) -> Self {
         let codemap = Arc::new(CodeMap::new());
         let emitter = Arc::new(SplitEmitter::new());
         let config = DiagnosticsConfig {
@@ -79,7 +84,7 @@ impl ParseTest {
             codemap.clone(),
             emitter.clone(),
         ));
-        let parser = Parser::new((), codemap);
+        let parser = Parser::new(parser_config, codemap);
         Self {
             diagnostics,
             emitter,
@@ -114,6 +119,11 @@ impl ParseTest {
             .parse_string::<Program, _, _>(&self.diagnostics, source)
     }
 
+    /// Returns all of the diagnostics which have been emitted during this test so far
+    pub fn captured_diagnostics(&self) -> String {
+        self.emitter.captured()
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -21,14 +21,16 @@ pub enum BindingType {
     Function(FunctionType),
     /// A function parameter corresponding to trace columns
     TraceParam(TraceBinding),
+    /// A function parameter corresponding to a value, i.e. a field element or vector of them
+    ValueParam(Type),
     /// A direct reference to one or more contiguous trace columns
     TraceColumn(TraceBinding),
     /// A potentially non-contiguous set of trace columns
     Vector(Vec<BindingType>),
     /// A direct reference to a random value binding
     RandomValue(RandBinding),
-    /// A direct reference to a public input
-    PublicInput(Type),
+    /// A direct reference to a public input, whose type is `None` if it is variable-length
+    PublicInput(Option<Type>),
     /// A direct reference to a periodic column
     PeriodicColumn(usize),
 }
@@ -40,7 +42,8 @@ impl BindingType {
             Self::Vector(elems) => Some(Type::Vector(elems.len())),
             Self::RandomValue(rb) => Some(rb.ty()),
             Self::Alias(aliased) => aliased.ty(),
-            Self::Local(ty) | Self::Constant(ty) | Self::PublicInput(ty) => Some(*ty),
+            Self::Local(ty) | Self::Constant(ty) | Self::ValueParam(ty) => Some(*ty),
+            Self::PublicInput(ty) => *ty,
             Self::PeriodicColumn(_) => Some(Type::Felt),
             Self::Function(ty) => ty.result(),
         }
@@ -55,6 +58,15 @@ impl BindingType {
         }
     }
 
+    /// Returns the number of trace columns represented by this binding, if it is a trace binding
+    pub fn num_trace_columns(&self) -> Option<usize> {
+        match self {
+            Self::TraceColumn(tb) | Self::TraceParam(tb) => Some(tb.size),
+            Self::Vector(ref elems) => elems.iter().map(|e| e.num_trace_columns()).sum(),
+            _ => None,
+        }
+    }
+
     /// This function is used to split the current binding into two parts, the
     /// first of which contains `n` trace columns, the second of which contains
     /// what remains of the original binding. This function returns `Ok` when
@@ -190,6 +202,7 @@ impl BindingType {
                 .map(|t| Self::Alias(Box::new(Self::Constant(t)))),
             Self::TraceColumn(tb) => tb.access(access_type).map(Self::TraceColumn),
             Self::TraceParam(tb) => tb.access(access_type).map(Self::TraceParam),
+            Self::ValueParam(ty) => ty.access(access_type).map(Self::ValueParam),
             Self::Vector(elems) => match access_type {
                 AccessType::Default => Ok(Self::Vector(elems.clone())),
                 AccessType::Index(idx) if idx >= elems.len() => {
@@ -210,7 +223,15 @@ impl BindingType {
             Self::RandomValue(tb) => tb
                 .access(access_type)
                 .map(|tb| Self::Alias(Box::new(Self::RandomValue(tb)))),
-            Self::PublicInput(ty) => ty.access(access_type).map(Self::PublicInput),
+            Self::PublicInput(Some(ty)) => {
+                ty.access(access_type).map(|ty| Self::PublicInput(Some(ty)))
+            }
+            // The length of a variable-length public input is only known at runtime, so it can
+            // only be indexed, and the index is checked by the generated code instead
+            Self::PublicInput(None) => match access_type {
+                AccessType::Index(_) => Ok(Self::PublicInput(Some(Type::Felt))),
+                _ => Err(InvalidAccessError::VariableLengthAccess),
+            },
             Self::PeriodicColumn(period) => match access_type {
                 AccessType::Default => Ok(Self::PeriodicColumn(*period)),
                 _ => Err(InvalidAccessError::IndexIntoScalar),
@@ -228,6 +249,7 @@ impl fmt::Display for BindingType {
             Self::Vector(_) => f.write_str("vector"),
             Self::Function(_) => f.write_str("function"),
             Self::TraceColumn(_) | Self::TraceParam(_) => f.write_str("trace column(s)"),
+            Self::ValueParam(_) => f.write_str("parameter"),
             Self::RandomValue(_) => f.write_str("random value(s)"),
             Self::PublicInput(_) => f.write_str("public input(s)"),
             Self::PeriodicColumn(_) => f.write_str("periodic column(s)"),
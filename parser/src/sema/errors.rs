@@ -27,6 +27,11 @@ pub enum SemanticAnalysisError {
     ImportSelf(SourceSpan),
     #[error("import conflict")]
     ImportConflict { item: Identifier, prev: SourceSpan },
+    #[error("cannot import private item")]
+    ImportPrivate {
+        item: Identifier,
+        declared: SourceSpan,
+    },
     #[error("import failed")]
     ImportFailed(SourceSpan),
     #[error(transparent)]
@@ -43,6 +48,9 @@ impl PartialEq for SemanticAnalysisError {
             (Self::ImportConflict { item: li, .. }, Self::ImportConflict { item: ri, .. }) => {
                 li == ri
             }
+            (Self::ImportPrivate { item: li, .. }, Self::ImportPrivate { item: ri, .. }) => {
+                li == ri
+            }
             (Self::InvalidExpr(l), Self::InvalidExpr(r)) => l == r,
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
@@ -84,6 +92,13 @@ impl ToDiagnostic for SemanticAnalysisError {
                     .with_message(format!("the item '{}' is imported here", item)),
                                   Label::secondary(prev.source_id(), prev)
                     .with_message("but it conflicts with an item of the same name here")]),
+            Self::ImportPrivate { item, declared } => Diagnostic::error()
+                .with_message("invalid import")
+                .with_labels(vec![Label::primary(item.span().source_id(), item.span())
+                    .with_message(format!("the item '{}' is private to its module", item)),
+                                  Label::secondary(declared.source_id(), declared)
+                    .with_message("it is declared here")])
+                .with_notes(vec!["Only items declared with `pub` may be imported from other modules".to_string()]),
             Self::ImportFailed(span) => Diagnostic::error()
                 .with_message("error occurred while resolving an import")
                 .with_labels(vec![Label::primary(span.source_id(), span)
@@ -8,18 +8,21 @@ use crate::{
     sema::SemanticAnalysisError,
 };
 
-pub type Imported = HashMap<NamespacedIdentifier, ModuleId>;
+/// Maps the names imported into a module, to the fully-qualified identifiers of the items they refer to
+///
+/// The name of an imported item and the name it was exported with differ if the item was renamed on import.
+pub type Imported = HashMap<NamespacedIdentifier, QualifiedIdentifier>;
 
 pub struct ImportResolver<'a> {
     diagnostics: &'a DiagnosticsHandler,
     library: &'a Library,
     /// Records the identifiers that were imported into the current module,
-    /// the source module of those identifiers, and their type.
+    /// the items they refer to, and their type.
     ///
     /// This is used to determine whether or not to raise a name conflict error
     /// when rolling up imports to the root module. If two identifiers conflict
-    /// on import, but they were both ultimately sourced from the same module, that
-    /// is not an error.
+    /// on import, but they both ultimately refer to the same item, that is not
+    /// an error.
     imported: Imported,
 }
 impl<'a> ImportResolver<'a> {
@@ -59,7 +62,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ImportResolver<'a> {
                     for export in imported_from.exports() {
                         let name = export.name();
                         let item = Identifier::new(from.span(), name.name());
-                        self.import(module, *from, item, export)?;
+                        self.import(module, *from, item, name, export)?;
                     }
                 }
                 Import::Partial {
@@ -74,17 +77,22 @@ impl<'a> VisitMut<SemanticAnalysisError> for ImportResolver<'a> {
                         Ok(value) => value,
                         Err(err) => return ControlFlow::Break(err),
                     };
-                    for export in imported_from.exports() {
-                        let name = export.name();
-                        // We fetch the item from the set, rather than simply
-                        // check for containment, because we want the span associated
-                        // with the item in the set, not the span associated with the
-                        // export.
-                        if let Some(item) = items.get(&name) {
-                            self.import(module, *from, *item, export)?;
+                    // We import items using the span associated with the name they
+                    // are bound to in this module, not the span associated with the export.
+                    for (local, item) in items.iter() {
+                        if let Some(export) = imported_from.get(item) {
+                            if !export.visibility().is_public() {
+                                return ControlFlow::Break(SemanticAnalysisError::ImportPrivate {
+                                    item: *item,
+                                    declared: export.span(),
+                                });
+                            }
+                            self.import(module, *from, *local, export.name(), export)?;
                         }
                     }
                 }
+                // Aliases are recorded in `Module::aliases` during module construction
+                Import::Alias { .. } => unreachable!(),
             }
         }
 
@@ -96,16 +104,30 @@ impl<'a> VisitMut<SemanticAnalysisError> for ImportResolver<'a> {
 
 impl<'a> ImportResolver<'a> {
     /// Imports a single item into the current module
+    ///
+    /// The item `exported` from `from` is bound to the name `item` in the current module.
     fn import(
         &mut self,
         module: &mut Module,
         from: ModuleId,
         item: Identifier,
+        exported: Identifier,
         export: Export<'_>,
     ) -> ControlFlow<SemanticAnalysisError> {
+        // We give the qualified identifier the span of the import, as that is
+        // where the item is referenced from the perspective of this module
+        let exported = Identifier::new(item.span(), exported.name());
         match export {
-            Export::Constant(_) => self.import_constant(module, from, item),
-            Export::Evaluator(_) => self.import_evaluator(module, from, item),
+            Export::Constant(_) => self.import_constant(
+                module,
+                QualifiedIdentifier::new(from, NamespacedIdentifier::Binding(exported)),
+                item,
+            ),
+            Export::Evaluator(_) => self.import_evaluator(
+                module,
+                QualifiedIdentifier::new(from, NamespacedIdentifier::Function(exported)),
+                item,
+            ),
         }
     }
 
@@ -113,7 +135,7 @@ impl<'a> ImportResolver<'a> {
     fn import_constant(
         &mut self,
         module: &mut Module,
-        from: ModuleId,
+        original: QualifiedIdentifier,
         item: Identifier,
     ) -> ControlFlow<SemanticAnalysisError> {
         use std::collections::hash_map::Entry;
@@ -128,8 +150,8 @@ impl<'a> ImportResolver<'a> {
                 match self.imported.entry(namespaced_name) {
                     Entry::Occupied(entry) => {
                         let id = entry.key();
-                        let originally_imported_from = entry.get();
-                        if originally_imported_from == &from {
+                        let originally_imported = entry.get();
+                        if originally_imported == &original {
                             // Warn about redundant import
                             self.diagnostics
                                 .diagnostic(Severity::Warning)
@@ -150,7 +172,7 @@ impl<'a> ImportResolver<'a> {
                         }
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(from);
+                        entry.insert(original);
                         ControlFlow::Continue(())
                     }
                 }
@@ -158,11 +180,11 @@ impl<'a> ImportResolver<'a> {
         }
     }
 
-    /// Imports a constant into the current module
+    /// Imports an evaluator into the current module
     fn import_evaluator(
         &mut self,
         module: &mut Module,
-        from: ModuleId,
+        original: QualifiedIdentifier,
         item: Identifier,
     ) -> ControlFlow<SemanticAnalysisError> {
         use std::collections::hash_map::Entry;
@@ -177,8 +199,8 @@ impl<'a> ImportResolver<'a> {
                 match self.imported.entry(namespaced_name) {
                     Entry::Occupied(entry) => {
                         let id = entry.key();
-                        let originally_imported_from = entry.get();
-                        if originally_imported_from == &from {
+                        let originally_imported = entry.get();
+                        if originally_imported == &original {
                             // Warn about redundant import
                             self.diagnostics
                                 .diagnostic(Severity::Warning)
@@ -199,7 +221,7 @@ impl<'a> ImportResolver<'a> {
                         }
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(from);
+                        entry.insert(original);
                         ControlFlow::Continue(())
                     }
                 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt, mem,
     ops::ControlFlow,
 };
@@ -14,6 +14,13 @@ use crate::{
 
 use super::*;
 
+/// The size assumed for a trace binding sized by a const parameter while analyzing the body
+/// of an evaluator function, as the actual size is only known once the function is instantiated.
+///
+/// The n-th const parameter of a function is assumed to have the value `CONST_PARAM_PLACEHOLDER + n`,
+/// so that bindings sized by different parameters are not considered to be of the same size.
+const CONST_PARAM_PLACEHOLDER: usize = u32::MAX as usize;
+
 /// A helper enum for representing what constraint mode is active
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum ConstraintMode {
@@ -62,6 +69,7 @@ pub struct SemanticAnalysis<'a> {
     library: &'a Library,
     deps: &'a mut DependencyGraph,
     imported: Imported,
+    aliases: BTreeMap<Identifier, ModuleId>,
     globals: HashMap<Identifier, BindingType>,
     locals: LexicalScope<NamespacedIdentifier, BindingType>,
     referenced: HashMap<QualifiedIdentifier, DependencyType>,
@@ -71,6 +79,8 @@ pub struct SemanticAnalysis<'a> {
     has_undefined_variables: bool,
     has_type_errors: bool,
     in_constraint_comprehension: bool,
+    /// The placeholder values of the const parameters of the evaluator being analyzed, if any
+    const_params: HashMap<Identifier, usize>,
 }
 impl<'a> SemanticAnalysis<'a> {
     /// Create a new instance of the semantic analyzer
@@ -87,6 +97,7 @@ impl<'a> SemanticAnalysis<'a> {
             library,
             deps,
             imported,
+            aliases: Default::default(),
             globals: Default::default(),
             locals: Default::default(),
             referenced: Default::default(),
@@ -96,6 +107,7 @@ impl<'a> SemanticAnalysis<'a> {
             has_undefined_variables: false,
             has_type_errors: false,
             in_constraint_comprehension: false,
+            const_params: Default::default(),
         }
     }
 
@@ -137,6 +149,7 @@ impl<'a> SemanticAnalysis<'a> {
 impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
     fn visit_mut_module(&mut self, module: &mut Module) -> ControlFlow<SemanticAnalysisError> {
         self.current_module = Some(module.name);
+        self.aliases = module.aliases.clone();
 
         // Register all globals implicitly defined in the module before all locally bound names
         //
@@ -146,7 +159,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // we know that all of the globally visible declarations from the root module cannot conflict
         // with each other, but we assert that this is so to catch any potentially invalid modules that
         // bypassed that validation somehow.
-        if let Some(rv) = self.program.random_values.as_ref() {
+        for rv in self.program.random_values.values() {
             assert_eq!(
                 self.globals.insert(
                     rv.name,
@@ -184,6 +197,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                             offset: 0,
                             size: segment.size,
                             ty: Type::Vector(segment.size),
+                            column_type: None,
                         })
                     ),
                     None
@@ -199,6 +213,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                                 offset: binding.offset,
                                 size: binding.size,
                                 ty: binding.ty,
+                                column_type: binding.column_type,
                             })
                         ),
                         None
@@ -209,7 +224,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 assert_eq!(
                     self.locals.insert(
                         NamespacedIdentifier::Binding(input.name),
-                        BindingType::PublicInput(Type::Vector(input.size))
+                        BindingType::PublicInput(input.ty())
                     ),
                     None
                 );
@@ -247,7 +262,10 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             assert_eq!(
                 self.locals.insert(
                     namespaced_name,
-                    BindingType::Function(FunctionType::Evaluator(function.params.clone()))
+                    BindingType::Function(FunctionType::Evaluator(
+                        function.params.clone(),
+                        function.value_params.clone()
+                    ))
                 ),
                 None
             );
@@ -257,7 +275,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         //
         // These _can_ conflict with globally defined names, but are guaranteed not to conflict
         // with other module-local declarations.
-        for periodic in module.periodic_columns.values() {
+        for periodic in module.periodic_columns.values_mut() {
             if let Some((prev, prev_binding)) = self.globals.get_key_value(&periodic.name) {
                 self.diagnostics
                     .diagnostic(Severity::Error)
@@ -270,15 +288,35 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                     .emit();
                 return ControlFlow::Break(SemanticAnalysisError::Invalid);
             }
+            let period = match periodic.definition.as_mut() {
+                None => periodic.period(),
+                Some(definition) => {
+                    self.visit_periodic_column_definition(periodic.name, definition)?
+                }
+            };
             assert_eq!(
                 self.locals.insert(
                     NamespacedIdentifier::Binding(periodic.name),
-                    BindingType::PeriodicColumn(periodic.period())
+                    BindingType::PeriodicColumn(period)
                 ),
                 None
             );
         }
 
+        // Resolve the names referenced by the definitions of constants, which are evaluated during
+        // constant propagation
+        for constant in module.constants.values_mut() {
+            if let Some(definition) = constant.definition.as_mut() {
+                self.visit_constant_definition(constant.name, definition)?;
+            }
+        }
+
+        // Resolve the names referenced by compile-time assertions, which are evaluated during
+        // constant propagation
+        if !module.assertions.is_empty() {
+            self.visit_assertions(&mut module.assertions)?;
+        }
+
         // From this point forward, we use the standard visitor traversal to visit every node
         // which can reference an identifier, and rewrite any references to imported names to
         // use the fully-qualified identifier. Likewise, any time we visit an imported item, we
@@ -321,10 +359,81 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // for this function using only those imports referenced from this function body
         let referenced = mem::take(&mut self.referenced);
 
+        // Add the const parameters to the current scope, assigning each a placeholder value
+        // which is used in place of the actual value when checking the function body
+        for (i, param) in function.const_params.iter().copied().enumerate() {
+            let namespaced_name = NamespacedIdentifier::Binding(param);
+            if let Some(prev) = self.locals.get_key(&namespaced_name) {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid evaluator function definition")
+                    .with_primary_label(param.span(), "this parameter name is already in use")
+                    .with_secondary_label(prev.span(), "previously declared here")
+                    .emit();
+                continue;
+            }
+            self.const_params.insert(param, CONST_PARAM_PLACEHOLDER + i);
+            self.locals
+                .insert(namespaced_name, BindingType::ValueParam(Type::Felt));
+        }
+        // Make sure that bindings are only sized by const parameters of this function, and that
+        // every const parameter sizes at least one binding, so that its value can be inferred
+        let mut sizes = self.const_params.clone();
+        for param in function
+            .params
+            .iter()
+            .flat_map(|segment| segment.size_params.iter().flatten())
+        {
+            if !self.const_params.contains_key(param) {
+                self.has_undefined_variables = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid evaluator function definition")
+                    .with_primary_label(
+                        param.span(),
+                        "this is not a const parameter of this function",
+                    )
+                    .emit();
+                sizes.insert(*param, CONST_PARAM_PLACEHOLDER);
+            }
+        }
+        for param in function.const_params.iter() {
+            let is_used = function
+                .params
+                .iter()
+                .flat_map(|segment| segment.size_params.iter())
+                .any(|p| p.as_ref() == Some(param));
+            if !is_used {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid evaluator function definition")
+                    .with_primary_label(
+                        param.span(),
+                        "the value of this parameter cannot be inferred",
+                    )
+                    .with_note("Const parameters must be used to size at least one trace binding, e.g. `x[N]`")
+                    .emit();
+            }
+        }
+
         // Add the set of parameters to the current scope, check for conflicts
-        for trace_segment in function.params.iter_mut() {
-            for trace_binding in trace_segment.bindings.iter() {
+        //
+        // NOTE: Bindings sized by a const parameter are given a placeholder size here, so we do not
+        // instantiate the segment, and the offsets of such bindings are meaningless until inlining
+        for trace_segment in function.params.iter() {
+            let mut offset = 0usize;
+            for (trace_binding, size_param) in trace_segment
+                .bindings
+                .iter()
+                .zip(trace_segment.size_params.iter())
+            {
                 let name = trace_binding.name.unwrap();
+                let (size, ty) = match size_param {
+                    Some(param) => (sizes[param], Type::Vector(sizes[param])),
+                    None => (trace_binding.size, trace_binding.ty),
+                };
                 let namespaced_name = NamespacedIdentifier::Binding(name);
                 self.locals.insert(
                     namespaced_name,
@@ -332,12 +441,29 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                         span: trace_binding.span,
                         name: Some(name),
                         segment: trace_segment.id,
-                        offset: trace_binding.offset,
-                        size: trace_binding.size,
-                        ty: trace_binding.ty,
+                        offset,
+                        size,
+                        ty,
+                        column_type: None,
                     }),
                 );
+                offset = offset.saturating_add(size);
+            }
+        }
+        for param in function.value_params.iter() {
+            let namespaced_name = NamespacedIdentifier::Binding(param.name);
+            if let Some(prev) = self.locals.get_key(&namespaced_name) {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid evaluator function definition")
+                    .with_primary_label(param.name.span(), "this parameter name is already in use")
+                    .with_secondary_label(prev.span(), "previously declared here")
+                    .emit();
+                continue;
             }
+            self.locals
+                .insert(namespaced_name, BindingType::ValueParam(param.ty));
         }
 
         // Visit all of the statements in the body
@@ -357,6 +483,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         self.referenced = referenced;
         // Restore the original lexical scope
         self.locals.exit();
+        self.const_params.clear();
         // Disallow constraints
         self.constraint_mode = ConstraintMode::None;
 
@@ -433,6 +560,94 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         result
     }
 
+    /// Bus interactions are only valid in integrity constraints, must refer to a declared bus, and
+    /// must send/receive tuples of scalar values.
+    fn visit_mut_bus_interaction(
+        &mut self,
+        expr: &mut BusInteraction,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        if !self.constraint_mode.is_integrity() {
+            self.invalid_constraint(
+                expr.span(),
+                "bus interactions are only permitted in integrity constraints",
+            )
+            .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        if !self.program.buses.contains_key(&expr.bus) {
+            self.has_undefined_variables = true;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("undefined bus")
+                .with_primary_label(
+                    expr.bus.span(),
+                    "this bus is not declared in the buses section",
+                )
+                .emit();
+        }
+
+        let span = expr.span();
+        for arg in expr.args.iter_mut() {
+            self.visit_mut_scalar_expr(arg)?;
+            self.expect_felt(
+                arg,
+                span,
+                "but bus interactions expect each element of the tuple to be a felt",
+            );
+        }
+
+        if let Some(selector) = expr.selector.as_mut() {
+            self.visit_mut_scalar_expr(selector)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Lookups are only valid in integrity constraints, and must look up tuples of scalar values
+    fn visit_mut_lookup(&mut self, expr: &mut Lookup) -> ControlFlow<SemanticAnalysisError> {
+        if !self.constraint_mode.is_integrity() {
+            self.invalid_constraint(
+                expr.span(),
+                "lookups are only permitted in integrity constraints",
+            )
+            .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        if !self.program.random_values.contains_key(&1) {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid lookup")
+                .with_primary_label(expr.span(), "lookups require a random_values declaration")
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        let span = expr.span();
+        for value in expr.table.iter_mut().chain(expr.query.iter_mut()) {
+            self.visit_mut_scalar_expr(value)?;
+            self.expect_felt(
+                value,
+                span,
+                "but lookups expect each element of a tuple to be a felt",
+            );
+        }
+
+        self.visit_mut_scalar_expr(&mut expr.multiplicity)?;
+        self.expect_felt(
+            &expr.multiplicity,
+            span,
+            "but the multiplicity of a lookup must be a felt",
+        );
+
+        if let Some(selector) = expr.selector.as_mut() {
+            self.visit_mut_scalar_expr(selector)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
     fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
         // Visit the binding expression first
         self.visit_mut_expr(&mut expr.value)?;
@@ -459,19 +674,55 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         ControlFlow::Continue(())
     }
 
+    fn visit_mut_lambda(&mut self, expr: &mut Lambda) -> ControlFlow<SemanticAnalysisError> {
+        // Lambdas given to `fold` are handled in `visit_mut_call`, so any others are invalid
+        self.has_type_errors = true;
+        self.diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid expression")
+            .with_primary_label(
+                expr.span(),
+                "lambdas may only be used as the combining function of `fold`",
+            )
+            .emit();
+        ControlFlow::Break(SemanticAnalysisError::Invalid)
+    }
+
     fn visit_mut_list_comprehension(
         &mut self,
         expr: &mut ListComprehension,
     ) -> ControlFlow<SemanticAnalysisError> {
+        // Only the body of this comprehension is a constraint, any comprehensions nested in it are not
+        let in_constraint_comprehension =
+            core::mem::replace(&mut self.in_constraint_comprehension, false);
+
         // Visit the iterables first, and resolve their identifiers
         for iterable in expr.iterables.iter_mut() {
             self.visit_mut_expr(iterable)?;
         }
 
+        // Infer the end of ranges which are bounded by the other iterables, e.g. `enumerate(xs)`
+        //
+        // In the body of a generic evaluator, the number of iterations may depend on its const
+        // parameters, in which case this is deferred until the evaluator is instantiated.
+        if self.const_params.is_empty() {
+            let len = expr.iterables.iter().find_map(|iterable| match iterable {
+                Expr::Range(range) if range.end == RangeBound::Inferred => None,
+                iterable => iterable.ty().and_then(|ty| ty.iterable_len()),
+            });
+            if let Some(len) = len {
+                for iterable in expr.iterables.iter_mut() {
+                    if let Expr::Range(ref mut range) = iterable {
+                        range.infer_end(len);
+                    }
+                }
+            }
+        }
+
         // Start a new lexical scope
         self.locals.enter();
 
-        // Track the result type of this comprehension expression
+        // Track the result type of this comprehension expression, i.e. a vector with one element per iteration
         let mut result_ty = None;
         // Add all of the bindings to the local scope, warn on shadowing, error on conflicting bindings
         let mut bound = HashSet::<Identifier>::default();
@@ -494,17 +745,41 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             bound.insert(binding);
 
             let iterable = &expr.iterables[i];
-            let iterable_ty = iterable.ty().unwrap();
-            if let Some(expected_ty) = result_ty.replace(iterable_ty) {
-                if expected_ty != iterable_ty {
-                    self.has_type_errors = true;
-                    self.type_mismatch(
-                        Some(&iterable_ty),
+            let iterable_ty = match iterable {
+                // The length of a range may depend on the const parameters of an evaluator
+                Expr::Range(range) => Type::Vector(self.range_len(range)),
+                iterable => iterable.ty().unwrap(),
+            };
+            // Vectors are iterated element-wise, and matrices row-wise
+            let Some(len) = iterable_ty.iterable_len() else {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid iterable")
+                    .with_primary_label(
                         iterable.span(),
-                        &expected_ty,
-                        expr.iterables[0].span(),
-                        expr.span(),
-                    );
+                        format!("expected a vector or matrix, but this expression has type {iterable_ty}"),
+                    )
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            };
+            // Ranges whose end is inferred have the same length as the other iterables by definition
+            let is_inferred =
+                matches!(iterable, Expr::Range(range) if range.end == RangeBound::Inferred);
+            if !is_inferred {
+                if let Some((expected_len, expected_ty, expected_span)) =
+                    result_ty.replace((len, iterable_ty, iterable.span()))
+                {
+                    if expected_len != len {
+                        self.has_type_errors = true;
+                        self.type_mismatch(
+                            Some(&iterable_ty),
+                            iterable.span(),
+                            &expected_ty,
+                            expected_span,
+                            expr.span(),
+                        );
+                    }
                 }
             }
             match self.expr_binding_type(iterable) {
@@ -543,6 +818,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         }
 
         // If we were unable to determine a type for any of the bindings, use a large vector as a placeholder
+        let result_ty = result_ty.map(|(len, _, _)| Type::Vector(len));
         let expected = BindingType::Local(result_ty.unwrap_or(Type::Vector(u32::MAX as usize)));
 
         // Bind everything now, resolving any deferred types using our fallback expected type
@@ -559,7 +835,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         }
 
         // Visit the comprehension body
-        if self.in_constraint_comprehension {
+        if in_constraint_comprehension {
             self.visit_mut_enforce(expr.body.as_mut())?;
         } else {
             self.visit_mut_scalar_expr(expr.body.as_mut())?;
@@ -575,6 +851,8 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
     }
 
     fn visit_mut_call(&mut self, expr: &mut Call) -> ControlFlow<SemanticAnalysisError> {
+        self.unshadow_builtin(expr);
+
         // Ensure the callee exists, and resolve the type if possible
         self.visit_mut_resolvable_identifier(&mut expr.callee)?;
 
@@ -589,7 +867,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                     // Builtin functions are ignored here
                     if !qid.is_builtin() {
                         let dependency_type = match fty {
-                            FunctionType::Evaluator(_) => DependencyType::Evaluator,
+                            FunctionType::Evaluator(..) => DependencyType::Evaluator,
                             _ => DependencyType::Function,
                         };
                         let prev = self.referenced.insert(qid, dependency_type);
@@ -619,25 +897,97 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             }
         }
 
-        // Visit the call arguments
+        // Visit the call arguments, the combining function of a fold is the only place a lambda may appear
+        let is_fold = expr.is_builtin() && expr.callee.as_ref().name() == symbols::Fold;
         for expr in expr.args.iter_mut() {
-            self.visit_mut_expr(expr)?;
+            match expr {
+                Expr::Lambda(ref mut lambda) if is_fold => self.visit_mut_fold_lambda(lambda)?,
+                expr => self.visit_mut_expr(expr)?,
+            }
         }
 
-        // Validate arguments for builtin functions, which currently consist only of the sum/prod reducers
+        // Validate arguments for builtin functions, i.e. the sum/prod reducers and field inversion
         if expr.is_builtin() {
-            self.validate_call_to_builtin(expr)?;
+            return self.validate_call_to_builtin(expr);
         }
 
         // Validate arguments for evaluator functions:
         //
-        // * Must be trace bindings or aliases of same
+        // * Must provide an argument for every parameter
+        // * Must be trace bindings or aliases of same, except for value parameters
         // * Must match the type signature of the callee
         if let Ok(ty) = callee_binding_ty {
-            if let BindingType::Function(FunctionType::Evaluator(ref params)) = ty.item {
-                for (arg, param) in expr.args.iter().zip(params.iter()) {
+            if let BindingType::Function(FunctionType::Evaluator(ref params, ref value_params)) =
+                ty.item
+            {
+                let arity = params.len() + value_params.len();
+                if expr.args.len() != arity {
+                    self.has_type_errors = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid call")
+                        .with_primary_label(
+                            expr.span(),
+                            format!(
+                                "the callee expects {} arguments, but got {}",
+                                arity,
+                                expr.args.len()
+                            ),
+                        )
+                        .with_secondary_label(ty.span(), "the callee is declared here")
+                        .emit();
+                    return ControlFlow::Continue(());
+                }
+                let (trace_args, value_args) = expr.args.split_at(params.len());
+                let sizes = trace_args
+                    .iter()
+                    .map(|arg| self.trace_argument_size(arg))
+                    .collect::<Vec<_>>();
+                // If any of the arguments is sized by a const parameter of the enclosing evaluator,
+                // the trace arguments can only be checked once it is instantiated during inlining
+                let is_deferred = sizes
+                    .iter()
+                    .any(|size| matches!(size, Some(size) if *size >= CONST_PARAM_PLACEHOLDER));
+                // If the callee has const parameters, infer their values from the arguments, and
+                // check the arguments against the callee instantiated with those values
+                let instantiated;
+                let params: &[TraceSegment] = if is_deferred {
+                    &[]
+                } else if params.iter().any(|segment| segment.is_generic()) {
+                    match infer_size_params(params, &sizes) {
+                        Ok(values) => {
+                            instantiated = params
+                                .iter()
+                                .map(|segment| segment.instantiate(&values))
+                                .collect::<Vec<_>>();
+                            &instantiated
+                        }
+                        Err(param) => {
+                            self.has_type_errors = true;
+                            self.diagnostics
+                                .diagnostic(Severity::Error)
+                                .with_message("invalid call")
+                                .with_primary_label(
+                                    expr.span(),
+                                    format!("unable to infer a value for the const parameter '{}' from these arguments", param),
+                                )
+                                .with_secondary_label(
+                                    param.span(),
+                                    "it determines the size of this binding",
+                                )
+                                .emit();
+                            return ControlFlow::Continue(());
+                        }
+                    }
+                } else {
+                    params
+                };
+                for (arg, param) in trace_args.iter().zip(params.iter()) {
                     self.validate_evaluator_argument(expr.span(), arg, param)?;
                 }
+                for (arg, param) in value_args.iter().zip(value_params.iter()) {
+                    self.validate_evaluator_value_argument(expr.span(), arg, param)?;
+                }
             }
         }
 
@@ -669,6 +1019,47 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         }
     }
 
+    fn visit_mut_if_expr(&mut self, expr: &mut IfExpr) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_comparison(&mut expr.condition)?;
+        self.visit_mut_scalar_expr(expr.then_expr.as_mut())?;
+        self.visit_mut_scalar_expr(expr.else_expr.as_mut())?;
+
+        // Validate that both branches produce the same type
+        if let (Ok(Some(lty)), Ok(Some(rty))) = (expr.then_expr.ty(), expr.else_expr.ty()) {
+            if lty != rty {
+                return self.type_mismatch(
+                    Some(&lty),
+                    expr.then_expr.span(),
+                    &rty,
+                    expr.else_expr.span(),
+                    expr.span(),
+                );
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_range_expr(&mut self, expr: &mut RangeExpr) -> ControlFlow<SemanticAnalysisError> {
+        // Range bounds which are not integers must refer to const parameters of the current evaluator
+        for bound in [expr.start, expr.end] {
+            if let RangeBound::Param(name) = bound {
+                if !self.const_params.contains_key(&name) {
+                    self.has_undefined_variables = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid range")
+                        .with_primary_label(
+                            name.span(),
+                            "this is not a const parameter of the enclosing evaluator",
+                        )
+                        .with_note("The bounds of a range must be integers, or const parameters of an evaluator, e.g. `0..N`")
+                        .emit();
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
     fn visit_mut_bounded_symbol_access(
         &mut self,
         expr: &mut BoundedSymbolAccess,
@@ -707,7 +1098,6 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         //
         // * This is an invalid trace access with offset in a boundary constraint
         // * This is an invalid periodic column access in a boundary constraint
-        // * This is an invalid public input access in an integrity constraint
         match &resolved_binding_ty.item {
             BindingType::TraceColumn(_) | BindingType::TraceParam(_) => {
                 if self.constraint_mode.is_boundary() && expr.offset > 0 {
@@ -722,9 +1112,6 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             ty @ BindingType::PeriodicColumn(_) if self.constraint_mode.is_boundary() => {
                 self.invalid_access_in_constraint(expr.span(), ty);
             }
-            ty @ BindingType::PublicInput(_) if self.constraint_mode.is_integrity() => {
-                self.invalid_access_in_constraint(expr.span(), ty);
-            }
             _ => (),
         }
 
@@ -802,6 +1189,76 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                     return ControlFlow::Continue(());
                 }
 
+                // References qualified by a module alias, e.g. `h::round`, are resolved to the
+                // item of the same name in the aliased module
+                let module = match self.aliases.get(&id.module) {
+                    Some(module) => *module,
+                    // Otherwise this identifier has already been resolved to a module-local item
+                    None if id.module == current_module => return ControlFlow::Continue(()),
+                    // Or to an imported item, or it is qualified by the name of an imported module,
+                    // in which case we must check that the item exists and is visible, just as we
+                    // do for items qualified by an alias
+                    None if self.imported.values().any(|qid| qid.module == id.module) => id.module,
+                    None => {
+                        self.has_undefined_variables = true;
+                        self.diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("reference to undefined module")
+                            .with_primary_label(
+                                id.module.span(),
+                                "no module alias by this name is declared in scope",
+                            )
+                            .with_note("Items may be referenced by a qualified name after aliasing their module on import, e.g. `use hash as h`")
+                            .emit();
+                        *expr = ResolvableIdentifier::Unresolved(id.item);
+                        return ControlFlow::Continue(());
+                    }
+                };
+
+                // The aliased module must be loaded, as it is imported by the current module
+                let imported_from = self.library.get(&module).unwrap();
+                let (export, kind) = match id.item {
+                    NamespacedIdentifier::Binding(name) => (
+                        imported_from.constants.get(&name).map(Export::Constant),
+                        "constant",
+                    ),
+                    NamespacedIdentifier::Function(name) => (
+                        imported_from.evaluators.get(&name).map(Export::Evaluator),
+                        "evaluator",
+                    ),
+                };
+                if let Some(export) = export.filter(|export| !export.visibility().is_public()) {
+                    self.has_undefined_variables = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("reference to private item")
+                        .with_primary_label(
+                            id.span(),
+                            format!("this {} is private to '{}'", kind, module),
+                        )
+                        .with_secondary_label(export.span(), "it is declared here")
+                        .with_note(
+                            "Only items declared with `pub` may be referenced from other modules",
+                        )
+                        .emit();
+                    *expr = ResolvableIdentifier::Unresolved(id.item);
+                    return ControlFlow::Continue(());
+                }
+                if export.is_none() {
+                    self.has_undefined_variables = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("reference to undefined item")
+                        .with_primary_label(
+                            id.span(),
+                            format!("no {} by this name is declared in '{}'", kind, module),
+                        )
+                        .emit();
+                    *expr = ResolvableIdentifier::Unresolved(id.item);
+                    return ControlFlow::Continue(());
+                }
+
+                *expr = ResolvableIdentifier::Resolved(QualifiedIdentifier::new(module, id.item));
                 ControlFlow::Continue(())
             }
             ResolvableIdentifier::Unresolved(namespaced_id) => {
@@ -815,7 +1272,8 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                         | BindingType::Vector(_)
                         | BindingType::PublicInput(_)
                         | BindingType::TraceColumn(_)
-                        | BindingType::TraceParam(_) => {
+                        | BindingType::TraceParam(_)
+                        | BindingType::ValueParam(_) => {
                             *expr = ResolvableIdentifier::Local(namespaced_id.id());
                         }
                         // These binding types are module-local declarations
@@ -842,11 +1300,8 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                 }
 
                 // If imported, resolve it to the imported module, and add it to the referenced set
-                if let Some((imported_id, imported_from)) =
-                    self.imported.get_key_value(&namespaced_id)
-                {
-                    let qualified_id = QualifiedIdentifier::new(*imported_from, *imported_id);
-                    *expr = ResolvableIdentifier::Resolved(qualified_id);
+                if let Some(imported) = self.imported.get(&namespaced_id) {
+                    *expr = ResolvableIdentifier::Resolved(*imported);
 
                     return ControlFlow::Continue(());
                 }
@@ -883,7 +1338,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
 }
 
 impl<'a> SemanticAnalysis<'a> {
-    /// Validate arguments for builtin functions, which currently consist only of the sum/prod reducers
+    /// Validate arguments for builtin functions, i.e. the sum/prod reducers and field inversion
     fn validate_call_to_builtin(&mut self, call: &Call) -> ControlFlow<SemanticAnalysisError> {
         match call.callee.as_ref().name() {
             // The known reducers - each takes a single argument, which must be an aggregate or comprehension
@@ -930,11 +1385,309 @@ impl<'a> SemanticAnalysis<'a> {
                     }
                 }
             }
+            // The field inverse takes a single scalar argument, which must be constant, but that
+            // is validated during constant propagation
+            symbols::Inv => match call.args.as_slice() {
+                [arg] => match self.expr_binding_type(arg) {
+                    Ok(binding_ty) => {
+                        if !binding_ty.ty().map(|t| t.is_scalar()).unwrap_or(false) {
+                            self.has_type_errors = true;
+                            self.diagnostics
+                                .diagnostic(Severity::Error)
+                                .with_message("invalid call")
+                                .with_primary_label(
+                                    call.span(),
+                                    "this function expects an argument of field element type",
+                                )
+                                .with_secondary_label(
+                                    arg.span(),
+                                    "but this argument is an aggregate",
+                                )
+                                .emit();
+                        }
+                    }
+                    Err(_) => {
+                        // We've already raised a diagnostic for this when visiting the access expression
+                        assert!(self.has_undefined_variables || self.has_type_errors);
+                    }
+                },
+                _ => {
+                    self.has_type_errors = true;
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid call")
+                        .with_primary_label(
+                            call.span(),
+                            format!(
+                                "the callee expects a single argument, but got {}",
+                                call.args.len()
+                            ),
+                        )
+                        .emit();
+                }
+            },
+            // Converts a vector of bits to the field element it represents
+            symbols::BitsToField => {
+                self.validate_builtin_arguments(call, &[Type::Vector(usize::MAX)], None);
+            }
+            // Folds a vector, starting from an initial value, using a lambda of two arguments,
+            // i.e. the accumulator and the current element
+            symbols::Fold => {
+                self.validate_builtin_arguments(
+                    call,
+                    &[Type::Vector(usize::MAX), Type::Felt],
+                    Some(2),
+                );
+            }
+            // The constraint builtins are handled by `validate_call_to_builtin_constraint`, so if
+            // we reach here, the call is not in a constraint position
+            symbols::IsBinary | symbols::OneHot | symbols::Decompose => {
+                self.invalid_constraint(
+                    call.span(),
+                    "this function is a constraint, and may only be used with `enf`",
+                )
+                .with_note(format!(
+                    "Constraint builtins are used like evaluators, e.g. `enf {}(...)`",
+                    call.callee.as_ref().name()
+                ))
+                .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
             other => unimplemented!("unrecognized builtin function: {}", other),
         }
         ControlFlow::Continue(())
     }
 
+    /// If `call` is to one of the builtin gadgets, e.g. `is_binary`, but a function with the same
+    /// name is declared in, or imported into, the current module, the call is rewritten to refer
+    /// to that function instead, so that existing programs are not affected by the builtins.
+    fn unshadow_builtin(&self, call: &mut Call) {
+        if !call.is_builtin() {
+            return;
+        }
+        let id = *call.callee.as_ref();
+        if !matches!(
+            id.name(),
+            symbols::IsBinary
+                | symbols::OneHot
+                | symbols::BitsToField
+                | symbols::Decompose
+                | symbols::Fold
+        ) {
+            return;
+        }
+        let name = NamespacedIdentifier::Function(id);
+        if self.locals.get(&name).is_some() || self.imported.contains_key(&name) {
+            call.callee = ResolvableIdentifier::Unresolved(name);
+            call.ty = None;
+        }
+    }
+
+    /// Visit the combining function of a call to `fold`, binding its parameters in the body.
+    ///
+    /// Both parameters are field elements, i.e. the accumulator and the current element, and the
+    /// body must produce a field element. As the fold is unrolled by substituting the parameters
+    /// in the body, the parameters may not shadow other local bindings.
+    fn visit_mut_fold_lambda(&mut self, expr: &mut Lambda) -> ControlFlow<SemanticAnalysisError> {
+        self.locals.enter();
+
+        let mut bound = HashSet::<Identifier>::default();
+        for param in expr.params.iter().copied() {
+            let name = NamespacedIdentifier::Binding(param);
+            let prev = bound
+                .get(&param)
+                .copied()
+                .or_else(|| self.locals.get_key(&name).map(|prev| prev.id()));
+            if let Some(prev) = prev {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid lambda")
+                    .with_primary_label(param.span(), "this name is already bound in this scope")
+                    .with_secondary_label(prev.span(), "previously bound here")
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::NameConflict(param.span()));
+            }
+            bound.insert(param);
+            self.locals.insert(name, BindingType::Local(Type::Felt));
+        }
+
+        self.visit_mut_scalar_expr(expr.body.as_mut())?;
+        if let Ok(Some(ty)) = expr.body.ty() {
+            if !ty.is_scalar() {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid lambda")
+                    .with_primary_label(
+                        expr.body.span(),
+                        format!("expected a field element, but this expression has type {ty}"),
+                    )
+                    .emit();
+            }
+        }
+
+        self.locals.exit();
+
+        ControlFlow::Continue(())
+    }
+
+    /// Validate arguments for the builtin functions which are themselves constraints, e.g. `is_binary`
+    ///
+    /// * `is_binary(x)` expects a field element
+    /// * `one_hot(v)` expects a vector
+    /// * `decompose(x, limbs, base)` expects a field element, a vector, and a field element.
+    ///
+    /// The base of a decomposition must also be constant, but that is validated during inlining
+    fn validate_call_to_builtin_constraint(
+        &mut self,
+        call: &Call,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let params: &[Type] = match call.callee.as_ref().name() {
+            symbols::IsBinary => &[Type::Felt],
+            symbols::OneHot => &[Type::Vector(usize::MAX)],
+            symbols::Decompose => &[Type::Felt, Type::Vector(usize::MAX), Type::Felt],
+            other => unreachable!("{} is not a constraint builtin", other),
+        };
+        self.validate_builtin_arguments(call, params, None);
+        ControlFlow::Continue(())
+    }
+
+    /// Validate that the arguments of `call` match `params`, where `Type::Vector(usize::MAX)`
+    /// represents a vector of any size.
+    ///
+    /// If `lambda_arity` is given, `params` is followed by a lambda with that many parameters.
+    fn validate_builtin_arguments(
+        &mut self,
+        call: &Call,
+        params: &[Type],
+        lambda_arity: Option<usize>,
+    ) {
+        let arity = params.len() + lambda_arity.is_some() as usize;
+        if call.args.len() != arity {
+            self.has_type_errors = true;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid call")
+                .with_primary_label(
+                    call.span(),
+                    format!(
+                        "the callee expects {} arguments, but got {}",
+                        arity,
+                        call.args.len()
+                    ),
+                )
+                .emit();
+            return;
+        }
+
+        for (arg, param) in call.args.iter().zip(params.iter()) {
+            let ty = match arg {
+                // Lambdas do not produce a value
+                Expr::Lambda(_) => None,
+                arg => match self.expr_binding_type(arg) {
+                    Ok(binding_ty) => binding_ty.ty(),
+                    Err(_) => {
+                        // We've already raised a diagnostic for this when visiting the access expression
+                        assert!(self.has_undefined_variables || self.has_type_errors);
+                        continue;
+                    }
+                },
+            };
+            let (is_valid, expected) = match param {
+                Type::Vector(_) => (
+                    matches!(ty, Some(Type::Vector(_))),
+                    "this function expects an argument of vector type",
+                ),
+                _ => (
+                    ty.map(|t| t.is_scalar()).unwrap_or(false),
+                    "this function expects an argument of field element type",
+                ),
+            };
+            if !is_valid {
+                self.has_type_errors = true;
+                let found = match ty {
+                    Some(ty) => format!("but this argument has type {ty}"),
+                    None => "but this argument has no value".to_string(),
+                };
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(call.span(), expected)
+                    .with_secondary_label(arg.span(), found)
+                    .emit();
+            }
+        }
+
+        if let Some(expected) = lambda_arity {
+            let arg = call.args.last().unwrap();
+            let found = match arg {
+                Expr::Lambda(ref lambda) if lambda.params.len() == expected => return,
+                Expr::Lambda(ref lambda) => {
+                    format!("but this lambda has {} parameters", lambda.params.len())
+                }
+                _ => "but this argument is not a lambda".to_string(),
+            };
+            self.has_type_errors = true;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid call")
+                .with_primary_label(
+                    call.span(),
+                    format!("this function expects a lambda of {expected} parameters, e.g. `|acc, x| acc + x`"),
+                )
+                .with_secondary_label(arg.span(), found)
+                .emit();
+        }
+    }
+
+    /// Value arguments may be any expression with the type expected by the callee, but as they
+    /// are substituted into the body of the callee when it is inlined, they may not reference
+    /// variables which are local to the caller, such as let-bound variables.
+    fn validate_evaluator_value_argument(
+        &mut self,
+        span: SourceSpan,
+        arg: &Expr,
+        param: &ValueParam,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let mut visitor = FindLocalVariable {
+            locals: &self.locals,
+        };
+        if let ControlFlow::Break(local) = visitor.visit_mut_expr(&mut arg.clone()) {
+            self.has_type_errors = true;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid call")
+                .with_primary_label(span, "invalid argument for evaluator function")
+                .with_secondary_label(
+                    local,
+                    "evaluator arguments cannot reference local variables",
+                )
+                .emit();
+            return ControlFlow::Continue(());
+        }
+
+        match self.expr_binding_type(arg) {
+            Ok(binding_ty) => {
+                let ty = binding_ty.ty();
+                if ty != Some(param.ty) {
+                    return self.type_mismatch(
+                        ty.as_ref(),
+                        arg.span(),
+                        &param.ty,
+                        param.span(),
+                        span,
+                    );
+                }
+            }
+            Err(_) => {
+                // We've already raised a diagnostic for this when visiting the access expression
+                assert!(self.has_undefined_variables || self.has_type_errors);
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
     fn validate_evaluator_argument(
         &mut self,
         span: SourceSpan,
@@ -1292,10 +2045,21 @@ impl<'a> SemanticAnalysis<'a> {
         // 1. That the constraint produces a scalar value
         // 2. That the expression is either an equality, or a call to an evaluator function
         //
+        if let ScalarExpr::Call(ref mut expr) = expr {
+            self.unshadow_builtin(expr);
+        }
+
         match expr {
             ScalarExpr::Binary(ref mut expr) if expr.op == BinaryOp::Eq => {
                 self.visit_mut_binary_expr(expr)
             }
+            // The constraint builtins are always resolved, so we only need to visit the arguments
+            ScalarExpr::Call(ref mut expr) if expr.is_builtin_constraint() => {
+                for arg in expr.args.iter_mut() {
+                    self.visit_mut_expr(arg)?;
+                }
+                self.validate_call_to_builtin_constraint(expr)
+            }
             ScalarExpr::Call(ref mut expr) => {
                 // Visit the call normally, so we can resolve the callee identifier
                 self.visit_mut_call(expr)?;
@@ -1306,10 +2070,22 @@ impl<'a> SemanticAnalysis<'a> {
                 match expr.callee {
                     ResolvableIdentifier::Resolved(callee) => {
                         match callee.id() {
+                            // The remaining builtins all produce a value, e.g. `sum`
+                            id @ NamespacedIdentifier::Function(_) if callee.is_builtin() => {
+                                self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
+                                    .with_note("Builtins which produce a value must be bound to a variable, e.g. `let x = sum(b)`")
+                                    .emit();
+                                ControlFlow::Break(SemanticAnalysisError::Invalid)
+                            }
                             id @ NamespacedIdentifier::Function(_) => {
-                                match self.locals.get_key_value(&id) {
+                                // Only calls resolved to the current module can refer to local functions
+                                let local = self
+                                    .locals
+                                    .get_key_value(&id)
+                                    .filter(|_| callee.module == self.current_module.unwrap());
+                                match local {
                                     // Binding is to a local evaluator
-                                    Some((_, BindingType::Function(FunctionType::Evaluator(_)))) => ControlFlow::Continue(()),
+                                    Some((_, BindingType::Function(FunctionType::Evaluator(..)))) => ControlFlow::Continue(()),
                                     // Binding is to a local non-evaluator function
                                     Some((local_name, _)) => {
                                         self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
@@ -1320,11 +2096,10 @@ impl<'a> SemanticAnalysis<'a> {
                                     None => {
                                         // If the call was resolved, it must be to an imported function,
                                         // and we will have already validated the reference
-                                        let (import_id, module_id) = self.imported.get_key_value(&id).unwrap();
-                                        let module = self.library.get(module_id).unwrap();
-                                        if module.evaluators.get(&id.id()).is_none() {
+                                        let module = self.library.get(&callee.module).unwrap();
+                                        if module.evaluators.get(callee.as_ref()).is_none() {
                                             self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
-                                                .with_secondary_label(import_id.span(), "the function imported here is not an evaluator")
+                                                .with_secondary_label(callee.span(), "the function imported here is not an evaluator")
                                                 .emit();
                                             return ControlFlow::Break(SemanticAnalysisError::Invalid);
                                         }
@@ -1405,6 +2180,21 @@ impl<'a> SemanticAnalysis<'a> {
             .with_primary_label(span, label)
     }
 
+    /// Raises a type error if `expr` is known to have a type other than felt
+    fn expect_felt(&mut self, expr: &ScalarExpr, expected_by: SourceSpan, label: &str) {
+        if let Ok(Some(ty)) = expr.ty() {
+            if !ty.is_scalar() {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("type mismatch")
+                    .with_primary_label(expr.span(), format!("this expression has type {ty}"))
+                    .with_secondary_label(expected_by, label)
+                    .emit();
+            }
+        }
+    }
+
     fn binding_mismatch(
         &mut self,
         inferred_type: &BindingType,
@@ -1466,10 +2256,135 @@ impl<'a> SemanticAnalysis<'a> {
             .emit();
     }
 
+    /// Resolves the expression defining the values of the periodic column `name`, and returns the
+    /// cycle length of the column, as given by the type of the expression.
+    ///
+    /// The expression itself is evaluated during constant propagation.
+    fn visit_periodic_column_definition(
+        &mut self,
+        name: Identifier,
+        definition: &mut Expr,
+    ) -> ControlFlow<SemanticAnalysisError, usize> {
+        // Track the items referenced by the definition separately from the rest of the module
+        let referenced = core::mem::take(&mut self.referenced);
+
+        self.visit_mut_expr(definition)?;
+
+        // Update the dependency graph for this periodic column
+        let current_item = QualifiedIdentifier::new(
+            self.current_module.unwrap(),
+            NamespacedIdentifier::Binding(name),
+        );
+        for (referenced_item, ref_type) in self.referenced.iter() {
+            let referenced_item = self.deps.add_node(*referenced_item);
+            self.deps.add_edge(current_item, referenced_item, *ref_type);
+        }
+
+        // Restore the original references metadata
+        self.referenced = referenced;
+
+        match self.expr_binding_type(definition) {
+            Ok(binding_ty) => match binding_ty.ty() {
+                Some(Type::Vector(n)) => ControlFlow::Continue(n),
+                _ => {
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid periodic column declaration")
+                        .with_primary_label(
+                            definition.span(),
+                            "the values of a periodic column must be a vector",
+                        )
+                        .emit();
+                    ControlFlow::Break(SemanticAnalysisError::Invalid)
+                }
+            },
+            // An invalid access has already been reported while visiting the definition
+            Err(_) => ControlFlow::Break(SemanticAnalysisError::Invalid),
+        }
+    }
+
+    /// Resolves the expression defining the value of the constant `name`, which must be a scalar.
+    ///
+    /// The expression itself is evaluated during constant propagation.
+    fn visit_constant_definition(
+        &mut self,
+        name: Identifier,
+        definition: &mut ScalarExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        // Track the items referenced by the definition separately from the rest of the module
+        let referenced = core::mem::take(&mut self.referenced);
+
+        self.visit_mut_scalar_expr(definition)?;
+
+        // Update the dependency graph for this constant
+        let current_item = QualifiedIdentifier::new(
+            self.current_module.unwrap(),
+            NamespacedIdentifier::Binding(name),
+        );
+        for (referenced_item, ref_type) in self.referenced.iter() {
+            let referenced_item = self.deps.add_node(*referenced_item);
+            self.deps.add_edge(current_item, referenced_item, *ref_type);
+        }
+
+        // Restore the original references metadata
+        self.referenced = referenced;
+
+        match definition.ty() {
+            Ok(Some(Type::Felt)) => ControlFlow::Continue(()),
+            Ok(_) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid constant declaration")
+                    .with_primary_label(
+                        definition.span(),
+                        "the value of a constant defined by an expression must be a scalar",
+                    )
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            // An invalid access has already been reported while visiting the definition
+            Err(_) => ControlFlow::Break(SemanticAnalysisError::Invalid),
+        }
+    }
+
+    /// Resolves the names referenced by the compile-time assertions of the current module.
+    ///
+    /// The assertions of a module are always evaluated, so the items they reference are tracked
+    /// by a special node in the dependency graph, which is treated as a root of the program.
+    fn visit_assertions(
+        &mut self,
+        assertions: &mut [Assertion],
+    ) -> ControlFlow<SemanticAnalysisError> {
+        // Track the items referenced by the assertions separately from the rest of the module
+        let referenced = core::mem::take(&mut self.referenced);
+
+        for assertion in assertions.iter_mut() {
+            self.visit_mut_comparison(&mut assertion.condition)?;
+        }
+
+        // Update the dependency graph for the assertions of this module
+        let current_item = QualifiedIdentifier::new(
+            self.current_module.unwrap(),
+            NamespacedIdentifier::Binding(Identifier::new(
+                SourceSpan::UNKNOWN,
+                Symbol::intern("$$assertions"),
+            )),
+        );
+        for (referenced_item, ref_type) in self.referenced.iter() {
+            let referenced_item = self.deps.add_node(*referenced_item);
+            self.deps.add_edge(current_item, referenced_item, *ref_type);
+        }
+
+        // Restore the original references metadata
+        self.referenced = referenced;
+
+        ControlFlow::Continue(())
+    }
+
     fn expr_binding_type(&self, expr: &Expr) -> Result<BindingType, InvalidAccessError> {
         match expr {
             Expr::Const(constant) => Ok(BindingType::Local(constant.ty())),
-            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(range.end - range.start))),
+            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(self.range_len(range)))),
             Expr::Vector(ref elems) => {
                 let mut binding_tys = Vec::with_capacity(elems.len());
                 for elem in elems.iter() {
@@ -1484,9 +2399,11 @@ impl<'a> SemanticAnalysis<'a> {
                 Ok(BindingType::Local(Type::Matrix(rows, columns)))
             }
             Expr::SymbolAccess(ref expr) => self.access_binding_type(expr),
-            Expr::Call(Call { ty: None, .. }) => Err(InvalidAccessError::InvalidBinding),
+            Expr::Call(Call { ty: None, .. }) | Expr::Lambda(_) => {
+                Err(InvalidAccessError::InvalidBinding)
+            }
             Expr::Call(Call { ty: Some(ty), .. }) => Ok(BindingType::Local(*ty)),
-            Expr::Binary(_) => Ok(BindingType::Local(Type::Felt)),
+            Expr::Binary(_) | Expr::If(_) => Ok(BindingType::Local(Type::Felt)),
             Expr::ListComprehension(ref lc) => {
                 match lc.ty {
                     Some(ty) => Ok(BindingType::Local(ty)),
@@ -1501,6 +2418,50 @@ impl<'a> SemanticAnalysis<'a> {
         }
     }
 
+    /// Returns the number of values produced by `range`, using the placeholder value of any
+    /// const parameters it refers to, as well as for an end bound which has yet to be inferred
+    fn range_len(&self, range: &RangeExpr) -> usize {
+        let bound = |bound| match bound {
+            RangeBound::Const(value) => value,
+            RangeBound::Param(name) => self
+                .const_params
+                .get(&name)
+                .copied()
+                .unwrap_or(CONST_PARAM_PLACEHOLDER),
+            RangeBound::Inferred => CONST_PARAM_PLACEHOLDER,
+        };
+        let len = bound(range.end).saturating_sub(bound(range.start));
+        // The length of a range which depends on a placeholder is itself a placeholder
+        if len >= CONST_PARAM_PLACEHOLDER || range.step == 1 {
+            len
+        } else {
+            (len + range.step - 1) / range.step
+        }
+    }
+
+    /// Returns the number of trace columns provided by `arg`, if it is a valid evaluator argument
+    fn trace_argument_size(&self, arg: &Expr) -> Option<usize> {
+        match arg {
+            Expr::SymbolAccess(ref access) => self
+                .access_binding_type(access)
+                .ok()
+                .and_then(|binding_ty| binding_ty.num_trace_columns()),
+            Expr::Vector(ref elems) => elems
+                .iter()
+                .map(|elem| {
+                    self.expr_binding_type(elem)
+                        .ok()
+                        .and_then(|binding_ty| binding_ty.num_trace_columns())
+                })
+                .sum(),
+            Expr::Const(Span {
+                item: ConstantExpr::Vector(ref elems),
+                ..
+            }) if elems.is_empty() => Some(0),
+            _ => None,
+        }
+    }
+
     fn access_binding_type(&self, expr: &SymbolAccess) -> Result<BindingType, InvalidAccessError> {
         let binding_ty = self.resolvable_binding_type(&expr.name)?;
         binding_ty.access(expr.access_type.clone())
@@ -1565,6 +2526,30 @@ impl<'a> SemanticAnalysis<'a> {
                         FunctionType::Function(vec![Type::Vector(usize::MAX)], Type::Felt);
                     Ok(Span::new(qid.span(), BindingType::Function(folder_ty)))
                 }
+                symbols::Inv => {
+                    let inv_ty = FunctionType::Function(vec![Type::Felt], Type::Felt);
+                    Ok(Span::new(qid.span(), BindingType::Function(inv_ty)))
+                }
+                symbols::BitsToField => {
+                    let bits_ty =
+                        FunctionType::Function(vec![Type::Vector(usize::MAX)], Type::Felt);
+                    Ok(Span::new(qid.span(), BindingType::Function(bits_ty)))
+                }
+                // The lambda given as the last argument has no type of its own, and is validated
+                // separately, see `validate_builtin_arguments`
+                symbols::Fold => {
+                    let fold_ty = FunctionType::Function(
+                        vec![Type::Vector(usize::MAX), Type::Felt],
+                        Type::Felt,
+                    );
+                    Ok(Span::new(qid.span(), BindingType::Function(fold_ty)))
+                }
+                // The constraint builtins behave like evaluators, i.e. they produce no value, but
+                // their arguments are validated separately, see `validate_call_to_builtin_constraint`
+                symbols::IsBinary | symbols::OneHot | symbols::Decompose => {
+                    let constraint_ty = FunctionType::Evaluator(vec![], vec![]);
+                    Ok(Span::new(qid.span(), BindingType::Function(constraint_ty)))
+                }
                 name => unimplemented!("unsupported builtin: {}", name),
             }
         } else {
@@ -1580,7 +2565,10 @@ impl<'a> SemanticAnalysis<'a> {
                     imported_from.evaluators.get(qid.as_ref()).map(|e| {
                         Span::new(
                             e.span(),
-                            BindingType::Function(FunctionType::Evaluator(e.params.clone())),
+                            BindingType::Function(FunctionType::Evaluator(
+                                e.params.clone(),
+                                e.value_params.clone(),
+                            )),
                         )
                     })
                 })
@@ -1593,6 +2581,24 @@ fn segment_id_to_name(id: TraceSegmentId) -> Symbol {
     match id {
         0 => symbols::Main,
         1 => symbols::Aux,
-        _ => unimplemented!(),
+        n => Symbol::intern(format!("$aux{n}")),
+    }
+}
+
+/// This visitor is used to find references to local variables, i.e. let-bound or comprehension-bound
+/// variables, in an expression. The span of the first such reference is returned via `Break`.
+struct FindLocalVariable<'a> {
+    locals: &'a LexicalScope<NamespacedIdentifier, BindingType>,
+}
+impl<'a> VisitMut<SourceSpan> for FindLocalVariable<'a> {
+    fn visit_mut_symbol_access(&mut self, expr: &mut SymbolAccess) -> ControlFlow<SourceSpan> {
+        if let ResolvableIdentifier::Local(id) = expr.name {
+            if let Some(BindingType::Local(_) | BindingType::Alias(_) | BindingType::Vector(_)) =
+                self.locals.get(&NamespacedIdentifier::Binding(id))
+            {
+                return ControlFlow::Break(expr.span());
+            }
+        }
+        ControlFlow::Continue(())
     }
 }
@@ -24,6 +24,22 @@ pub mod predefined {
     pub const Sum: Symbol = Symbol::new(3);
     /// The symbol `prod`
     pub const Prod: Symbol = Symbol::new(4);
+    /// The symbol `inv`
+    pub const Inv: Symbol = Symbol::new(5);
+    /// The symbol `enumerate`
+    pub const Enumerate: Symbol = Symbol::new(6);
+    /// The symbol `lookup`
+    pub const Lookup: Symbol = Symbol::new(7);
+    /// The symbol `is_binary`
+    pub const IsBinary: Symbol = Symbol::new(8);
+    /// The symbol `one_hot`
+    pub const OneHot: Symbol = Symbol::new(9);
+    /// The symbol `bits_to_field`
+    pub const BitsToField: Symbol = Symbol::new(10);
+    /// The symbol `decompose`
+    pub const Decompose: Symbol = Symbol::new(11);
+    /// The symbol `fold`
+    pub const Fold: Symbol = Symbol::new(12);
 
     pub(super) const __SYMBOLS: &[(Symbol, &str)] = &[
         (Main, "$main"),
@@ -31,6 +47,14 @@ pub mod predefined {
         (Builtin, "$builtin"),
         (Sum, "sum"),
         (Prod, "prod"),
+        (Inv, "inv"),
+        (Enumerate, "enumerate"),
+        (Lookup, "lookup"),
+        (IsBinary, "is_binary"),
+        (OneHot, "one_hot"),
+        (BitsToField, "bits_to_field"),
+        (Decompose, "decompose"),
+        (Fold, "fold"),
     ];
 }
 
@@ -4,7 +4,7 @@ use std::{
 };
 
 use air_pass::Pass;
-use miden_diagnostics::{DiagnosticsHandler, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, Span, Spanned};
 
 use crate::{
     ast::{visit::VisitMut, *},
@@ -21,7 +21,6 @@ use crate::{
 /// so it will panic if it encounters invalid constructions to help catch bugs in the semantic
 /// analysis pass, should they exist.
 pub struct ConstantPropagation<'a> {
-    #[allow(unused)]
     diagnostics: &'a DiagnosticsHandler,
     global: HashMap<QualifiedIdentifier, Span<ConstantExpr>>,
     local: LexicalScope<Identifier, Span<ConstantExpr>>,
@@ -55,13 +54,30 @@ impl<'a> ConstantPropagation<'a> {
     }
 
     fn run_visitor(&mut self, program: &mut Program) -> ControlFlow<SemanticAnalysisError> {
-        // Record all of the constant declarations
+        // Record all of the constant declarations whose values are given as literals
         for (name, constant) in program.constants.iter() {
-            assert_eq!(
-                self.global
-                    .insert(*name, Span::new(constant.span(), constant.value.clone())),
-                None
-            );
+            if constant.definition.is_none() {
+                assert_eq!(
+                    self.global
+                        .insert(*name, Span::new(constant.span(), constant.value.clone())),
+                    None
+                );
+            }
+        }
+
+        // Evaluate the values of constants defined by an expression
+        self.evaluate_constants(program)?;
+
+        // Evaluate the values of periodic columns defined by an expression
+        for column in program.periodic_columns.values_mut() {
+            if column.definition.is_some() {
+                self.evaluate_periodic_column(column)?;
+            }
+        }
+
+        // Check that all compile-time assertions hold
+        for assertion in program.assertions.iter_mut() {
+            self.evaluate_assertion(assertion)?;
         }
 
         // Visit all of the evaluators
@@ -74,6 +90,168 @@ impl<'a> ConstantPropagation<'a> {
         self.visit_mut_integrity_constraints(&mut program.integrity_constraints)
     }
 
+    /// Evaluates the expressions defining the values of constants, and records their values
+    ///
+    /// As a definition may reference constants which are themselves defined by an expression, we
+    /// evaluate the definitions whose references are known, until all of them are evaluated. If no
+    /// progress can be made, the remaining definitions depend on each other, or on values which
+    /// are not known at compile time.
+    fn evaluate_constants(&mut self, program: &mut Program) -> ControlFlow<SemanticAnalysisError> {
+        loop {
+            let mut pending = 0;
+            let mut progress = false;
+            for (name, constant) in program.constants.iter_mut() {
+                let Some(definition) = constant.definition.as_ref() else {
+                    continue;
+                };
+                let mut value = definition.clone();
+                self.visit_mut_scalar_expr(&mut value)?;
+                match value {
+                    ScalarExpr::Const(value) => {
+                        constant.value = ConstantExpr::Scalar(value.item);
+                        constant.definition = None;
+                        self.global
+                            .insert(*name, Span::new(constant.span(), constant.value.clone()));
+                        progress = true;
+                    }
+                    _ => pending += 1,
+                }
+            }
+
+            if pending == 0 {
+                return ControlFlow::Continue(());
+            }
+            if !progress {
+                for constant in program.constants.values() {
+                    if let Some(definition) = constant.definition.as_ref() {
+                        self.diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("invalid constant declaration")
+                            .with_primary_label(
+                                definition.span(),
+                                "the value of a constant must be known at compile time",
+                            )
+                            .with_note("A constant can only be defined in terms of other constants, and may not depend on itself")
+                            .emit();
+                    }
+                }
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
+        }
+    }
+
+    /// Evaluates the expression defining the values of `column`, and validates its cycle length
+    fn evaluate_periodic_column(
+        &mut self,
+        column: &mut PeriodicColumn,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let mut definition = column.definition.take().unwrap();
+        let span = definition.span();
+        self.visit_mut_expr(&mut definition)?;
+
+        let values = match definition {
+            Expr::Const(Span {
+                item: ConstantExpr::Vector(values),
+                ..
+            }) => values,
+            Expr::Range(ref range) if range.is_constant() => {
+                range.values().unwrap().map(|v| v as u64).collect()
+            }
+            _ => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid periodic column declaration")
+                    .with_primary_label(span, "the values of a periodic column must be constant")
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
+        };
+
+        if !PeriodicColumn::is_valid_period(values.len()) {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid periodic column declaration")
+                .with_primary_label(
+                    span,
+                    format!(
+                        "this expression has {} values, but periodic columns must have a cycle length which is a power of two, and at least 2",
+                        values.len()
+                    ),
+                )
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        column.values = values;
+        ControlFlow::Continue(())
+    }
+
+    /// Evaluates the condition of `assertion`, raising an error if it does not hold
+    fn evaluate_assertion(
+        &mut self,
+        assertion: &mut Assertion,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_comparison(&mut assertion.condition)?;
+
+        match assertion.condition.evaluate() {
+            Some(true) => ControlFlow::Continue(()),
+            Some(false) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("assertion failed")
+                    .with_primary_label(assertion.condition.span(), assertion.message.as_str())
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            None => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid assertion")
+                    .with_primary_label(
+                        assertion.condition.span(),
+                        "this condition is not known at compile-time",
+                    )
+                    .with_note("Assertions may only refer to constants, e.g. `assert A * B == 64 : \"message\"`")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+
+    /// Evaluates a call to `fold`, if the list and initial value are constant, and the body of
+    /// the combining function is constant at every step
+    fn evaluate_fold(&mut self, call: &Call) -> ControlFlow<SemanticAnalysisError, Option<u64>> {
+        let (elems, init, lambda) = match call.args.as_slice() {
+            [Expr::Const(Span {
+                item: ConstantExpr::Vector(ref elems),
+                ..
+            }), Expr::Const(Span {
+                item: ConstantExpr::Scalar(init),
+                ..
+            }), Expr::Lambda(ref lambda)] => (elems, *init, lambda),
+            _ => return ControlFlow::Continue(None),
+        };
+
+        let mut acc = init;
+        for elem in elems.iter().copied() {
+            self.local.enter();
+            for (param, value) in lambda.params.iter().copied().zip([acc, elem]) {
+                self.local
+                    .insert(param, Span::new(param.span(), ConstantExpr::Scalar(value)));
+            }
+            let mut body = lambda.body.as_ref().clone();
+            let result = self.visit_mut_scalar_expr(&mut body);
+            self.local.exit();
+            result?;
+            match body {
+                ScalarExpr::Const(value) => acc = value.item,
+                _ => return ControlFlow::Continue(None),
+            }
+        }
+
+        ControlFlow::Continue(Some(acc))
+    }
+
     fn try_fold_binary_expr(
         &mut self,
         expr: &mut BinaryExpr,
@@ -157,8 +335,26 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                     Err(err) => ControlFlow::Break(err),
                 }
             }
-            // While calls cannot be constant folded, arguments can be
-            ScalarExpr::Call(ref mut call) => self.visit_mut_call(call),
+            // Conditionals with a constant condition are replaced with the selected branch
+            ScalarExpr::If(ref mut if_expr) => {
+                self.visit_mut_if_expr(if_expr)?;
+                if let Some(selected) = try_fold_if_expr(if_expr) {
+                    *expr = selected;
+                }
+                ControlFlow::Continue(())
+            }
+            // Calls to `inv` with a constant argument can be folded, otherwise only the arguments can be
+            ScalarExpr::Call(ref mut call) => {
+                self.visit_mut_call(call)?;
+                match try_fold_inv(call) {
+                    Ok(Some(folded)) => {
+                        *expr = ScalarExpr::Const(folded);
+                        ControlFlow::Continue(())
+                    }
+                    Ok(None) => ControlFlow::Continue(()),
+                    Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                }
+            }
             // This cannot be constant folded
             ScalarExpr::BoundedSymbolAccess(_) => ControlFlow::Continue(()),
         }
@@ -259,11 +455,61 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                             }
                         }
                     }
+                    symbols::Inv => match try_fold_inv(call) {
+                        Ok(Some(folded)) => {
+                            *expr = Expr::Const(Span::new(
+                                folded.span(),
+                                ConstantExpr::Scalar(folded.item),
+                            ));
+                        }
+                        Ok(None) => (),
+                        Err(err) => {
+                            return ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err))
+                        }
+                    },
+                    symbols::BitsToField => {
+                        assert_eq!(call.args.len(), 1);
+                        if let Expr::Const(ref value) = &call.args[0] {
+                            let span = value.span();
+                            match &value.item {
+                                ConstantExpr::Vector(ref bits) => {
+                                    let folded =
+                                        bits.iter().enumerate().fold(0, |acc, (i, bit)| {
+                                            add(acc, mul(*bit, pow(2, i as u64)))
+                                        });
+                                    *expr =
+                                        Expr::Const(Span::new(span, ConstantExpr::Scalar(folded)));
+                                }
+                                invalid => {
+                                    panic!("bad argument to bits_to_field builtin: {:#?}", invalid)
+                                }
+                            }
+                        }
+                    }
+                    symbols::Fold => {
+                        assert_eq!(call.args.len(), 3);
+                        if let Some(folded) = self.evaluate_fold(call)? {
+                            *expr = Expr::Const(Span::new(span, ConstantExpr::Scalar(folded)));
+                        }
+                    }
+                    // Calls to the constraint builtins are expanded during inlining
+                    symbols::IsBinary | symbols::OneHot | symbols::Decompose => (),
                     invalid => unimplemented!("unknown builtin function: {}", invalid),
                 }
                 ControlFlow::Continue(())
             }
             Expr::Call(ref mut call) => self.visit_mut_call(call),
+            // The parameters of a lambda are never constant, but its body may refer to constants
+            Expr::Lambda(ref mut lambda) => {
+                let prev_live = core::mem::take(&mut self.live);
+                self.visit_mut_scalar_expr(lambda.body.as_mut())?;
+                let mut live = core::mem::take(&mut self.live);
+                for param in lambda.params.iter() {
+                    live.remove(param);
+                }
+                self.live = &prev_live | &live;
+                ControlFlow::Continue(())
+            }
             Expr::Binary(ref mut binary_expr) => match self.try_fold_binary_expr(binary_expr) {
                 Ok(maybe_folded) => {
                     if let Some(folded) = maybe_folded {
@@ -276,6 +522,15 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                 }
                 Err(err) => ControlFlow::Break(err),
             },
+            // Conditionals with a constant condition are replaced with the selected branch
+            Expr::If(ref mut if_expr) => {
+                self.visit_mut_if_expr(if_expr)?;
+                // The branches have already been folded, so the selected branch is as well
+                if let Some(selected) = try_fold_if_expr(if_expr) {
+                    *expr = Expr::try_from(selected).unwrap();
+                }
+                ControlFlow::Continue(())
+            }
             // Ranges are constant
             Expr::Range(_) => ControlFlow::Continue(()),
             // Visit vector elements, and promote the vector to `Expr::Const` if possible
@@ -386,7 +641,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                         ..
                     }) => rows.len(),
                     Expr::Const(_) => panic!("expected iterable constant, got scalar"),
-                    Expr::Range(range) => range.end - range.start,
+                    Expr::Range(range) => range.values().unwrap().len(),
                     _ => unreachable!(),
                 };
 
@@ -412,8 +667,8 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 self.local.insert(binding, Span::new(span, value));
                             }
                             Expr::Range(range) => {
-                                assert!(range.end > range.start + step);
-                                let value = ConstantExpr::Scalar((range.start + step) as u64);
+                                let value = range.values().unwrap().nth(step).unwrap();
+                                let value = ConstantExpr::Scalar(value as u64);
                                 self.local.insert(binding, Span::new(span, value));
                             }
                             _ => unreachable!(),
@@ -487,7 +742,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 self.local.insert(expr.name, value.clone());
                             }
                             Expr::Range(ref range) => {
-                                let vector = range.item.clone().map(|i| i as u64).collect();
+                                let vector = range.values().unwrap().map(|i| i as u64).collect();
                                 self.local.insert(
                                     expr.name,
                                     Span::new(range.span(), ConstantExpr::Vector(vector)),
@@ -524,6 +779,19 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                     self.visit_mut_list_comprehension(expr)?;
                     self.in_constraint_comprehension = false;
                 }
+                Statement::EnforceMatch(ref mut expr) => {
+                    self.in_constraint_comprehension = true;
+                    for arm in expr.iter_mut() {
+                        self.visit_mut_list_comprehension(arm)?;
+                    }
+                    self.in_constraint_comprehension = false;
+                }
+                Statement::BusInteraction(ref mut expr) => {
+                    self.visit_mut_bus_interaction(expr)?;
+                }
+                Statement::Lookup(ref mut expr) => {
+                    self.visit_mut_lookup(expr)?;
+                }
                 Statement::Expr(ref mut expr) => {
                     self.visit_mut_expr(expr)?;
                 }
@@ -558,13 +826,19 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
     }
 }
 
+/// The modulus of the field over which AirScript programs are evaluated, i.e. `2^64 - 2^32 + 1`
+pub(crate) const MODULUS: u64 = 0xffff_ffff_0000_0001;
+
 /// This function attempts to folds a binary operator expression into a constant value.
 ///
-/// If the operands are both constant, the operator is applied, and if the result does not
-/// overflow/underflow, then `Ok(Some)` is returned with the result of the evaluation.
+/// If the operands are both constant, the operator is applied in the field, i.e. all
+/// arithmetic is performed modulo [MODULUS], and `Ok(Some)` is returned with the result of
+/// the evaluation. In particular, this means that `0 - n` is folded to `p - n`, so that
+/// negated constants are represented as ordinary constant values.
 ///
-/// If the operands are not both constant, or the operation would overflow/underflow, then
-/// `Ok(None)` is returned.
+/// Likewise, division is folded to multiplication by the field inverse of the divisor.
+///
+/// If the operands are not both constant, then `Ok(None)` is returned.
 ///
 /// If the operands are constant, or there is some validation error with the expression,
 /// `Err(InvalidExprError)` will be returned.
@@ -574,17 +848,21 @@ pub(crate) fn try_fold_binary_expr(
     // If both operands are constant, fold
     if let (ScalarExpr::Const(l), ScalarExpr::Const(r)) = (expr.lhs.as_ref(), expr.rhs.as_ref()) {
         let folded = match expr.op {
-            BinaryOp::Add => l.item.checked_add(r.item),
-            BinaryOp::Sub => l.item.checked_sub(r.item),
-            BinaryOp::Mul => l.item.checked_mul(r.item),
-            BinaryOp::Exp => match r.item.try_into() {
-                Ok(exp) => l.item.checked_pow(exp),
+            BinaryOp::Add => add(l.item, r.item),
+            BinaryOp::Sub => sub(l.item, r.item),
+            BinaryOp::Mul => mul(l.item, r.item),
+            BinaryOp::Div => match inverse(r.item) {
+                Some(inv) => mul(l.item, inv),
+                None => return Err(InvalidExprError::DivisionByZero(expr.rhs.span())),
+            },
+            BinaryOp::Exp => match u32::try_from(r.item) {
+                Ok(exp) => pow(l.item, exp as u64),
                 Err(_) => return Err(InvalidExprError::InvalidExponent(expr.span())),
             },
             // This op cannot be folded
             BinaryOp::Eq => return Ok(None),
         };
-        Ok(folded.map(|v| Span::new(expr.span(), v)))
+        Ok(Some(Span::new(expr.span(), folded)))
     } else {
         // If we observe a non-constant power in an exponentiation operation, raise an error
         if expr.op == BinaryOp::Exp && !expr.rhs.is_constant() {
@@ -594,3 +872,77 @@ pub(crate) fn try_fold_binary_expr(
         }
     }
 }
+
+/// This function attempts to fold a call to the `inv` builtin into a constant value.
+///
+/// Returns `Ok(None)` if `call` is not a call to `inv`, or its argument is not constant.
+///
+/// Returns `Err(InvalidExprError)` if the argument is zero, as zero has no inverse.
+pub(crate) fn try_fold_inv(call: &Call) -> Result<Option<Span<u64>>, InvalidExprError> {
+    if !call.is_builtin() || call.callee.as_ref().name() != symbols::Inv {
+        return Ok(None);
+    }
+    match call.args.as_slice() {
+        [Expr::Const(Span {
+            item: ConstantExpr::Scalar(value),
+            ..
+        })] => match inverse(*value) {
+            Some(inv) => Ok(Some(Span::new(call.span(), inv))),
+            None => Err(InvalidExprError::DivisionByZero(call.args[0].span())),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// This function attempts to fold a conditional expression into the branch selected by its
+/// condition.
+///
+/// Returns `None` if the condition is not constant.
+pub(crate) fn try_fold_if_expr(expr: &IfExpr) -> Option<ScalarExpr> {
+    expr.condition.evaluate().map(|selected| {
+        if selected {
+            expr.then_expr.as_ref().clone()
+        } else {
+            expr.else_expr.as_ref().clone()
+        }
+    })
+}
+
+/// Adds `a` and `b` in the field
+fn add(a: u64, b: u64) -> u64 {
+    ((a as u128 + b as u128) % MODULUS as u128) as u64
+}
+
+/// Subtracts `b` from `a` in the field
+fn sub(a: u64, b: u64) -> u64 {
+    let (a, b) = ((a % MODULUS) as u128, (b % MODULUS) as u128);
+    ((a + MODULUS as u128 - b) % MODULUS as u128) as u64
+}
+
+/// Multiplies `a` and `b` in the field
+fn mul(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+/// Raises `base` to the power `exp` in the field
+pub(crate) fn pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut acc = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul(acc, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    acc % MODULUS
+}
+
+/// Computes the multiplicative inverse of `value` in the field, i.e. `value^(p - 2)`.
+///
+/// Returns `None` if `value` is zero (modulo `p`), as it has no inverse.
+fn inverse(value: u64) -> Option<u64> {
+    match value % MODULUS {
+        0 => None,
+        value => Some(pow(value, MODULUS - 2)),
+    }
+}
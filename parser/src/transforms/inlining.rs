@@ -4,7 +4,7 @@ use std::{
 };
 
 use air_pass::Pass;
-use miden_diagnostics::{DiagnosticsHandler, SourceSpan, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
 use crate::{
     ast::{visit::VisitMut, *},
@@ -56,15 +56,13 @@ use super::constant_propagation;
 /// be observed at this stage of compilation (e.g. no references to constant declarations, no
 /// undefined variables, expressions are well-typed, etc.).
 pub struct Inlining<'a> {
-    // This may be unused for now, but it's helpful to assume its needed in case we want it in the future
-    #[allow(unused)]
     diagnostics: &'a DiagnosticsHandler,
     /// The name of the root module
     root: Identifier,
     /// The global trace segment configuration
     trace: Vec<TraceSegment>,
-    /// The random_values declaration
-    random_values: Option<RandomValues>,
+    /// The random_values declarations
+    random_values: BTreeMap<TraceSegmentId, RandomValues>,
     /// The public_inputs declaration
     public_inputs: BTreeMap<Identifier, PublicInput>,
     /// All local/global bindings in scope
@@ -120,6 +118,7 @@ impl<'p> Pass for Inlining<'p> {
                     offset: 0,
                     size: segment.size,
                     ty: Type::Vector(segment.size),
+                    column_type: None,
                 }),
             );
             for binding in segment.bindings.iter().copied() {
@@ -132,12 +131,13 @@ impl<'p> Pass for Inlining<'p> {
                         offset: binding.offset,
                         size: binding.size,
                         ty: binding.ty,
+                        column_type: binding.column_type,
                     }),
                 );
             }
         }
         // Random values..
-        if let Some(rv) = program.random_values.as_ref() {
+        for rv in program.random_values.values() {
             self.bindings.insert(
                 rv.name,
                 BindingType::RandomValue(RandBinding::new(
@@ -155,10 +155,8 @@ impl<'p> Pass for Inlining<'p> {
         }
         // Public inputs..
         for input in program.public_inputs.values() {
-            self.bindings.insert(
-                input.name,
-                BindingType::PublicInput(Type::Vector(input.size)),
-            );
+            self.bindings
+                .insert(input.name, BindingType::PublicInput(input.ty()));
         }
         // For periodic columns, we register the imported item, but do not add any to the local bindings.
         for (name, periodic) in program.periodic_columns.iter() {
@@ -181,7 +179,7 @@ impl<'a> Inlining<'a> {
             diagnostics,
             root: Identifier::new(SourceSpan::UNKNOWN, crate::symbols::Main),
             trace: vec![],
-            random_values: None,
+            random_values: Default::default(),
             public_inputs: Default::default(),
             bindings: Default::default(),
             let_bound: Default::default(),
@@ -203,6 +201,13 @@ impl<'a> Inlining<'a> {
         Identifier::new(span, crate::Symbol::intern(format!("%lc{}", id)))
     }
 
+    /// Generate a new variable to bind the vector argument of a builtin, e.g. `one_hot`
+    fn next_builtin_ident(&mut self, span: SourceSpan) -> Identifier {
+        let id = self.next_ident;
+        self.next_ident += 1;
+        Identifier::new(span, crate::Symbol::intern(format!("%builtin{}", id)))
+    }
+
     /// Inline/expand all of the statements in the `boundary_constraints` section
     fn expand_boundary_constraints(
         &mut self,
@@ -255,12 +260,21 @@ impl<'a> Inlining<'a> {
     /// Expand a single statement into one or more statements which are fully-expanded
     fn expand_statement(
         &mut self,
-        statement: Statement,
+        mut statement: Statement,
     ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        // Let-bound values are handled by `expand_let`, as it is also called directly
+        if !matches!(statement, Statement::Let(_)) && self.bind_value_builtins(&mut statement)? {
+            return self.expand_statement(statement);
+        }
+
         match statement {
             // Expanding a let requires special treatment, as let-bound values may be inlined as a block
             // of statements, which requires us to rewrite the `let` into a `let` tree
             Statement::Let(expr) => self.expand_let(expr),
+            // A call to a constraint builtin is expanded into the constraints it represents
+            Statement::Enforce(ScalarExpr::Call(call)) if call.is_builtin_constraint() => {
+                self.expand_builtin_constraint(call)
+            }
             // A call to an evaluator function is expanded by inlining the function itself at the call site
             Statement::Enforce(ScalarExpr::Call(call)) => self.expand_evaluator_callsite(call),
             // Constraints are inlined by expanding the constraint expression
@@ -272,10 +286,21 @@ impl<'a> Inlining<'a> {
                 self.in_comprehension_constraint = in_cc;
                 result
             }
+            // The arms of a match are inlined as if each was its own constraint comprehension
+            Statement::EnforceMatch(expr) => {
+                let mut statements = vec![];
+                for arm in expr.arms.into_iter().chain(expr.default) {
+                    statements.append(&mut self.expand_statement(Statement::EnforceAll(arm))?);
+                }
+                Ok(statements)
+            }
             // Conditional constraints are expanded like regular constraints, except the selector is applied
             // to all constraints in the expansion.
             Statement::EnforceIf(expr, mut selector) => {
                 let mut statements = match expr {
+                    ScalarExpr::Call(call) if call.is_builtin_constraint() => {
+                        self.expand_builtin_constraint(call)?
+                    }
                     ScalarExpr::Call(call) => self.expand_evaluator_callsite(call)?,
                     expr => self.expand_constraint(expr)?,
                 };
@@ -291,6 +316,27 @@ impl<'a> Inlining<'a> {
                 }
                 Ok(statements)
             }
+            // Bus interactions are expanded by expanding the tuple values and the selector
+            Statement::BusInteraction(mut expr) => {
+                for arg in expr.args.iter_mut() {
+                    self.rewrite_scalar_expr(arg)?;
+                }
+                if let Some(selector) = expr.selector.as_mut() {
+                    self.rewrite_scalar_expr(selector)?;
+                }
+                Ok(vec![Statement::BusInteraction(expr)])
+            }
+            // Lookups are expanded by expanding the table and query tuples, the multiplicity, and the selector
+            Statement::Lookup(mut expr) => {
+                for value in expr.table.iter_mut().chain(expr.query.iter_mut()) {
+                    self.rewrite_scalar_expr(value)?;
+                }
+                self.rewrite_scalar_expr(&mut expr.multiplicity)?;
+                if let Some(selector) = expr.selector.as_mut() {
+                    self.rewrite_scalar_expr(selector)?;
+                }
+                Ok(vec![Statement::Lookup(expr)])
+            }
             // Expression statements are introduced during inlining, and are always already expanded,
             // but they are recursively visited to apply rewrites
             Statement::Expr(mut expr) => {
@@ -319,12 +365,22 @@ impl<'a> Inlining<'a> {
     /// * If a let-bound variable is dead (i.e. has no references), then the let is elided,
     /// by replacing it with the result of expanding its body
     fn expand_let(&mut self, expr: Let) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        let mut statement = Statement::Let(expr);
+        if self.bind_value_builtins(&mut statement)? {
+            return self.expand_statement(statement);
+        }
+        let Statement::Let(expr) = statement else {
+            unreachable!()
+        };
         let span = expr.span();
         let name = expr.name;
         let body = expr.body;
 
         // Visit the let-bound expression first, since it determines how the rest of the process goes
-        let mut statements = match expr.value {
+        //
+        // The value is never a constraint, even when the let is in a constraint comprehension
+        let in_cc = core::mem::replace(&mut self.in_comprehension_constraint, false);
+        let statements = match expr.value {
             // When expanding a call in this context, we're expecting a single
             // statement of either `Expr` or `Let` type, as calls to pure functions
             // can never contain constraints.
@@ -332,17 +388,18 @@ impl<'a> Inlining<'a> {
             // In the case where a `Let` is produced, we'll sink the current
             // let to the end of its body, so that it appears that the current
             // let came after the expansion point.
-            Expr::Call(call) => self.expand_call(call)?,
+            Expr::Call(call) => self.expand_call(call),
             // Same as above, but for list comprehensions.
             //
             // The rules for expansion are the same.
-            Expr::ListComprehension(lc) => self.expand_comprehension(lc)?,
+            Expr::ListComprehension(lc) => self.expand_comprehension(lc),
             // Other expressions we visit just to expand rewrites
-            mut value => {
-                self.rewrite_expr(&mut value)?;
-                vec![Statement::Expr(value)]
-            }
+            mut value => self
+                .rewrite_expr(&mut value)
+                .map(|_| vec![Statement::Expr(value)]),
         };
+        self.in_comprehension_constraint = in_cc;
+        let mut statements = statements?;
 
         // Since the let-bound expression may have expanded to a nested `let` tree,
         // ultimately terminating a value expression of some kind, it is necessary to
@@ -416,6 +473,58 @@ impl<'a> Inlining<'a> {
         }
     }
 
+    /// Calls to builtins which produce a value, e.g. `sum` or `bits_to_field`, are expanded into a
+    /// `let` tree, so they can only be expanded where they are the value of a `let`.
+    ///
+    /// This binds each such call nested in `statement` to a generated variable, by wrapping the
+    /// statement in a `let` for each call, and returns true if any call was found.
+    fn bind_value_builtins(
+        &mut self,
+        statement: &mut Statement,
+    ) -> Result<bool, SemanticAnalysisError> {
+        let mut visitor = BindValueBuiltinsVisitor {
+            inliner: self,
+            bound: vec![],
+        };
+        let result = match statement {
+            // Evaluator calls and builtin constraints are expanded along with their arguments
+            Statement::Enforce(ScalarExpr::Call(_)) => ControlFlow::Continue(()),
+            Statement::EnforceIf(ScalarExpr::Call(_), ref mut selector) => {
+                visitor.visit_mut_scalar_expr(selector)
+            }
+            // A call which is the value of a let is expanded in place, but not its arguments
+            Statement::Let(Let {
+                value: Expr::Call(ref mut call),
+                ..
+            }) => call
+                .args
+                .iter_mut()
+                .try_for_each(|arg| visitor.visit_mut_expr(arg)),
+            Statement::Let(ref mut expr) => visitor.visit_mut_expr(&mut expr.value),
+            // Comprehensions are visited once unrolled
+            Statement::EnforceAll(_) | Statement::EnforceMatch(_) => ControlFlow::Continue(()),
+            _ => visitor.visit_mut_statement(statement),
+        };
+        if let ControlFlow::Break(err) = result {
+            return Err(err);
+        }
+
+        let bound = visitor.bound;
+        if bound.is_empty() {
+            return Ok(false);
+        }
+        let span = statement.span();
+        let mut wrapped = core::mem::replace(
+            statement,
+            Statement::Expr(Expr::Const(Span::new(span, ConstantExpr::Scalar(0)))),
+        );
+        for (name, call) in bound.into_iter().rev() {
+            wrapped = Statement::Let(Let::new(span, name, call, vec![wrapped]));
+        }
+        *statement = wrapped;
+        Ok(true)
+    }
+
     /// Expand a call to a pure function (including builtin list folding functions)
     fn expand_call(&mut self, mut call: Call) -> Result<Vec<Statement>, SemanticAnalysisError> {
         if call.is_builtin() {
@@ -428,6 +537,76 @@ impl<'a> Inlining<'a> {
                     assert_eq!(call.args.len(), 1);
                     self.expand_fold(BinaryOp::Mul, call.args.pop().unwrap())
                 }
+                // The result is the composition of the bits in base 2, i.e. `sum(b_i * 2^i)`
+                symbols::BitsToField => {
+                    assert_eq!(call.args.len(), 1);
+                    let span = call.span();
+                    let mut bits = call.args.pop().unwrap();
+                    let (name, elems) = self.bind_builtin_vector(span, &mut bits);
+                    let composed = compose(span, elems, 2);
+                    self.expand_let(Let::new(
+                        span,
+                        name,
+                        bits,
+                        vec![Statement::Expr(
+                            composed
+                                .try_into()
+                                .map_err(SemanticAnalysisError::InvalidExpr)?,
+                        )],
+                    ))
+                }
+                // The fold is unrolled into a sequence of `let`s, one per element, each binding the
+                // body of the lambda with the accumulator and element substituted for its parameters
+                symbols::Fold => {
+                    assert_eq!(call.args.len(), 3);
+                    let span = call.span();
+                    let Some(Expr::Lambda(lambda)) = call.args.pop() else {
+                        unreachable!("expected lambda as the last argument to fold")
+                    };
+                    let init = call.args.pop().unwrap();
+                    let mut list = call.args.pop().unwrap();
+                    let (name, elems) = self.bind_builtin_vector(span, &mut list);
+
+                    let mut acc = self.next_builtin_ident(span);
+                    let mut steps = vec![(acc, init)];
+                    for elem in elems {
+                        let values = HashMap::from_iter([
+                            (lambda.params[0], local_access(span, acc)),
+                            (
+                                lambda.params[1],
+                                elem.try_into()
+                                    .map_err(SemanticAnalysisError::InvalidExpr)?,
+                            ),
+                        ]);
+                        let mut body = lambda.body.as_ref().clone();
+                        let mut visitor = RewriteIterableBindingsVisitor { values: &values };
+                        if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut body) {
+                            return Err(err);
+                        }
+                        acc = self.next_builtin_ident(span);
+                        steps.push((
+                            acc,
+                            body.try_into()
+                                .map_err(SemanticAnalysisError::InvalidExpr)?,
+                        ));
+                    }
+                    let folded = steps.into_iter().rev().fold(
+                        vec![Statement::Expr(local_access(span, acc))],
+                        |body, (name, value)| {
+                            vec![Statement::Let(Let::new(span, name, value, body))]
+                        },
+                    );
+                    self.expand_let(Let::new(span, name, list, folded))
+                }
+                symbols::Inv => {
+                    assert_eq!(call.args.len(), 1);
+                    let mut expr = ScalarExpr::Call(call);
+                    self.rewrite_scalar_expr(&mut expr)?;
+                    Ok(vec![Statement::Expr(
+                        expr.try_into()
+                            .map_err(SemanticAnalysisError::InvalidExpr)?,
+                    )])
+                }
                 other => unimplemented!("unhandled builtin: {}", other),
             }
         } else {
@@ -435,6 +614,134 @@ impl<'a> Inlining<'a> {
         }
     }
 
+    /// Expand a call to a builtin which is itself a constraint into the constraints it represents:
+    ///
+    /// * `is_binary(x)` expands to `enf x^2 = x`
+    /// * `one_hot(v)` expands to `enf v_i^2 = v_i` for each element of `v`, and `enf sum(v) = 1`
+    /// * `decompose(x, limbs, base)` expands to `enf x = sum(limbs_i * base^i)`
+    ///
+    /// Vector arguments are bound to a generated variable, so that the expansion of the resulting
+    /// `let` takes care of arguments which are comprehensions, let-bound vectors, or trace bindings.
+    fn expand_builtin_constraint(
+        &mut self,
+        mut call: Call,
+    ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        let span = call.span();
+        match call.callee.as_ref().name() {
+            symbols::IsBinary => {
+                assert_eq!(call.args.len(), 1);
+                let x: ScalarExpr = call
+                    .args
+                    .pop()
+                    .unwrap()
+                    .try_into()
+                    .map_err(SemanticAnalysisError::InvalidExpr)?;
+                self.expand_constraint(is_binary(span, x))
+            }
+            symbols::OneHot => {
+                assert_eq!(call.args.len(), 1);
+                let mut list = call.args.pop().unwrap();
+                let (name, elems) = self.bind_builtin_vector(span, &mut list);
+                let mut body = elems
+                    .iter()
+                    .cloned()
+                    .map(|elem| Statement::Enforce(is_binary(span, elem)))
+                    .collect::<Vec<_>>();
+                let sum = elems
+                    .into_iter()
+                    .reduce(|acc, elem| {
+                        ScalarExpr::Binary(BinaryExpr::new(span, BinaryOp::Add, acc, elem))
+                    })
+                    .unwrap();
+                body.push(Statement::Enforce(ScalarExpr::Binary(BinaryExpr::new(
+                    span,
+                    BinaryOp::Eq,
+                    sum,
+                    ScalarExpr::Const(Span::new(span, 1)),
+                ))));
+                self.expand_let(Let::new(span, name, list, body))
+            }
+            symbols::Decompose => {
+                assert_eq!(call.args.len(), 3);
+                let base = call.args.pop().unwrap();
+                let mut limbs = call.args.pop().unwrap();
+                let x: ScalarExpr = call
+                    .args
+                    .pop()
+                    .unwrap()
+                    .try_into()
+                    .map_err(SemanticAnalysisError::InvalidExpr)?;
+                let base = match base {
+                    Expr::Const(Span {
+                        item: ConstantExpr::Scalar(base),
+                        ..
+                    }) if base > 1 => base,
+                    invalid => {
+                        self.diagnostics
+                            .diagnostic(Severity::Error)
+                            .with_message("invalid call")
+                            .with_primary_label(
+                                invalid.span(),
+                                "the base of a decomposition must be a constant, and at least 2",
+                            )
+                            .emit();
+                        return Err(SemanticAnalysisError::Invalid);
+                    }
+                };
+                let (name, elems) = self.bind_builtin_vector(span, &mut limbs);
+                let composed = compose(span, elems, base);
+                let body = vec![Statement::Enforce(ScalarExpr::Binary(BinaryExpr::new(
+                    span,
+                    BinaryOp::Eq,
+                    x,
+                    composed,
+                )))];
+                self.expand_let(Let::new(span, name, limbs, body))
+            }
+            other => unreachable!("{} is not a constraint builtin", other),
+        }
+    }
+
+    /// Generates a variable to bind the vector argument `list` of a builtin, and returns it along
+    /// with an access to each element of the vector via that variable.
+    ///
+    /// If `list` is a let-bound variable, it is replaced with its value, as is done when expanding
+    /// a list folding operation.
+    fn bind_builtin_vector(
+        &mut self,
+        span: SourceSpan,
+        list: &mut Expr,
+    ) -> (Identifier, Vec<ScalarExpr>) {
+        while let Expr::SymbolAccess(SymbolAccess {
+            access_type: AccessType::Default,
+            ref name,
+            ..
+        }) = list
+        {
+            match self.let_bound.get(name.as_ref()).cloned() {
+                Some(value) => *list = value,
+                None => break,
+            }
+        }
+        let len = match self.expr_binding_type(list).ok().and_then(|ty| ty.ty()) {
+            Some(Type::Vector(len)) => len,
+            invalid => unreachable!("expected vector argument to builtin, got {:?}", invalid),
+        };
+        let name = self.next_builtin_ident(span);
+        let elems = (0..len)
+            .map(|i| {
+                ScalarExpr::SymbolAccess(SymbolAccess {
+                    span,
+                    name: ResolvableIdentifier::Local(name),
+                    access_type: AccessType::Index(i),
+                    offset: 0,
+                    ty: Some(Type::Felt),
+                })
+            })
+            .collect();
+        (name, elems)
+    }
+
     /// Expand a list folding operation (e.g. sum/prod) over an expression of aggregate type into an equivalent expression tree
     fn expand_fold(
         &mut self,
@@ -556,6 +863,12 @@ impl<'a> Inlining<'a> {
                 self.rewrite_scalar_expr(binary_expr.lhs.as_mut())?;
                 self.rewrite_scalar_expr(binary_expr.rhs.as_mut())?;
             }
+            // Conditionals must be resolved by now, see `rewrite_scalar_expr`
+            Expr::If(ref mut if_expr) => {
+                let mut selected = ScalarExpr::If(if_expr.clone());
+                self.rewrite_scalar_expr(&mut selected)?;
+                *expr = Expr::try_from(selected).map_err(SemanticAnalysisError::InvalidExpr)?;
+            }
             Expr::SymbolAccess(ref mut access) => {
                 if let Some(rewrite) = self.get_trace_access_rewrite(access) {
                     *access = rewrite;
@@ -572,6 +885,8 @@ impl<'a> Inlining<'a> {
                     self.rewrite_expr(expr)?;
                 }
             }
+            // Lambda rewrites happen when the fold they belong to is expanded
+            Expr::Lambda(_) => (),
         }
         Ok(())
     }
@@ -590,25 +905,71 @@ impl<'a> Inlining<'a> {
                 }
                 Ok(())
             }
-            ScalarExpr::Binary(BinaryExpr {
-                op,
-                ref mut lhs,
-                ref mut rhs,
-                ..
-            }) => {
-                self.rewrite_scalar_expr(lhs.as_mut())?;
-                self.rewrite_scalar_expr(rhs.as_mut())?;
-                match op {
-                    BinaryOp::Exp if !rhs.is_constant() => Err(SemanticAnalysisError::InvalidExpr(
-                        InvalidExprError::NonConstantExponent(rhs.span()),
-                    )),
+            ScalarExpr::Binary(ref mut binary_expr) => {
+                self.rewrite_scalar_expr(binary_expr.lhs.as_mut())?;
+                self.rewrite_scalar_expr(binary_expr.rhs.as_mut())?;
+                match binary_expr.op {
+                    BinaryOp::Exp if !binary_expr.rhs.is_constant() => {
+                        Err(SemanticAnalysisError::InvalidExpr(
+                            InvalidExprError::NonConstantExponent(binary_expr.rhs.span()),
+                        ))
+                    }
+                    // Division must be folded by now, as it is only valid between constants
+                    BinaryOp::Div => {
+                        match constant_propagation::try_fold_binary_expr(binary_expr) {
+                            Ok(Some(folded)) => {
+                                *expr = ScalarExpr::Const(folded);
+                                Ok(())
+                            }
+                            Ok(None) => {
+                                let span = if binary_expr.lhs.is_constant() {
+                                    binary_expr.rhs.span()
+                                } else {
+                                    binary_expr.lhs.span()
+                                };
+                                Err(SemanticAnalysisError::InvalidExpr(
+                                    InvalidExprError::NonConstantDivision(span),
+                                ))
+                            }
+                            Err(err) => Err(SemanticAnalysisError::InvalidExpr(err)),
+                        }
+                    }
                     _ => Ok(()),
                 }
             }
-            ScalarExpr::Call(ref mut expr) => {
-                for arg in expr.args.iter_mut() {
+            // Conditionals must be folded by now, as their conditions must be known at compile-time
+            ScalarExpr::If(ref mut if_expr) => {
+                self.rewrite_scalar_expr(if_expr.condition.lhs.as_mut())?;
+                self.rewrite_scalar_expr(if_expr.condition.rhs.as_mut())?;
+                match constant_propagation::try_fold_if_expr(if_expr) {
+                    Some(mut selected) => {
+                        self.rewrite_scalar_expr(&mut selected)?;
+                        *expr = selected;
+                        Ok(())
+                    }
+                    None => Err(SemanticAnalysisError::InvalidExpr(
+                        InvalidExprError::NonConstantCondition(if_expr.condition.span()),
+                    )),
+                }
+            }
+            ScalarExpr::Call(ref mut call) => {
+                for arg in call.args.iter_mut() {
                     self.rewrite_expr(arg)?;
                 }
+                // Calls to `inv` must be folded by now, as it is only valid on constants
+                if call.is_builtin() && call.callee.as_ref().name() == symbols::Inv {
+                    match constant_propagation::try_fold_inv(call) {
+                        Ok(Some(folded)) => {
+                            *expr = ScalarExpr::Const(folded);
+                        }
+                        Ok(None) => {
+                            return Err(SemanticAnalysisError::InvalidExpr(
+                                InvalidExprError::NonConstantDivision(call.args[0].span()),
+                            ))
+                        }
+                        Err(err) => return Err(SemanticAnalysisError::InvalidExpr(err)),
+                    }
+                }
                 Ok(())
             }
         }
@@ -727,11 +1088,69 @@ impl<'a> Inlining<'a> {
         lc: &ListComprehension,
         index: usize,
     ) -> Result<Vec<Statement>, SemanticAnalysisError> {
-        // Register each iterable binding and its abstract value.
+        let bound_values = self.bind_iterables(lc, index)?;
+
+        // Clone the comprehension body for this iteration, so we don't modify the original
+        let mut body = lc.body.as_ref().clone();
+
+        // Rewrite all references to the iterable bindings in the comprehension body
+        let mut visitor = RewriteIterableBindingsVisitor {
+            values: &bound_values,
+        };
+        if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut body) {
+            return Err(err);
+        }
+
+        // Next, handle comprehension filters/selectors as follows:
+        //
+        // 1. Selectors are evaluated in the same context as the body, so we must visit iterable references in the same way.
+        // 2. If a selector has a constant value, we can elide the selector for this iteration. Furthermore, in situations where
+        // the selector is known false, we can elide the expansion of this iteration entirely.
         //
-        // The abstract value is either a constant (in which case it is concrete, not abstract), or
-        // an expression which represents accessing the iterable at the index corresponding to the
-        // current iteration.
+        // Since the selector is the last piece we need to construct the Statement corresponding to the expansion of
+        // this iteration, we do that now before proceeding to the next step.
+        let statement = if let Some(mut selector) = lc.selector.clone() {
+            assert!(
+                self.in_comprehension_constraint,
+                "selectors are not permitted in list comprehensions"
+            );
+            // #1
+            if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut selector) {
+                return Err(err);
+            }
+            // #2
+            match selector {
+                // If the selector value is zero, or false, we can elide the expansion entirely
+                ScalarExpr::Const(value) if value.item == 0 => return Ok(vec![]),
+                // If the selector value is non-zero, or true, we can elide just the selector
+                ScalarExpr::Const(_) => Statement::Enforce(body),
+                // We have a selector that requires evaluation at runtime, we need to emit a conditional scalar constraint
+                other => Statement::EnforceIf(body, other),
+            }
+        } else if self.in_comprehension_constraint {
+            Statement::Enforce(body)
+        } else {
+            Statement::Expr(body.try_into().unwrap())
+        };
+
+        // Next, although we've rewritten the comprehension body corresponding to this iteration, we
+        // haven't yet performed inlining on it. We do that now, while all of the bindings are
+        // in scope with the proper values. The result of that expansion is what we emit as the result
+        // for this iteration.
+        self.expand_statement(statement)
+    }
+
+    /// Binds each of the bindings of `lc` to its abstract value for the iteration given by `index`,
+    /// returning those values so that references to the bindings may be rewritten.
+    ///
+    /// The abstract value is either a constant (in which case it is concrete, not abstract), or
+    /// an expression which represents accessing the iterable at the index corresponding to the
+    /// current iteration.
+    fn bind_iterables(
+        &mut self,
+        lc: &ListComprehension,
+        index: usize,
+    ) -> Result<HashMap<Identifier, Expr>, SemanticAnalysisError> {
         let mut bound_values = HashMap::<Identifier, Expr>::default();
         for (iterable, binding) in lc.iterables.iter().zip(lc.bindings.iter().copied()) {
             let abstract_value = match iterable {
@@ -753,10 +1172,8 @@ impl<'a> Inlining<'a> {
                     let span = range.span();
                     let binding_ty = BindingType::Constant(Type::Felt);
                     self.bindings.insert(binding, binding_ty);
-                    Expr::Const(Span::new(
-                        span,
-                        ConstantExpr::Scalar((range.start + index) as u64),
-                    ))
+                    let value = range.values().unwrap().nth(index).unwrap();
+                    Expr::Const(Span::new(span, ConstantExpr::Scalar(value as u64)))
                 }
                 // If the iterable was a vector, the abstract value is whatever expression is at
                 // the corresponding index of the vector.
@@ -796,65 +1213,38 @@ impl<'a> Inlining<'a> {
                     self.bindings.insert(binding, binding_ty);
                     Expr::SymbolAccess(current_access)
                 }
+                // If the iterable is itself a comprehension, the abstract value is the body of that
+                // comprehension for the current iteration. The bindings of the nested comprehension
+                // are only visible in its body, so they are bound in their own lexical scope.
+                Expr::ListComprehension(ref nested) => {
+                    self.bindings.enter();
+                    let nested_values = self.bind_iterables(nested, index);
+                    self.bindings.exit();
+                    let nested_values = nested_values?;
+                    let mut body = nested.body.as_ref().clone();
+                    let mut visitor = RewriteIterableBindingsVisitor {
+                        values: &nested_values,
+                    };
+                    if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut body) {
+                        return Err(err);
+                    }
+                    let abstract_value: Expr = body.try_into().unwrap();
+                    let binding_ty = self.expr_binding_type(&abstract_value).unwrap();
+                    self.bindings.insert(binding, binding_ty);
+                    abstract_value
+                }
                 // TODO: Currently, calls cannot be used as iterables, because we don't have pure functions
                 // which can produce aggregates. However, when those are added, we may want to add support
                 // for that here. This branch is set up to raise an appropriate panic if we forget to do so.
                 Expr::Call(_) => unimplemented!("calls to functions as iterables"),
-                // Binary expressions are scalar, so cannot be used as iterables, and we don't (currently)
-                // support nested comprehensions, so it is never possible to observe these expression types here
-                Expr::Binary(_) | Expr::ListComprehension(_) => unreachable!(),
+                // Binary and conditional expressions are scalar, and lambdas do not produce a value,
+                // so none of these can be used as iterables
+                Expr::Binary(_) | Expr::If(_) | Expr::Lambda(_) => unreachable!(),
             };
             bound_values.insert(binding, abstract_value);
         }
 
-        // Clone the comprehension body for this iteration, so we don't modify the original
-        let mut body = lc.body.as_ref().clone();
-
-        // Rewrite all references to the iterable bindings in the comprehension body
-        let mut visitor = RewriteIterableBindingsVisitor {
-            values: &bound_values,
-        };
-        if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut body) {
-            return Err(err);
-        }
-
-        // Next, handle comprehension filters/selectors as follows:
-        //
-        // 1. Selectors are evaluated in the same context as the body, so we must visit iterable references in the same way.
-        // 2. If a selector has a constant value, we can elide the selector for this iteration. Furthermore, in situations where
-        // the selector is known false, we can elide the expansion of this iteration entirely.
-        //
-        // Since the selector is the last piece we need to construct the Statement corresponding to the expansion of
-        // this iteration, we do that now before proceeding to the next step.
-        let statement = if let Some(mut selector) = lc.selector.clone() {
-            assert!(
-                self.in_comprehension_constraint,
-                "selectors are not permitted in list comprehensions"
-            );
-            // #1
-            if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut selector) {
-                return Err(err);
-            }
-            // #2
-            match selector {
-                // If the selector value is zero, or false, we can elide the expansion entirely
-                ScalarExpr::Const(value) if value.item == 0 => return Ok(vec![]),
-                // If the selector value is non-zero, or true, we can elide just the selector
-                ScalarExpr::Const(_) => Statement::Enforce(body),
-                // We have a selector that requires evaluation at runtime, we need to emit a conditional scalar constraint
-                other => Statement::EnforceIf(body, other),
-            }
-        } else if self.in_comprehension_constraint {
-            Statement::Enforce(body)
-        } else {
-            Statement::Expr(body.try_into().unwrap())
-        };
-
-        // Next, although we've rewritten the comprehension body corresponding to this iteration, we
-        // haven't yet performed inlining on it. We do that now, while all of the bindings are
-        // in scope with the proper values. The result of that expansion is what we emit as the result
-        // for this iteration.
-        self.expand_statement(statement)
+        Ok(bound_values)
     }
 
     /// This function handles inlining evaluator function calls.
@@ -864,9 +1254,15 @@ impl<'a> Inlining<'a> {
     /// function parameters. However, the number and type of bindings are permitted to be
     /// different, as long as the vectors are the same size when expanded - in effect, re-grouping
     /// the trace columns at the call boundary.
+    ///
+    /// Arguments for value parameters are substituted for all uses of those parameters in the
+    /// function body, after first applying any rewrites which are pending in the caller.
+    ///
+    /// Evaluators with const parameters are first instantiated for the call site, see
+    /// `instantiate_evaluator` for details.
     fn expand_evaluator_callsite(
         &mut self,
-        call: Call,
+        mut call: Call,
     ) -> Result<Vec<Statement>, SemanticAnalysisError> {
         // The callee is guaranteed to be resolved and exist at this point
         let callee = call
@@ -877,6 +1273,30 @@ impl<'a> Inlining<'a> {
         // inlining process, and we must not modify the original
         let mut evaluator = self.evaluators.get(&callee).unwrap().clone();
 
+        // If the callee has const parameters, instantiate it for this call site
+        if evaluator.is_generic() {
+            self.instantiate_evaluator(&mut evaluator, &call)?;
+        }
+        self.check_trace_arguments(&evaluator.params, &call)?;
+
+        // Substitute the value arguments in the evaluator body, while we are still in the caller's scope
+        let value_args = call.args.split_off(evaluator.params.len());
+        if !value_args.is_empty() {
+            let mut values = HashMap::with_capacity(value_args.len());
+            for (mut arg, param) in value_args.into_iter().zip(evaluator.value_params.iter()) {
+                self.rewrite_expr(&mut arg)?;
+                values.insert(param.name, arg);
+            }
+            let mut visitor = RewriteValueParamsVisitor {
+                values: &values,
+                shadowed: LexicalScope::default(),
+            };
+            if let ControlFlow::Break(err) = visitor.visit_mut_statement_block(&mut evaluator.body)
+            {
+                return Err(err);
+            }
+        }
+
         // This will be the initial set of bindings visible within the evaluator body
         //
         // This is distinct from `self.bindings` at this point, because the evaluator doesn't
@@ -897,7 +1317,7 @@ impl<'a> Inlining<'a> {
         // root module.
         let is_evaluator_in_root = callee.module == self.root;
         if is_evaluator_in_root {
-            if let Some(rv) = self.random_values.as_ref() {
+            for rv in self.random_values.values() {
                 eval_bindings.insert(
                     rv.name,
                     BindingType::RandomValue(RandBinding::new(
@@ -923,6 +1343,7 @@ impl<'a> Inlining<'a> {
                         offset: 0,
                         size: segment.size,
                         ty: Type::Vector(segment.size),
+                        column_type: None,
                     }),
                 );
                 for binding in segment.bindings.iter().copied() {
@@ -935,16 +1356,14 @@ impl<'a> Inlining<'a> {
                             offset: binding.offset,
                             size: binding.size,
                             ty: binding.ty,
+                            column_type: binding.column_type,
                         }),
                     );
                 }
             }
 
             for input in self.public_inputs.values() {
-                eval_bindings.insert(
-                    input.name,
-                    BindingType::PublicInput(Type::Vector(input.size)),
-                );
+                eval_bindings.insert(input.name, BindingType::PublicInput(input.ty()));
             }
         }
 
@@ -972,6 +1391,131 @@ impl<'a> Inlining<'a> {
         Ok(evaluator.body)
     }
 
+    /// This function instantiates an evaluator with const parameters for the given call.
+    ///
+    /// The values of the const parameters are inferred from the number of columns passed for each
+    /// trace segment, and are used to determine the size of the trace bindings sized by those
+    /// parameters. All uses of the parameters in the function body are then replaced with their
+    /// values, and the types of expressions in the body which depend on them are recomputed.
+    ///
+    /// Semantic analysis can only check the function body in terms of unknown sizes, so accesses
+    /// which are out of bounds for a specific instantiation are reported here, at the call site.
+    fn instantiate_evaluator(
+        &mut self,
+        evaluator: &mut EvaluatorFunction,
+        call: &Call,
+    ) -> Result<(), SemanticAnalysisError> {
+        let trace_args = &call.args[..evaluator.params.len()];
+        let sizes = trace_args
+            .iter()
+            .map(|arg| self.trace_argument_size(arg))
+            .collect::<Vec<_>>();
+        let values = match infer_size_params(&evaluator.params, &sizes) {
+            Ok(values) => values,
+            Err(param) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(
+                        call.span(),
+                        format!(
+                            "unable to infer a value for the const parameter '{}' from these arguments",
+                            param
+                        ),
+                    )
+                    .with_secondary_label(param.span(), "it determines the size of this binding")
+                    .emit();
+                return Err(SemanticAnalysisError::Invalid);
+            }
+        };
+
+        let params = evaluator
+            .params
+            .iter()
+            .map(|segment| segment.instantiate(&values))
+            .collect::<Vec<_>>();
+
+        // Substitute the const parameters in the body, recomputing types as we go
+        let mut visitor = InstantiateEvaluatorVisitor {
+            diagnostics: self.diagnostics,
+            call: call.span(),
+            values: &values,
+            types: LexicalScope::default(),
+        };
+        for binding in params.iter().flat_map(|segment| segment.bindings.iter()) {
+            visitor
+                .types
+                .insert(binding.name.unwrap(), Some(binding.ty));
+        }
+        for param in evaluator.value_params.iter() {
+            visitor.types.insert(param.name, Some(param.ty));
+        }
+        if let ControlFlow::Break(err) = visitor.visit_mut_statement_block(&mut evaluator.body) {
+            return Err(err);
+        }
+
+        evaluator.params = params;
+        evaluator.const_params.clear();
+
+        Ok(())
+    }
+
+    /// Verify that the trace arguments of `call` provide as many columns as `params` expects.
+    ///
+    /// This is checked during semantic analysis for most calls, but calls whose arguments are sized
+    /// by a const parameter, as well as calls to generic evaluators, can only be checked once the
+    /// relevant evaluator has been instantiated.
+    fn check_trace_arguments(
+        &self,
+        params: &[TraceSegment],
+        call: &Call,
+    ) -> Result<(), SemanticAnalysisError> {
+        for (arg, segment) in call.args.iter().zip(params.iter()) {
+            let Some(size) = self.trace_argument_size(arg) else {
+                continue;
+            };
+            if size != segment.size {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(call.span(), "type mismatch in function argument")
+                    .with_secondary_label(
+                        arg.span(),
+                        format!(
+                            "callee expects {} trace columns here, but this argument provides {}",
+                            segment.size, size
+                        ),
+                    )
+                    .emit();
+                return Err(SemanticAnalysisError::Invalid);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of trace columns provided by `arg`, if it is a valid evaluator argument
+    fn trace_argument_size(&self, arg: &Expr) -> Option<usize> {
+        match arg {
+            Expr::SymbolAccess(ref access) => self
+                .access_binding_type(access)
+                .ok()
+                .and_then(|binding_ty| binding_ty.num_trace_columns()),
+            Expr::Vector(ref elems) => elems
+                .iter()
+                .map(|elem| {
+                    self.expr_binding_type(elem)
+                        .ok()
+                        .and_then(|binding_ty| binding_ty.num_trace_columns())
+                })
+                .sum(),
+            Expr::Const(Span {
+                item: ConstantExpr::Vector(ref elems),
+                ..
+            }) if elems.is_empty() => Some(0),
+            _ => None,
+        }
+    }
+
     /// Populate the set of access rewrites, as well as the initial set of bindings to use when inlining an evaluator function.
     ///
     /// This is done by resolving the arguments provided by the call to the evaluator, with the parameter list of the evaluator itself.
@@ -1016,7 +1560,7 @@ impl<'a> Inlining<'a> {
                         // that these columns may have been gathered from multiple bindings in the caller
                         let (matched, rest) = bt.split_columns(binding.size).unwrap();
                         self.rewrites.insert(binding_name);
-                        eval_bindings.insert(binding_name, matched);
+                        eval_bindings.insert(binding_name, with_param_type(binding, matched));
                         // Update `binding_ty` with whatever remains of the input
                         binding_ty = rest;
                     }
@@ -1119,7 +1663,10 @@ impl<'a> Inlining<'a> {
                                     };
                                     // This binding has been fulfilled, move to the next one
                                     self.rewrites.insert(binding_name);
-                                    eval_bindings.insert(binding_name, eval_binding);
+                                    eval_bindings.insert(
+                                        binding_name,
+                                        with_param_type(binding, eval_binding),
+                                    );
                                     binding_ty = rest;
                                     // If we have no more columns remaining in this input, advance
                                     // to the next input starting with the next binding
@@ -1213,7 +1760,10 @@ impl<'a> Inlining<'a> {
     fn expr_binding_type(&self, expr: &Expr) -> Result<BindingType, InvalidAccessError> {
         match expr {
             Expr::Const(constant) => Ok(BindingType::Local(constant.ty())),
-            Expr::Range(range) => Ok(BindingType::Local(Type::Vector(range.end - range.start))),
+            Expr::Range(range) => {
+                let len = range.values().unwrap().len();
+                Ok(BindingType::Local(Type::Vector(len)))
+            }
             Expr::Vector(ref elems) => match elems[0].ty() {
                 None | Some(Type::Felt) => {
                     let mut binding_tys = Vec::with_capacity(elems.len());
@@ -1234,9 +1784,11 @@ impl<'a> Inlining<'a> {
                 Ok(BindingType::Local(Type::Matrix(rows, columns)))
             }
             Expr::SymbolAccess(ref access) => self.access_binding_type(access),
-            Expr::Call(Call { ty: None, .. }) => Err(InvalidAccessError::InvalidBinding),
+            Expr::Call(Call { ty: None, .. }) | Expr::Lambda(_) => {
+                Err(InvalidAccessError::InvalidBinding)
+            }
             Expr::Call(Call { ty: Some(ty), .. }) => Ok(BindingType::Local(*ty)),
-            Expr::Binary(_) => Ok(BindingType::Local(Type::Felt)),
+            Expr::Binary(_) | Expr::If(_) => Ok(BindingType::Local(Type::Felt)),
             Expr::ListComprehension(ref lc) => {
                 // The types of all iterables must be the same, so the type of
                 // the comprehension is given by the type of the iterables. We
@@ -1303,11 +1855,11 @@ impl<'a> RewriteIterableBindingsVisitor<'a> {
             }
             Some(Expr::Range(range)) => {
                 let span = range.span();
-                let range = range.item.clone();
+                let mut values = range.values().unwrap();
                 match access.access_type {
                     AccessType::Index(idx) => Some(ScalarExpr::Const(Span::new(
                         span,
-                        (range.start + idx) as u64,
+                        values.nth(idx).unwrap() as u64,
                     ))),
                     invalid => panic!(
                         "expected range to be reduced to scalar by access, got {:#?}",
@@ -1350,13 +1902,60 @@ impl<'a> RewriteIterableBindingsVisitor<'a> {
                 new_access.offset = access.offset;
                 Some(ScalarExpr::SymbolAccess(new_access))
             }
+            // These are only observed as the arguments of value parameters, which are scalar
+            // when they are of these types
+            Some(Expr::Binary(binary_expr)) => {
+                assert_eq!(access.access_type, AccessType::Default);
+                Some(ScalarExpr::Binary(binary_expr.clone()))
+            }
+            Some(Expr::If(if_expr)) => {
+                assert_eq!(access.access_type, AccessType::Default);
+                Some(ScalarExpr::If(if_expr.clone()))
+            }
+            Some(Expr::Call(call)) => {
+                assert_eq!(access.access_type, AccessType::Default);
+                Some(ScalarExpr::Call(call.clone()))
+            }
             // These types of expressions will never be observed in this context, as they are
-            // not valid iterable elements.
-            Some(Expr::Call(_) | Expr::Binary(_) | Expr::ListComprehension(_)) => unreachable!(),
+            // not valid iterable elements, and are never scalar.
+            Some(Expr::ListComprehension(_) | Expr::Lambda(_)) => unreachable!(),
             None => None,
         };
         ControlFlow::Continue(result)
     }
+
+    /// Rewrite an argument of a call to a builtin, e.g. `is_binary` or `sum`.
+    ///
+    /// Unlike evaluators, the builtins have no bindings of their own, so accesses which are not
+    /// reduced to a scalar are replaced with the bound value in its entirety.
+    fn rewrite_builtin_argument(&mut self, expr: &mut Expr) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            Expr::Vector(ref mut elems) => {
+                for elem in elems.iter_mut() {
+                    self.rewrite_builtin_argument(elem)?;
+                }
+                ControlFlow::Continue(())
+            }
+            Expr::SymbolAccess(ref access) => match self.values.get(access.name.as_ref()) {
+                None => ControlFlow::Continue(()),
+                Some(value) if access.access_type == AccessType::Default => {
+                    *expr = value.clone();
+                    ControlFlow::Continue(())
+                }
+                Some(_) => {
+                    let replacement = self.rewrite_scalar_access(access.clone())?.unwrap();
+                    match replacement.try_into() {
+                        Ok(replacement) => {
+                            *expr = replacement;
+                            ControlFlow::Continue(())
+                        }
+                        Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                    }
+                }
+            },
+            _ => self.visit_mut_expr(expr),
+        }
+    }
 }
 impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a> {
     fn visit_mut_scalar_expr(
@@ -1397,7 +1996,205 @@ impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a>
                     Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
                 }
             }
-            // If we observe a call here, just rewrite the arguments, inlining happens elsewhere
+            // Conditionals are replaced with the selected branch, if the condition is now constant
+            ScalarExpr::If(ref mut if_expr) => {
+                self.visit_mut_if_expr(if_expr)?;
+                if let Some(selected) = constant_propagation::try_fold_if_expr(if_expr) {
+                    *expr = selected;
+                }
+                ControlFlow::Continue(())
+            }
+            // If we observe a call here, just rewrite the arguments, inlining happens elsewhere,
+            // but if the call is to `inv`, try to fold it while we're here
+            ScalarExpr::Call(ref mut call) if call.is_builtin_constraint() => {
+                for arg in call.args.iter_mut() {
+                    self.rewrite_builtin_argument(arg)?;
+                }
+                ControlFlow::Continue(())
+            }
+            ScalarExpr::Call(ref mut call) => {
+                let is_builtin = call.is_builtin();
+                for arg in call.args.iter_mut() {
+                    if is_builtin {
+                        self.rewrite_builtin_argument(arg)?;
+                    } else {
+                        self.visit_mut_expr(arg)?;
+                    }
+                }
+                match constant_propagation::try_fold_inv(call) {
+                    Ok(Some(folded)) => {
+                        *expr = ScalarExpr::Const(folded);
+                        ControlFlow::Continue(())
+                    }
+                    Ok(None) => ControlFlow::Continue(()),
+                    Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                }
+            }
+        }
+    }
+}
+
+/// This visitor is used to substitute the arguments for the value parameters of an evaluator
+/// in its body when it is inlined.
+///
+/// Unlike iterable bindings, value parameters may be used in their entirety, e.g. when passed
+/// as an argument to another evaluator, so accesses are rewritten wherever they occur, except
+/// where a parameter is shadowed by a local binding.
+struct RewriteValueParamsVisitor<'a> {
+    /// The arguments to substitute, keyed by the name of the corresponding parameter
+    values: &'a HashMap<Identifier, Expr>,
+    /// The local bindings in scope which shadow a parameter
+    shadowed: LexicalScope<Identifier, ()>,
+}
+impl<'a> RewriteValueParamsVisitor<'a> {
+    /// Returns the argument for the parameter referenced by `name`, unless it is shadowed
+    fn value(&self, name: &ResolvableIdentifier) -> Option<&'a Expr> {
+        match self.shadowed.get(name.as_ref()) {
+            None => self.values.get(name.as_ref()),
+            Some(_) => None,
+        }
+    }
+
+    /// Records the bindings which shadow a parameter in the current scope
+    fn shadow(&mut self, bindings: &[Identifier]) {
+        for binding in bindings {
+            if self.values.contains_key(binding) {
+                self.shadowed.insert(*binding, ());
+            }
+        }
+    }
+}
+impl<'a> VisitMut<SemanticAnalysisError> for RewriteValueParamsVisitor<'a> {
+    fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_expr(&mut expr.value)?;
+        self.shadowed.enter();
+        self.shadow(&[expr.name]);
+        self.visit_mut_statement_block(&mut expr.body)?;
+        self.shadowed.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_enforce_all(
+        &mut self,
+        expr: &mut ListComprehension,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_list_comprehension(expr)
+    }
+
+    fn visit_mut_list_comprehension(
+        &mut self,
+        expr: &mut ListComprehension,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        for iterable in expr.iterables.iter_mut() {
+            self.visit_mut_expr(iterable)?;
+        }
+        self.shadowed.enter();
+        self.shadow(&expr.bindings);
+        if let Some(selector) = expr.selector.as_mut() {
+            self.visit_mut_scalar_expr(selector)?;
+        }
+        self.visit_mut_scalar_expr(expr.body.as_mut())?;
+        self.shadowed.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_lambda(&mut self, expr: &mut Lambda) -> ControlFlow<SemanticAnalysisError> {
+        self.shadowed.enter();
+        self.shadow(&expr.params);
+        self.visit_mut_scalar_expr(expr.body.as_mut())?;
+        self.shadowed.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            // Accesses which are not reduced to a scalar, e.g. a vector passed as an argument to a
+            // call, are replaced with the rewritten value in its entirety
+            Expr::SymbolAccess(ref access) => match self.value(&access.name) {
+                None => ControlFlow::Continue(()),
+                Some(value) => match (value, access.access_type.clone()) {
+                    (value, AccessType::Default) => {
+                        *expr = value.clone();
+                        ControlFlow::Continue(())
+                    }
+                    (Expr::SymbolAccess(symbol_access), access_type) => {
+                        let mut new_access = symbol_access.access(access_type).unwrap();
+                        new_access.offset = access.offset;
+                        *expr = Expr::SymbolAccess(new_access);
+                        ControlFlow::Continue(())
+                    }
+                    (Expr::Vector(elems), AccessType::Slice(range)) => {
+                        let span = elems.span();
+                        *expr = Expr::Vector(Span::new(span, elems[range].to_vec()));
+                        ControlFlow::Continue(())
+                    }
+                    (Expr::Const(constant), AccessType::Slice(range)) => {
+                        let ConstantExpr::Vector(ref elems) = constant.item else {
+                            panic!("expected constant vector, got {:#?}", constant)
+                        };
+                        let elems = elems[range].to_vec();
+                        *expr =
+                            Expr::Const(Span::new(constant.span(), ConstantExpr::Vector(elems)));
+                        ControlFlow::Continue(())
+                    }
+                    _ => {
+                        let mut visitor = RewriteIterableBindingsVisitor {
+                            values: self.values,
+                        };
+                        let replacement = visitor.rewrite_scalar_access(access.clone())?.unwrap();
+                        match replacement.try_into() {
+                            Ok(replacement) => {
+                                *expr = replacement;
+                                ControlFlow::Continue(())
+                            }
+                            Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                        }
+                    }
+                },
+            },
+            _ => visit::visit_mut_expr(self, expr),
+        }
+    }
+
+    fn visit_mut_scalar_expr(
+        &mut self,
+        expr: &mut ScalarExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            ScalarExpr::Const(_) => ControlFlow::Continue(()),
+            // Scalar accesses are rewritten the same way as iterable bindings
+            ScalarExpr::SymbolAccess(SymbolAccess { ref name, .. })
+            | ScalarExpr::BoundedSymbolAccess(BoundedSymbolAccess {
+                column: SymbolAccess { ref name, .. },
+                ..
+            }) if self.value(name).is_none() => ControlFlow::Continue(()),
+            ScalarExpr::SymbolAccess(_) | ScalarExpr::BoundedSymbolAccess(_) => {
+                RewriteIterableBindingsVisitor {
+                    values: self.values,
+                }
+                .visit_mut_scalar_expr(expr)
+            }
+            // Constant operands may be folded once the arguments are substituted
+            ScalarExpr::Binary(ref mut binary_expr) => {
+                self.visit_mut_binary_expr(binary_expr)?;
+                match constant_propagation::try_fold_binary_expr(binary_expr) {
+                    Ok(Some(folded)) => {
+                        *expr = ScalarExpr::Const(folded);
+                        ControlFlow::Continue(())
+                    }
+                    Ok(None) => ControlFlow::Continue(()),
+                    Err(err) => ControlFlow::Break(SemanticAnalysisError::InvalidExpr(err)),
+                }
+            }
+            // Likewise for the condition of a conditional
+            ScalarExpr::If(ref mut if_expr) => {
+                self.visit_mut_if_expr(if_expr)?;
+                if let Some(selected) = constant_propagation::try_fold_if_expr(if_expr) {
+                    *expr = selected;
+                }
+                ControlFlow::Continue(())
+            }
+            // Call arguments are visited by this visitor, so that they are rewritten in their entirety
             ScalarExpr::Call(ref mut call) => {
                 for arg in call.args.iter_mut() {
                     self.visit_mut_expr(arg)?;
@@ -1408,6 +2205,221 @@ impl<'a> VisitMut<SemanticAnalysisError> for RewriteIterableBindingsVisitor<'a>
     }
 }
 
+/// This visitor is used to replace calls to builtins which produce a value with accesses to
+/// generated variables, see `Inlining::bind_value_builtins`.
+///
+/// The bodies of comprehensions and lambdas are not visited, as calls in them may depend on
+/// their bindings; they are visited once the comprehension or fold is unrolled.
+struct BindValueBuiltinsVisitor<'a, 'b> {
+    inliner: &'a mut Inlining<'b>,
+    /// The calls which were replaced, along with the variable each is to be bound to
+    bound: Vec<(Identifier, Expr)>,
+}
+impl<'a, 'b> BindValueBuiltinsVisitor<'a, 'b> {
+    fn is_value_builtin(call: &Call) -> bool {
+        call.is_builtin()
+            && matches!(
+                call.callee.as_ref().name(),
+                symbols::Sum | symbols::Prod | symbols::BitsToField | symbols::Fold
+            )
+    }
+
+    /// Returns an access to a new variable, which `call` is to be bound to
+    fn bind(&mut self, call: Call) -> SymbolAccess {
+        let span = call.span();
+        let name = self.inliner.next_builtin_ident(span);
+        self.bound.push((name, Expr::Call(call)));
+        SymbolAccess {
+            span,
+            name: ResolvableIdentifier::Local(name),
+            access_type: AccessType::Default,
+            offset: 0,
+            ty: Some(Type::Felt),
+        }
+    }
+}
+impl<'a, 'b> VisitMut<SemanticAnalysisError> for BindValueBuiltinsVisitor<'a, 'b> {
+    fn visit_mut_expr(&mut self, expr: &mut Expr) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            Expr::Call(ref call) if Self::is_value_builtin(call) => {
+                *expr = Expr::SymbolAccess(self.bind(call.clone()));
+                ControlFlow::Continue(())
+            }
+            Expr::ListComprehension(_) | Expr::Lambda(_) => ControlFlow::Continue(()),
+            _ => visit::visit_mut_expr(self, expr),
+        }
+    }
+
+    fn visit_mut_scalar_expr(
+        &mut self,
+        expr: &mut ScalarExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            ScalarExpr::Call(ref call) if Self::is_value_builtin(call) => {
+                *expr = ScalarExpr::SymbolAccess(self.bind(call.clone()));
+                ControlFlow::Continue(())
+            }
+            _ => visit::visit_mut_scalar_expr(self, expr),
+        }
+    }
+}
+
+/// This visitor is used to instantiate the body of an evaluator with const parameters, by
+/// replacing all uses of those parameters with their values, and recomputing the types of
+/// accesses and comprehensions which depend on them.
+struct InstantiateEvaluatorVisitor<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    /// The span of the call for which the evaluator is being instantiated
+    call: SourceSpan,
+    /// The values of the const parameters
+    values: &'a HashMap<Identifier, usize>,
+    /// The types of all bindings in scope, where known. Bindings not in this set are not
+    /// affected by instantiation, and keep the types assigned during semantic analysis.
+    types: LexicalScope<Identifier, Option<Type>>,
+}
+impl<'a> InstantiateEvaluatorVisitor<'a> {
+    /// Returns the value of the const parameter referenced by `access`, if applicable
+    fn const_param_value(&self, access: &SymbolAccess) -> Option<u64> {
+        match access.name {
+            ResolvableIdentifier::Local(name) if self.types.get(&name).is_none() => {
+                self.values.get(&name).map(|value| *value as u64)
+            }
+            _ => None,
+        }
+    }
+}
+impl<'a> VisitMut<SemanticAnalysisError> for InstantiateEvaluatorVisitor<'a> {
+    fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_expr(&mut expr.value)?;
+        self.types.enter();
+        self.types.insert(expr.name, expr.value.ty());
+        self.visit_mut_statement_block(&mut expr.body)?;
+        self.types.exit();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_list_comprehension(
+        &mut self,
+        expr: &mut ListComprehension,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        for iterable in expr.iterables.iter_mut() {
+            self.visit_mut_expr(iterable)?;
+        }
+        // Now that the const parameters are known, so is the number of iterations
+        let len = expr
+            .iterables
+            .iter()
+            .find_map(|iterable| iterable.ty().and_then(|ty| ty.iterable_len()));
+        if let Some(len) = len {
+            for iterable in expr.iterables.iter_mut() {
+                if let Expr::Range(ref mut range) = iterable {
+                    range.infer_end(len);
+                }
+            }
+        }
+        expr.ty = len.map(Type::Vector);
+
+        self.types.enter();
+        for (binding, iterable) in expr.bindings.iter().zip(expr.iterables.iter()) {
+            let ty = iterable
+                .ty()
+                .and_then(|ty| ty.access(AccessType::Index(0)).ok());
+            self.types.insert(*binding, ty);
+        }
+        if let Some(selector) = expr.selector.as_mut() {
+            self.visit_mut_scalar_expr(selector)?;
+        }
+        self.visit_mut_scalar_expr(expr.body.as_mut())?;
+        self.types.exit();
+
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            Expr::SymbolAccess(ref access) => {
+                if let Some(value) = self.const_param_value(access) {
+                    *expr = Expr::Const(Span::new(access.span(), ConstantExpr::Scalar(value)));
+                    return ControlFlow::Continue(());
+                }
+                visit::visit_mut_expr(self, expr)
+            }
+            _ => visit::visit_mut_expr(self, expr),
+        }
+    }
+
+    fn visit_mut_scalar_expr(
+        &mut self,
+        expr: &mut ScalarExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        match expr {
+            ScalarExpr::SymbolAccess(ref access) => {
+                if let Some(value) = self.const_param_value(access) {
+                    *expr = ScalarExpr::Const(Span::new(access.span(), value));
+                    return ControlFlow::Continue(());
+                }
+                visit::visit_mut_scalar_expr(self, expr)
+            }
+            _ => visit::visit_mut_scalar_expr(self, expr),
+        }
+    }
+
+    fn visit_mut_range_expr(&mut self, expr: &mut RangeExpr) -> ControlFlow<SemanticAnalysisError> {
+        for bound in [&mut expr.start, &mut expr.end] {
+            if let RangeBound::Param(name) = *bound {
+                *bound = RangeBound::Const(self.values[&name]);
+            }
+        }
+        // The end of the range is inferred by the enclosing comprehension
+        let Some(range) = expr.to_range() else {
+            return ControlFlow::Continue(());
+        };
+        if range.start > range.end {
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid range")
+                .with_primary_label(
+                    expr.span(),
+                    format!(
+                        "this range is {}..{} when instantiated",
+                        range.start, range.end
+                    ),
+                )
+                .with_secondary_label(self.call, "instantiated by this call")
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_mut_symbol_access(
+        &mut self,
+        expr: &mut SymbolAccess,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let ResolvableIdentifier::Local(name) = expr.name else {
+            return ControlFlow::Continue(());
+        };
+        let Some(Some(ty)) = self.types.get(&name) else {
+            return ControlFlow::Continue(());
+        };
+        match ty.access(expr.access_type.clone()) {
+            Ok(ty) => {
+                expr.ty = Some(ty);
+                ControlFlow::Continue(())
+            }
+            Err(err) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid variable access")
+                    .with_primary_label(expr.span(), err.to_string())
+                    .with_secondary_label(self.call, "when instantiated by this call")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+}
+
 /// This visitor is used to apply a selector expression to all constraints in a block
 ///
 /// For constraints which already have a selector, this rewrites those selectors to be the
@@ -1443,12 +2455,62 @@ impl<'a> VisitMut<SemanticAnalysisError> for ApplyConstraintSelector<'a> {
                 ));
                 ControlFlow::Continue(())
             }
-            Statement::EnforceAll(_) => unreachable!(),
+            Statement::BusInteraction(ref mut expr) => {
+                expr.apply_selector(self.selector.clone());
+                ControlFlow::Continue(())
+            }
+            Statement::Lookup(ref mut expr) => {
+                expr.apply_selector(self.selector.clone());
+                ControlFlow::Continue(())
+            }
+            Statement::EnforceAll(_) | Statement::EnforceMatch(_) => unreachable!(),
             Statement::Expr(_) => ControlFlow::Continue(()),
         }
     }
 }
 
+/// Constructs the constraint `x^2 = x`, which holds only if `x` is binary
+fn is_binary(span: SourceSpan, x: ScalarExpr) -> ScalarExpr {
+    let squared = ScalarExpr::Binary(BinaryExpr::new(
+        span,
+        BinaryOp::Exp,
+        x.clone(),
+        ScalarExpr::Const(Span::new(span, 2)),
+    ));
+    ScalarExpr::Binary(BinaryExpr::new(span, BinaryOp::Eq, squared, x))
+}
+
+/// Constructs an access to the local variable `name`, which must be a field element
+fn local_access(span: SourceSpan, name: Identifier) -> Expr {
+    Expr::SymbolAccess(SymbolAccess {
+        span,
+        name: ResolvableIdentifier::Local(name),
+        access_type: AccessType::Default,
+        offset: 0,
+        ty: Some(Type::Felt),
+    })
+}
+
+/// Constructs the little-endian composition of `elems` in the given `base`, i.e. `sum(e_i * base^i)`
+fn compose(span: SourceSpan, elems: Vec<ScalarExpr>, base: u64) -> ScalarExpr {
+    elems
+        .into_iter()
+        .enumerate()
+        .map(
+            |(i, elem)| match constant_propagation::pow(base, i as u64) {
+                1 => elem,
+                coeff => ScalarExpr::Binary(BinaryExpr::new(
+                    span,
+                    BinaryOp::Mul,
+                    elem,
+                    ScalarExpr::Const(Span::new(span, coeff)),
+                )),
+            },
+        )
+        .reduce(|acc, elem| ScalarExpr::Binary(BinaryExpr::new(span, BinaryOp::Add, acc, elem)))
+        .unwrap()
+}
+
 /// This helper function is used to perform a mutation/replacement based on the expression
 /// representing the effective value of a `let`-tree.
 ///
@@ -1532,3 +2594,19 @@ where
 
     Ok(())
 }
+
+/// Returns `matched`, the binding type of the columns bound to the evaluator parameter `binding`,
+/// adjusted so that a single column bound to a vector parameter is itself treated as a vector.
+///
+/// This can only occur for parameters sized by a const parameter, which are always vectors.
+fn with_param_type(binding: &TraceBinding, matched: BindingType) -> BindingType {
+    match matched {
+        BindingType::TraceColumn(tb) if tb.is_scalar() && binding.ty.is_vector() => {
+            BindingType::TraceColumn(TraceBinding {
+                ty: binding.ty,
+                ..tb
+            })
+        }
+        matched => matched,
+    }
+}
@@ -1,5 +1,7 @@
 mod constant_propagation;
 mod inlining;
+mod selector_analysis;
 
 pub use self::constant_propagation::ConstantPropagation;
 pub use self::inlining::Inlining;
+pub use self::selector_analysis::SelectorAnalysis;
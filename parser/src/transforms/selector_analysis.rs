@@ -0,0 +1,486 @@
+use std::collections::{HashMap, HashSet};
+
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{ast::*, sema::SemanticAnalysisError};
+
+/// The maximum number of distinct values the selectors of a single match may depend on, for
+/// which we will attempt to prove that the selectors are binary and mutually exclusive.
+///
+/// The analysis evaluates the selectors for every assignment of binary values, so its cost
+/// is exponential in the number of values involved.
+const MAX_SELECTOR_INPUTS: usize = 16;
+
+/// A column (or element of a vector of columns) referenced by name, and index if applicable
+type Column = (Identifier, Option<usize>);
+
+/// A column accessed at a specific row offset
+type Input = (Column, usize);
+
+/// This pass checks that the selectors of every `enf match` statement in a [Program] are binary
+/// and mutually exclusive, as is assumed by the language, and emits a warning for each match
+/// where this cannot be proven.
+///
+/// A value used in a selector is considered binary if it is constrained to be so by an unconditional
+/// constraint in the same block, e.g. `enf s^2 = s`, either directly or via a call to an evaluator
+/// which does so, or if it is a column declared as `bool`. Given that, the selectors of a match are
+/// evaluated for every assignment of binary values to the columns they reference.
+///
+/// This pass is optional, does not modify the [Program], and must run before inlining, as that
+/// expands `enf match` statements into their constituent constraints.
+pub struct SelectorAnalysis<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    /// The set of columns which are constrained to be binary by each evaluator, in terms of its parameters
+    evaluators: HashMap<QualifiedIdentifier, HashSet<Column>>,
+}
+impl<'p> Pass for SelectorAnalysis<'p> {
+    type Input<'a> = Program;
+    type Output<'a> = Program;
+    type Error = SemanticAnalysisError;
+
+    fn run<'a>(&mut self, program: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        for (name, evaluator) in program.evaluators.iter() {
+            let binary = self.evaluator_binary_columns(&program, *name);
+            self.check_block(&evaluator.body, &binary);
+        }
+
+        // Columns declared as `bool` are implicitly constrained to be binary
+        let mut binary = HashSet::default();
+        for binding in program
+            .trace_columns
+            .iter()
+            .flat_map(|segment| segment.bindings.iter())
+            .filter(|binding| binding.column_type == Some(ColumnType::Bool))
+        {
+            let name = binding.name.unwrap();
+            if binding.is_scalar() {
+                binary.insert((name, None));
+            } else {
+                binary.extend((0..binding.size).map(|i| (name, Some(i))));
+            }
+        }
+        self.collect_binary_columns(&program, &program.integrity_constraints, &mut binary);
+        self.check_block(&program.integrity_constraints, &binary);
+
+        Ok(program)
+    }
+}
+impl<'a> SelectorAnalysis<'a> {
+    pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
+        Self {
+            diagnostics,
+            evaluators: Default::default(),
+        }
+    }
+
+    /// Returns the set of parameter columns of the evaluator `name` which its body constrains to be binary
+    fn evaluator_binary_columns(
+        &mut self,
+        program: &Program,
+        name: QualifiedIdentifier,
+    ) -> HashSet<Column> {
+        if let Some(binary) = self.evaluators.get(&name) {
+            return binary.clone();
+        }
+        let mut binary = HashSet::default();
+        if let Some(evaluator) = program.evaluators.get(&name) {
+            self.collect_binary_columns(program, &evaluator.body, &mut binary);
+        }
+        self.evaluators.insert(name, binary.clone());
+        binary
+    }
+
+    /// Collects the set of columns which are constrained to be binary by the unconditional
+    /// constraints in `body` into `binary`
+    fn collect_binary_columns(
+        &mut self,
+        program: &Program,
+        body: &[Statement],
+        binary: &mut HashSet<Column>,
+    ) {
+        for statement in body.iter() {
+            match statement {
+                Statement::Let(ref expr) => {
+                    self.collect_binary_columns(program, &expr.body, binary)
+                }
+                Statement::Enforce(ScalarExpr::Binary(BinaryExpr {
+                    op: BinaryOp::Eq,
+                    ref lhs,
+                    ref rhs,
+                    ..
+                })) => {
+                    if let Some(column) = binary_constrained_column(lhs, rhs) {
+                        binary.insert(column);
+                    }
+                }
+                Statement::Enforce(ScalarExpr::Call(ref call)) => {
+                    if let Some(callee) = call.callee.resolved() {
+                        self.collect_evaluator_binary_columns(program, callee, call, binary);
+                    }
+                }
+                // A constraint comprehension without a selector constrains every element of its
+                // iterables, e.g. `enf x^2 = x for x in s`
+                Statement::EnforceAll(ListComprehension {
+                    ref bindings,
+                    ref iterables,
+                    ref body,
+                    selector: None,
+                    ..
+                }) => {
+                    let ScalarExpr::Binary(BinaryExpr {
+                        op: BinaryOp::Eq,
+                        ref lhs,
+                        ref rhs,
+                        ..
+                    }) = body.as_ref()
+                    else {
+                        continue;
+                    };
+                    let Some((binding, None)) = binary_constrained_column(lhs, rhs) else {
+                        continue;
+                    };
+                    let Some(iterable) = bindings
+                        .iter()
+                        .position(|b| b == &binding)
+                        .map(|i| &iterables[i])
+                    else {
+                        continue;
+                    };
+                    if let Expr::SymbolAccess(ref access) = iterable {
+                        if let Some(columns) = access_columns(access) {
+                            binary.extend(columns);
+                        }
+                    }
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    /// Maps the binary parameter columns of the evaluator called by `call` to the columns passed as arguments
+    fn collect_evaluator_binary_columns(
+        &mut self,
+        program: &Program,
+        callee: QualifiedIdentifier,
+        call: &Call,
+        binary: &mut HashSet<Column>,
+    ) {
+        let callee_binary = self.evaluator_binary_columns(program, callee);
+        if callee_binary.is_empty() {
+            return;
+        }
+        let evaluator = &program.evaluators[&callee];
+
+        // The size of generic parameters is not known until the evaluator is instantiated
+        if evaluator.is_generic() {
+            return;
+        }
+        let params = evaluator
+            .params
+            .iter()
+            .flat_map(|segment| segment.bindings.iter())
+            .flat_map(|binding| {
+                let name = binding.name.unwrap();
+                match binding.ty {
+                    Type::Felt => vec![(name, None)],
+                    _ => (0..binding.size).map(|i| (name, Some(i))).collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut args = vec![];
+        for arg in call.args.iter().take(evaluator.params.len()) {
+            let elems = match arg {
+                Expr::Vector(ref elems) => elems.as_slice(),
+                arg => core::slice::from_ref(arg),
+            };
+            for elem in elems.iter() {
+                let Expr::SymbolAccess(ref access) = elem else {
+                    return;
+                };
+                let Some(columns) = access_columns(access) else {
+                    return;
+                };
+                args.extend(columns);
+            }
+        }
+        if args.len() != params.len() {
+            return;
+        }
+
+        for (param, arg) in params.iter().zip(args) {
+            if callee_binary.contains(param) {
+                binary.insert(arg);
+            }
+        }
+    }
+
+    /// Checks every `enf match` statement in `body`, given the set of columns known to be binary
+    fn check_block(&self, body: &[Statement], binary: &HashSet<Column>) {
+        for statement in body.iter() {
+            match statement {
+                Statement::Let(ref expr) => self.check_block(&expr.body, binary),
+                Statement::EnforceMatch(ref expr) => self.check_match(expr, binary),
+                _ => continue,
+            }
+        }
+    }
+
+    fn check_match(&self, expr: &Match, binary: &HashSet<Column>) {
+        let selectors = expr
+            .arms
+            .iter()
+            .map(|arm| arm.selector.as_ref().unwrap())
+            .collect::<Vec<_>>();
+
+        // Determine the set of values the selectors depend on, all of which must be binary
+        let mut inputs = vec![];
+        let mut is_provable = true;
+        for selector in selectors.iter() {
+            let mut accesses = vec![];
+            if !collect_inputs(selector, &mut accesses) {
+                self.diagnostics
+                    .diagnostic(Severity::Warning)
+                    .with_message("match selector is not provably binary")
+                    .with_primary_label(selector.span(), "unable to analyze this selector")
+                    .emit();
+                is_provable = false;
+                continue;
+            }
+            for access in accesses.into_iter() {
+                let column = access_column(access);
+                if inputs
+                    .iter()
+                    .any(|(input, _)| input == &(column, access.offset))
+                {
+                    continue;
+                }
+                if !binary.contains(&column) {
+                    self.diagnostics
+                        .diagnostic(Severity::Warning)
+                        .with_message("match selector is not provably binary")
+                        .with_primary_label(
+                            access.span(),
+                            "this value is not constrained to be binary",
+                        )
+                        .with_note(format!(
+                            "Consider adding a constraint such as `enf {0}^2 = {0}`",
+                            access
+                        ))
+                        .emit();
+                    is_provable = false;
+                }
+                inputs.push(((column, access.offset), access));
+            }
+        }
+        if !is_provable {
+            return;
+        }
+        if inputs.len() > MAX_SELECTOR_INPUTS {
+            self.diagnostics
+                .diagnostic(Severity::Warning)
+                .with_message("match selectors could not be analyzed")
+                .with_primary_label(
+                    expr.span(),
+                    format!(
+                        "the selectors of this match depend on more than {} values",
+                        MAX_SELECTOR_INPUTS
+                    ),
+                )
+                .emit();
+            return;
+        }
+
+        // Evaluate the selectors for every assignment of binary values to their inputs
+        let mut reported_selectors = HashSet::<usize>::default();
+        let mut reported_overlap = false;
+        for assignment in 0..(1usize << inputs.len()) {
+            let values = inputs
+                .iter()
+                .enumerate()
+                .map(|(i, (input, _))| (*input, ((assignment >> i) & 1) as i128))
+                .collect::<HashMap<Input, i128>>();
+            let describe = || {
+                inputs
+                    .iter()
+                    .map(|(input, access)| format!("{} = {}", access, values[input]))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            let mut selected: Option<usize> = None;
+            for (i, selector) in selectors.iter().enumerate() {
+                let value = eval(selector, &values);
+                if value != Some(0) && value != Some(1) {
+                    if reported_selectors.insert(i) {
+                        let label = match value {
+                            Some(value) => {
+                                format!("this selector evaluates to {} when {}", value, describe())
+                            }
+                            None => {
+                                format!("this selector cannot be evaluated when {}", describe())
+                            }
+                        };
+                        self.diagnostics
+                            .diagnostic(Severity::Warning)
+                            .with_message("match selector is not binary")
+                            .with_primary_label(selector.span(), label)
+                            .emit();
+                    }
+                    continue;
+                }
+                if value != Some(1) {
+                    continue;
+                }
+                match selected {
+                    None => selected = Some(i),
+                    Some(prev) if !reported_overlap => {
+                        reported_overlap = true;
+                        self.diagnostics
+                            .diagnostic(Severity::Warning)
+                            .with_message("match selectors are not mutually exclusive")
+                            .with_primary_label(
+                                selector.span(),
+                                format!("this selector is selected when {}", describe()),
+                            )
+                            .with_secondary_label(selectors[prev].span(), "but so is this selector")
+                            .emit();
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the column constrained to be binary by the constraint `lhs = rhs`, if it is such a constraint.
+///
+/// Rather than matching specific forms, e.g. `x^2 = x` or `x * (x - 1) = 0`, we check that `lhs - rhs`
+/// is a polynomial of degree at most two in a single column, which has roots at 0 and 1, and is not
+/// identically zero. Such a polynomial has no other roots, so the column must be binary.
+fn binary_constrained_column(lhs: &ScalarExpr, rhs: &ScalarExpr) -> Option<Column> {
+    let mut accesses = vec![];
+    if !collect_inputs(lhs, &mut accesses) || !collect_inputs(rhs, &mut accesses) {
+        return None;
+    }
+    let input = accesses
+        .first()
+        .map(|access| (access_column(access), access.offset))?;
+    if accesses
+        .iter()
+        .any(|access| (access_column(access), access.offset) != input)
+    {
+        return None;
+    }
+    if degree(lhs)?.max(degree(rhs)?) > 2 {
+        return None;
+    }
+
+    let value = |x: i128| {
+        let values = HashMap::from([(input, x)]);
+        Some(eval(lhs, &values)? - eval(rhs, &values)?)
+    };
+    if value(0)? == 0 && value(1)? == 0 && value(2)? != 0 {
+        Some(input.0)
+    } else {
+        None
+    }
+}
+
+/// Returns the column referenced by a scalar access
+fn access_column(access: &SymbolAccess) -> Column {
+    let name = *access.name.as_ref();
+    match access.access_type {
+        AccessType::Index(idx) => (name, Some(idx)),
+        _ => (name, None),
+    }
+}
+
+/// Returns the set of columns referenced by an access of a column or vector of columns
+fn access_columns(access: &SymbolAccess) -> Option<Vec<Column>> {
+    let name = *access.name.as_ref();
+    match (&access.access_type, access.ty?) {
+        (AccessType::Default, Type::Felt) => Some(vec![(name, None)]),
+        (AccessType::Index(idx), Type::Felt) => Some(vec![(name, Some(*idx))]),
+        (AccessType::Default, Type::Vector(len)) => {
+            Some((0..len).map(|i| (name, Some(i))).collect())
+        }
+        (AccessType::Slice(range), Type::Vector(_)) => {
+            Some(range.clone().map(|i| (name, Some(i))).collect())
+        }
+        _ => None,
+    }
+}
+
+/// Collects the scalar accesses in `expr` into `accesses`.
+///
+/// Returns false if `expr` contains anything other than constants, scalar accesses, and arithmetic.
+fn collect_inputs<'a>(expr: &'a ScalarExpr, accesses: &mut Vec<&'a SymbolAccess>) -> bool {
+    match expr {
+        ScalarExpr::Const(_) => true,
+        ScalarExpr::SymbolAccess(ref access) => match access.access_type {
+            AccessType::Default | AccessType::Index(_) => {
+                accesses.push(access);
+                true
+            }
+            _ => false,
+        },
+        ScalarExpr::Binary(ref expr) => {
+            collect_inputs(&expr.lhs, accesses) && collect_inputs(&expr.rhs, accesses)
+        }
+        ScalarExpr::BoundedSymbolAccess(_) | ScalarExpr::If(_) | ScalarExpr::Call(_) => false,
+    }
+}
+
+/// Returns an upper bound on the degree of `expr` as a polynomial, if it is one
+fn degree(expr: &ScalarExpr) -> Option<usize> {
+    match expr {
+        ScalarExpr::Const(_) => Some(0),
+        ScalarExpr::SymbolAccess(_) => Some(1),
+        ScalarExpr::Binary(BinaryExpr {
+            op,
+            ref lhs,
+            ref rhs,
+            ..
+        }) => match op {
+            BinaryOp::Add | BinaryOp::Sub => Some(degree(lhs)?.max(degree(rhs)?)),
+            BinaryOp::Mul => Some(degree(lhs)? + degree(rhs)?),
+            BinaryOp::Exp => match rhs.as_ref() {
+                ScalarExpr::Const(exp) => degree(lhs)?.checked_mul(exp.item as usize),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Evaluates `expr` over the integers, given the values of its inputs
+fn eval(expr: &ScalarExpr, values: &HashMap<Input, i128>) -> Option<i128> {
+    match expr {
+        ScalarExpr::Const(value) => Some(value.item as i128),
+        ScalarExpr::SymbolAccess(ref access) => {
+            values.get(&(access_column(access), access.offset)).copied()
+        }
+        ScalarExpr::Binary(BinaryExpr {
+            op,
+            ref lhs,
+            ref rhs,
+            ..
+        }) => {
+            let lhs = eval(lhs, values)?;
+            match op {
+                BinaryOp::Add => lhs.checked_add(eval(rhs, values)?),
+                BinaryOp::Sub => lhs.checked_sub(eval(rhs, values)?),
+                BinaryOp::Mul => lhs.checked_mul(eval(rhs, values)?),
+                BinaryOp::Exp => match rhs.as_ref() {
+                    ScalarExpr::Const(exp) => lhs.checked_pow(u32::try_from(exp.item).ok()?),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}